@@ -4,7 +4,9 @@ pub mod ai;
 pub mod persistence;
 pub mod pronunciation;
 pub mod speech;
+pub mod system;
 
 pub use ai::GenAiCardNormalizer;
 pub use pronunciation::AzurePronunciationAssessor;
 pub use speech::AiSpeechSynthesizer;
+pub use system::{SystemClock, SystemRandomSource};
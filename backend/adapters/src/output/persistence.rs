@@ -4,8 +4,10 @@ pub mod sqlite_ai_settings_repository;
 pub mod sqlite_card_repository;
 pub mod sqlite_language_profile_repository;
 pub mod sqlite_pronunciation_settings_repository;
+pub mod sqlite_review_log_repository;
 pub mod sqlite_speech_audio_repository;
 pub mod sqlite_study_session_repository;
+pub mod sqlite_study_statistics_repository;
 pub mod sqlite_user_repository;
 
 pub use sqlite_ai_settings_repository::{
@@ -18,10 +20,36 @@ pub use sqlite_language_profile_repository::{
 pub use sqlite_pronunciation_settings_repository::{
     SqlitePronunciationSettingsRepository, SqlitePronunciationSettingsRepositoryInitError,
 };
+pub use sqlite_review_log_repository::{
+    SqliteReviewLogRepository, SqliteReviewLogRepositoryInitError,
+};
 pub use sqlite_speech_audio_repository::{
     SqliteSpeechAudioRepository, SqliteSpeechAudioRepositoryInitError,
 };
 pub use sqlite_study_session_repository::{
     SqliteStudySessionRepository, SqliteStudySessionRepositoryInitError,
 };
+pub use sqlite_study_statistics_repository::{
+    SqliteStudyStatisticsRepository, SqliteStudyStatisticsRepositoryInitError,
+};
 pub use sqlite_user_repository::{SqliteUserRepository, SqliteUserRepositoryInitError};
+
+/// Adds a column introduced after `table` was first created in existing databases.
+pub(crate) fn add_missing_column(
+    connection: &rusqlite::Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> rusqlite::Result<()> {
+    let exists = connection.query_row(
+        "SELECT EXISTS(SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2)",
+        rusqlite::params![table, column],
+        |row| row.get::<_, bool>(0),
+    )?;
+    if !exists {
+        connection.execute_batch(&format!(
+            "ALTER TABLE {table} ADD COLUMN {column} {definition}"
+        ))?;
+    }
+    Ok(())
+}
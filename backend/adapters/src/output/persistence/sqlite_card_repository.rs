@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     fs,
     path::{Path, PathBuf},
     sync::{Arc, Mutex, MutexGuard},
@@ -7,8 +8,9 @@ use std::{
 use application::ports::{
     input::{
         card_catalog::models::{
-            Card, CardDirection, CardId, CardListCursor, CardOrder, CardPage, CardSelectionQuery,
-            CardSortField, CardSummary, ListCardsQuery, Meaning, PendingInverseCard, SortDirection,
+            Card, CardCandidate, CardDirection, CardId, CardListCursor, CardOrder, CardPage,
+            CardSchedule, CardSelectionQuery, CardSortField, CardStatus, CardSummary,
+            DueCardsQuery, LeechState, ListCardsQuery, Meaning, PendingInverseCard, SortDirection,
             UsageExample, Word,
         },
         language_profile::models::ProfileId,
@@ -22,6 +24,8 @@ use rusqlite::{
 };
 use thiserror::Error;
 
+use super::{add_missing_column, sqlite_review_log_repository::create_review_log};
+
 #[derive(Debug, Error)]
 pub enum SqliteCardRepositoryInitError {
     #[error("failed to create database directory {path:?}: {source}")]
@@ -115,6 +119,18 @@ impl SqliteCardRepository {
                         REFERENCES card_meanings(card_id, position) ON DELETE CASCADE
                 );
 
+                CREATE TABLE IF NOT EXISTS card_schedules (
+                    card_id TEXT PRIMARY KEY NOT NULL,
+                    ease_permille INTEGER NOT NULL,
+                    interval_days INTEGER NOT NULL,
+                    repetitions INTEGER NOT NULL,
+                    lapses INTEGER NOT NULL,
+                    due_at INTEGER NOT NULL,
+                    last_reviewed_at INTEGER NOT NULL,
+                    introduced_at INTEGER NOT NULL,
+                    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+                );
+
                 CREATE INDEX IF NOT EXISTS idx_cards_profile
                     ON cards(profile_id);
                 CREATE INDEX IF NOT EXISTS idx_cards_profile_created
@@ -123,9 +139,36 @@ impl SqliteCardRepository {
                     ON cards(profile_id, score, id);
                 CREATE INDEX IF NOT EXISTS idx_cards_profile_word
                     ON cards(profile_id, word_sort_key, id);
+                CREATE INDEX IF NOT EXISTS idx_card_schedules_due
+                    ON card_schedules(due_at);
                 ",
             )
             .map_err(SqliteCardRepositoryInitError::Initialize)?;
+        create_review_log(&connection).map_err(SqliteCardRepositoryInitError::Initialize)?;
+        add_missing_column(
+            &connection,
+            "cards",
+            "consecutive_failures",
+            "INTEGER NOT NULL DEFAULT 0",
+        )
+        .and_then(|()| {
+            add_missing_column(
+                &connection,
+                "cards",
+                "is_leech",
+                "INTEGER NOT NULL DEFAULT 0",
+            )
+        })
+        .and_then(|()| {
+            add_missing_column(
+                &connection,
+                "cards",
+                "suspended",
+                "INTEGER NOT NULL DEFAULT 0",
+            )
+        })
+        .and_then(|()| add_missing_column(&connection, "cards", "buried_until", "INTEGER"))
+        .map_err(SqliteCardRepositoryInitError::Initialize)?;
 
         Ok(Self {
             connection: Arc::new(Mutex::new(connection)),
@@ -179,6 +222,23 @@ impl SqliteCardRepository {
         }
     }
 
+    /// Splits a status into the `suspended` and `buried_until` columns.
+    fn status_columns(status: CardStatus) -> (bool, Option<i64>) {
+        match status {
+            CardStatus::Active => (false, None),
+            CardStatus::Suspended => (true, None),
+            CardStatus::Buried { until } => (false, Some(until)),
+        }
+    }
+
+    fn parse_status(suspended: bool, buried_until: Option<i64>) -> CardStatus {
+        match (suspended, buried_until) {
+            (true, _) => CardStatus::Suspended,
+            (false, Some(until)) => CardStatus::Buried { until },
+            (false, None) => CardStatus::Active,
+        }
+    }
+
     fn normalized_search_text(word: &Word) -> String {
         std::iter::once(word.text.as_str())
             .chain(word.readings.iter().map(String::as_str))
@@ -271,12 +331,14 @@ impl SqliteCardRepository {
 
     fn insert_card(transaction: &Transaction<'_>, card: &Card) -> Result<(), CardRepositoryError> {
         Self::ensure_word_available(transaction, &card.profile_id, &card.word.text, None)?;
+        let (suspended, buried_until) = Self::status_columns(card.status);
         transaction
             .execute(
                 "INSERT INTO cards (
                     id, profile_id, direction, word, word_sort_key, search_text,
-                    score, created_at, version
-                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                    score, consecutive_failures, is_leech, suspended, buried_until,
+                    created_at, version
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
                 params![
                     card.id.as_str(),
                     card.profile_id.as_str(),
@@ -285,6 +347,10 @@ impl SqliteCardRepository {
                     card.word.text.to_lowercase(),
                     Self::normalized_search_text(&card.word),
                     card.score,
+                    card.leech.consecutive_failures,
+                    card.leech.is_leech,
+                    suspended,
+                    buried_until,
                     card.created_at,
                     card.version,
                 ],
@@ -335,16 +401,22 @@ impl SqliteCardRepository {
             &card.word.text,
             Some(&card.id),
         )?;
+        let (suspended, buried_until) = Self::status_columns(card.status);
         let affected = transaction
             .execute(
                 "UPDATE cards
                  SET word = ?1, word_sort_key = ?2, search_text = ?3,
-                     version = version + 1
-                 WHERE id = ?4 AND profile_id = ?5 AND version = ?6",
+                     consecutive_failures = ?4, is_leech = ?5, suspended = ?6,
+                     buried_until = ?7, version = version + 1
+                 WHERE id = ?8 AND profile_id = ?9 AND version = ?10",
                 params![
                     card.word.text,
                     card.word.text.to_lowercase(),
                     Self::normalized_search_text(&card.word),
+                    card.leech.consecutive_failures,
+                    card.leech.is_leech,
+                    suspended,
+                    buried_until,
                     card.id.as_str(),
                     card.profile_id.as_str(),
                     expected_version,
@@ -371,100 +443,167 @@ impl SqliteCardRepository {
         Ok(card)
     }
 
-    fn read_card(connection: &Connection, card_id: &CardId) -> Result<Card, CardRepositoryError> {
-        let mut card = connection
-            .query_row(
-                "SELECT id, profile_id, direction, word, score, created_at, version
-                 FROM cards WHERE id = ?1",
-                params![card_id.as_str()],
-                |row| {
-                    Ok(Card {
-                        id: CardId::new(row.get::<_, String>(0)?),
-                        profile_id: ProfileId::new(row.get::<_, String>(1)?),
-                        direction: Self::parse_direction(row.get(2)?)?,
-                        word: Word {
-                            text: row.get(3)?,
-                            readings: Vec::new(),
-                        },
-                        meanings: Vec::new(),
-                        score: row.get(4)?,
-                        created_at: row.get(5)?,
-                        version: row.get(6)?,
-                    })
-                },
+    /// Loads the requested cards of a profile in request order, skipping missing ones. Child
+    /// rows are fetched for all cards at once, so the number of queries does not grow with
+    /// the number of cards.
+    fn read_cards(
+        connection: &Connection,
+        profile_id: &ProfileId,
+        card_ids: &[CardId],
+    ) -> Result<Vec<Card>, CardRepositoryError> {
+        if card_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids = serde_json::to_string(
+            &card_ids
+                .iter()
+                .map(|card_id| card_id.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .map_err(|_| CardRepositoryError::Unavailable)?;
+
+        let mut statement = connection
+            .prepare(
+                "SELECT id, profile_id, direction, word, score, consecutive_failures, is_leech,
+                        suspended, buried_until, created_at, version
+                 FROM cards
+                 WHERE profile_id = ?1 AND id IN (SELECT value FROM json_each(?2))",
             )
             .map_err(Self::map_sqlite_error)?;
+        let mut cards = statement
+            .query_map(params![profile_id.as_str(), ids], |row| {
+                Ok(Card {
+                    id: CardId::new(row.get::<_, String>(0)?),
+                    profile_id: ProfileId::new(row.get::<_, String>(1)?),
+                    direction: Self::parse_direction(row.get(2)?)?,
+                    word: Word {
+                        text: row.get(3)?,
+                        readings: Vec::new(),
+                    },
+                    meanings: Vec::new(),
+                    score: row.get(4)?,
+                    leech: LeechState {
+                        consecutive_failures: row.get(5)?,
+                        is_leech: row.get(6)?,
+                    },
+                    status: Self::parse_status(row.get(7)?, row.get(8)?),
+                    created_at: row.get(9)?,
+                    version: row.get(10)?,
+                })
+            })
+            .map_err(Self::map_sqlite_error)?
+            .map(|card| card.map(|card| (card.id.as_str().to_string(), card)))
+            .collect::<Result<HashMap<_, _>, _>>()
+            .map_err(Self::map_sqlite_error)?;
 
         let mut readings = connection
             .prepare(
-                "SELECT text FROM card_readings
-                 WHERE card_id = ?1 ORDER BY position",
+                "SELECT card_id, text FROM card_readings
+                 WHERE card_id IN (SELECT value FROM json_each(?1))
+                 ORDER BY card_id, position",
             )
             .map_err(Self::map_sqlite_error)?;
-        card.word.readings = readings
-            .query_map(params![card_id.as_str()], |row| row.get(0))
-            .map_err(Self::map_sqlite_error)?
-            .collect::<Result<_, _>>()
+        let reading_rows = readings
+            .query_map(params![ids], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
             .map_err(Self::map_sqlite_error)?;
+        for row in reading_rows {
+            let (card_id, text) = row.map_err(Self::map_sqlite_error)?;
+            if let Some(card) = cards.get_mut(&card_id) {
+                card.word.readings.push(text);
+            }
+        }
 
         let mut meanings = connection
             .prepare(
-                "SELECT position, definition, translated_definition
-                 FROM card_meanings WHERE card_id = ?1 ORDER BY position",
+                "SELECT card_id, definition, translated_definition FROM card_meanings
+                 WHERE card_id IN (SELECT value FROM json_each(?1))
+                 ORDER BY card_id, position",
             )
             .map_err(Self::map_sqlite_error)?;
         let meaning_rows = meanings
-            .query_map(params![card_id.as_str()], |row| {
+            .query_map(params![ids], |row| {
                 Ok((
-                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(0)?,
                     row.get::<_, String>(1)?,
                     row.get::<_, String>(2)?,
                 ))
             })
-            .map_err(Self::map_sqlite_error)?
-            .collect::<Result<Vec<_>, _>>()
             .map_err(Self::map_sqlite_error)?;
+        for row in meaning_rows {
+            let (card_id, definition, translated_definition) =
+                row.map_err(Self::map_sqlite_error)?;
+            if let Some(card) = cards.get_mut(&card_id) {
+                card.meanings.push(Meaning {
+                    definition,
+                    translated_definition,
+                    word_translations: Vec::new(),
+                    examples: Vec::new(),
+                });
+            }
+        }
 
-        for (position, definition, translated_definition) in meaning_rows {
-            let mut translations = connection
-                .prepare(
-                    "SELECT text FROM card_translations
-                     WHERE card_id = ?1 AND meaning_position = ?2
-                     ORDER BY position",
-                )
-                .map_err(Self::map_sqlite_error)?;
-            let word_translations = translations
-                .query_map(params![card_id.as_str(), position], |row| row.get(0))
-                .map_err(Self::map_sqlite_error)?
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(Self::map_sqlite_error)?;
-
-            let mut examples = connection
-                .prepare(
-                    "SELECT sentence, translation FROM card_examples
-                     WHERE card_id = ?1 AND meaning_position = ?2
-                     ORDER BY position",
-                )
-                .map_err(Self::map_sqlite_error)?;
-            let examples = examples
-                .query_map(params![card_id.as_str(), position], |row| {
-                    Ok(UsageExample {
-                        sentence: row.get(0)?,
-                        translation: row.get(1)?,
-                    })
-                })
-                .map_err(Self::map_sqlite_error)?
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(Self::map_sqlite_error)?;
+        // Meaning positions are contiguous from zero, so they index into `Card::meanings`.
+        let mut translations = connection
+            .prepare(
+                "SELECT card_id, meaning_position, text FROM card_translations
+                 WHERE card_id IN (SELECT value FROM json_each(?1))
+                 ORDER BY card_id, meaning_position, position",
+            )
+            .map_err(Self::map_sqlite_error)?;
+        let translation_rows = translations
+            .query_map(params![ids], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, usize>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .map_err(Self::map_sqlite_error)?;
+        for row in translation_rows {
+            let (card_id, position, text) = row.map_err(Self::map_sqlite_error)?;
+            if let Some(meaning) = cards
+                .get_mut(&card_id)
+                .and_then(|card| card.meanings.get_mut(position))
+            {
+                meaning.word_translations.push(text);
+            }
+        }
 
-            card.meanings.push(Meaning {
-                definition,
-                translated_definition,
-                word_translations,
-                examples,
-            });
+        let mut examples = connection
+            .prepare(
+                "SELECT card_id, meaning_position, sentence, translation FROM card_examples
+                 WHERE card_id IN (SELECT value FROM json_each(?1))
+                 ORDER BY card_id, meaning_position, position",
+            )
+            .map_err(Self::map_sqlite_error)?;
+        let example_rows = examples
+            .query_map(params![ids], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, usize>(1)?,
+                    UsageExample {
+                        sentence: row.get(2)?,
+                        translation: row.get(3)?,
+                    },
+                ))
+            })
+            .map_err(Self::map_sqlite_error)?;
+        for row in example_rows {
+            let (card_id, position, example) = row.map_err(Self::map_sqlite_error)?;
+            if let Some(meaning) = cards
+                .get_mut(&card_id)
+                .and_then(|card| card.meanings.get_mut(position))
+            {
+                meaning.examples.push(example);
+            }
         }
-        Ok(card)
+
+        Ok(card_ids
+            .iter()
+            .filter_map(|card_id| cards.remove(card_id.as_str()))
+            .collect())
     }
 
     fn encode_cursor(summary: &CardSummary, sort_field: CardSortField) -> CardListCursor {
@@ -561,6 +700,47 @@ impl CardRepository for SqliteCardRepository {
         .map_err(Self::map_join_error)?
     }
 
+    async fn set_status(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        card_ids: &[CardId],
+        status: CardStatus,
+    ) -> Result<usize, CardRepositoryError> {
+        let repository = self.clone();
+        let user_id = user_id.clone();
+        let profile_id = profile_id.clone();
+        let card_ids = card_ids.to_vec();
+        let (suspended, buried_until) = Self::status_columns(status);
+        tokio::task::spawn_blocking(move || {
+            let mut connection = repository.lock_connection()?;
+            if !Self::profile_belongs_to_user(&connection, &user_id, &profile_id)? {
+                return Err(CardRepositoryError::NotFound);
+            }
+            let transaction = connection.transaction().map_err(Self::map_sqlite_error)?;
+            let mut updated = 0;
+            for card_id in card_ids {
+                updated += transaction
+                    .execute(
+                        "UPDATE cards
+                         SET suspended = ?1, buried_until = ?2, version = version + 1
+                         WHERE id = ?3 AND profile_id = ?4",
+                        params![
+                            suspended,
+                            buried_until,
+                            card_id.as_str(),
+                            profile_id.as_str()
+                        ],
+                    )
+                    .map_err(Self::map_sqlite_error)?;
+            }
+            transaction.commit().map_err(Self::map_sqlite_error)?;
+            Ok(updated)
+        })
+        .await
+        .map_err(Self::map_join_error)?
+    }
+
     async fn find(
         &self,
         user_id: &UserId,
@@ -584,9 +764,10 @@ impl CardRepository for SqliteCardRepository {
                     |row| row.get::<_, bool>(0),
                 )
                 .map_err(Self::map_sqlite_error)?;
-            exists
-                .then(|| Self::read_card(&connection, &card_id))
-                .transpose()
+            if !exists {
+                return Ok(None);
+            }
+            Ok(Self::read_cards(&connection, &profile_id, std::slice::from_ref(&card_id))?.pop())
         })
         .await
         .map_err(Self::map_join_error)?
@@ -615,10 +796,10 @@ impl CardRepository for SqliteCardRepository {
                 )
                 .optional()
                 .map_err(Self::map_sqlite_error)?;
-            card_id
-                .map(CardId::new)
-                .map(|card_id| Self::read_card(&connection, &card_id))
-                .transpose()
+            let Some(card_id) = card_id.map(CardId::new) else {
+                return Ok(None);
+            };
+            Ok(Self::read_cards(&connection, &profile_id, std::slice::from_ref(&card_id))?.pop())
         })
         .await
         .map_err(Self::map_join_error)?
@@ -705,21 +886,23 @@ impl CardRepository for SqliteCardRepository {
                 SortDirection::Descending => "DESC",
             };
             let sql = format!(
-                "SELECT c.id, c.word, c.direction, c.score, c.created_at
+                "SELECT c.id, c.word, c.direction, c.score, c.consecutive_failures, c.is_leech,
+                        c.suspended, c.buried_until, c.created_at
                  FROM cards c
                  JOIN language_profiles p ON p.id = c.profile_id
                  WHERE p.user_id = ?1 AND c.profile_id = ?2
                    AND (?3 IS NULL OR c.direction = ?3)
                    AND (?4 IS NULL OR c.score >= ?4)
                    AND (?5 IS NULL OR c.score <= ?5)
-                   AND (?6 = '' OR c.search_text LIKE '%' || ?6 || '%')
+                   AND (?6 IS NULL OR c.is_leech = ?6)
+                   AND (?7 = '' OR c.search_text LIKE '%' || ?7 || '%')
                    AND (
-                     ?7 IS NULL OR
-                     ({sort_expression} {comparison} ?7) OR
-                     ({sort_expression} = ?7 AND c.id {comparison} ?8)
+                     ?8 IS NULL OR
+                     ({sort_expression} {comparison} ?8) OR
+                     ({sort_expression} = ?8 AND c.id {comparison} ?9)
                    )
                  ORDER BY {sort_expression} {order}, c.id {order}
-                 LIMIT ?9"
+                 LIMIT ?10"
             );
             let (cursor_value, cursor_id) = Self::decode_cursor(query.cursor, query.sort_field)?;
             let direction = query
@@ -736,6 +919,10 @@ impl CardRepository for SqliteCardRepository {
                 .max_score
                 .map(|value| Value::Integer(value.into()))
                 .unwrap_or(Value::Null);
+            let leech = query
+                .leech
+                .map(|is_leech| Value::Integer(is_leech.into()))
+                .unwrap_or(Value::Null);
             let search = query.search.unwrap_or_default().trim().to_lowercase();
             let values = vec![
                 Value::Text(query.user_id.as_str().to_string()),
@@ -743,6 +930,7 @@ impl CardRepository for SqliteCardRepository {
                 direction,
                 min_score,
                 max_score,
+                leech,
                 Value::Text(search),
                 cursor_value,
                 cursor_id,
@@ -756,7 +944,12 @@ impl CardRepository for SqliteCardRepository {
                         word: row.get(1)?,
                         direction: Self::parse_direction(row.get(2)?)?,
                         score: row.get(3)?,
-                        created_at: row.get(4)?,
+                        leech: LeechState {
+                            consecutive_failures: row.get(4)?,
+                            is_leech: row.get(5)?,
+                        },
+                        status: Self::parse_status(row.get(6)?, row.get(7)?),
+                        created_at: row.get(8)?,
                     })
                 })
                 .map_err(Self::map_sqlite_error)?
@@ -780,7 +973,7 @@ impl CardRepository for SqliteCardRepository {
     async fn select_for_session(
         &self,
         query: CardSelectionQuery,
-    ) -> Result<Vec<Card>, CardRepositoryError> {
+    ) -> Result<Vec<CardCandidate>, CardRepositoryError> {
         let repository = self.clone();
         tokio::task::spawn_blocking(move || {
             let connection = repository.lock_connection()?;
@@ -788,20 +981,33 @@ impl CardRepository for SqliteCardRepository {
                 return Err(CardRepositoryError::NotFound);
             }
             let order = match query.order {
-                CardOrder::OldestFirst => "c.created_at ASC, c.id ASC",
+                CardOrder::OldestFirst | CardOrder::ScoreWeighted => "c.created_at ASC, c.id ASC",
                 CardOrder::Random => "RANDOM()",
+                CardOrder::WeakestFirst => "c.score ASC, c.created_at ASC, c.id ASC",
+                CardOrder::LeastRecentlySeen => "last_seen_at ASC, c.created_at ASC, c.id ASC",
             };
             let limit = query
                 .limit
                 .map(|limit| limit.to_string())
                 .unwrap_or_else(|| "-1".to_string());
+            let excluded = (0..query.excluded_ids.len())
+                .map(|index| format!("?{}", index + 7))
+                .collect::<Vec<_>>()
+                .join(", ");
             let sql = format!(
-                "SELECT c.id FROM cards c
+                "SELECT c.id, c.direction, c.score,
+                        (SELECT MAX(l.reviewed_at) FROM review_log l
+                         WHERE l.card_id = c.id AND l.undone_at IS NULL)
+                            AS last_seen_at
+                 FROM cards c
                  JOIN language_profiles p ON p.id = c.profile_id
                  WHERE p.user_id = ?1 AND c.profile_id = ?2
                    AND (?3 IS NULL OR c.direction = ?3)
                    AND (?4 IS NULL OR c.score >= ?4)
                    AND (?5 IS NULL OR c.score <= ?5)
+                   AND c.suspended = 0
+                   AND (c.buried_until IS NULL OR c.buried_until <= ?6)
+                   AND c.id NOT IN ({excluded})
                  ORDER BY {order}
                  LIMIT {limit}"
             );
@@ -819,24 +1025,245 @@ impl CardRepository for SqliteCardRepository {
                 .max_score
                 .map(|value| Value::Integer(value.into()))
                 .unwrap_or(Value::Null);
-            let values = vec![
+            let mut values = vec![
                 Value::Text(query.user_id.as_str().to_string()),
                 Value::Text(query.profile_id.as_str().to_string()),
                 direction,
                 min_score,
                 max_score,
+                Value::Integer(query.available_at),
             ];
+            values.extend(
+                query
+                    .excluded_ids
+                    .iter()
+                    .map(|card_id| Value::Text(card_id.as_str().to_string())),
+            );
             let mut statement = connection.prepare(&sql).map_err(Self::map_sqlite_error)?;
-            let ids = statement
+            statement
                 .query_map(params_from_iter(values), |row| {
-                    row.get::<_, String>(0).map(CardId::new)
+                    Ok(CardCandidate {
+                        id: CardId::new(row.get::<_, String>(0)?),
+                        direction: Self::parse_direction(row.get(1)?)?,
+                        score: row.get(2)?,
+                        last_seen_at: row.get(3)?,
+                    })
                 })
                 .map_err(Self::map_sqlite_error)?
                 .collect::<Result<Vec<_>, _>>()
+                .map_err(Self::map_sqlite_error)
+        })
+        .await
+        .map_err(Self::map_join_error)?
+    }
+
+    async fn find_many(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        card_ids: &[CardId],
+    ) -> Result<Vec<Card>, CardRepositoryError> {
+        if card_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let repository = self.clone();
+        let user_id = user_id.clone();
+        let profile_id = profile_id.clone();
+        let card_ids = card_ids.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let connection = repository.lock_connection()?;
+            if !Self::profile_belongs_to_user(&connection, &user_id, &profile_id)? {
+                return Err(CardRepositoryError::NotFound);
+            }
+            Self::read_cards(&connection, &profile_id, &card_ids)
+        })
+        .await
+        .map_err(Self::map_join_error)?
+    }
+
+    async fn existing_ids(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        card_ids: &[CardId],
+    ) -> Result<Vec<CardId>, CardRepositoryError> {
+        if card_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let repository = self.clone();
+        let user_id = user_id.clone();
+        let profile_id = profile_id.clone();
+        let card_ids = card_ids.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let connection = repository.lock_connection()?;
+            if !Self::profile_belongs_to_user(&connection, &user_id, &profile_id)? {
+                return Err(CardRepositoryError::NotFound);
+            }
+            let mut statement = connection
+                .prepare("SELECT EXISTS(SELECT 1 FROM cards WHERE id = ?1 AND profile_id = ?2)")
+                .map_err(Self::map_sqlite_error)?;
+            let mut existing = Vec::with_capacity(card_ids.len());
+            for card_id in card_ids {
+                if statement
+                    .query_row(params![card_id.as_str(), profile_id.as_str()], |row| {
+                        row.get::<_, bool>(0)
+                    })
+                    .map_err(Self::map_sqlite_error)?
+                {
+                    existing.push(card_id);
+                }
+            }
+            Ok(existing)
+        })
+        .await
+        .map_err(Self::map_join_error)?
+    }
+
+    async fn find_schedules(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        card_ids: &[CardId],
+    ) -> Result<Vec<CardSchedule>, CardRepositoryError> {
+        if card_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let repository = self.clone();
+        let user_id = user_id.clone();
+        let profile_id = profile_id.clone();
+        let card_ids = card_ids.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let connection = repository.lock_connection()?;
+            if !Self::profile_belongs_to_user(&connection, &user_id, &profile_id)? {
+                return Err(CardRepositoryError::NotFound);
+            }
+            let ids = serde_json::to_string(
+                &card_ids
+                    .iter()
+                    .map(|card_id| card_id.as_str())
+                    .collect::<Vec<_>>(),
+            )
+            .map_err(|_| CardRepositoryError::Unavailable)?;
+            let mut statement = connection
+                .prepare(
+                    "SELECT s.card_id, s.ease_permille, s.interval_days, s.repetitions,
+                            s.lapses, s.due_at, s.last_reviewed_at, s.introduced_at
+                     FROM card_schedules s
+                     JOIN cards c ON c.id = s.card_id
+                     WHERE s.card_id IN (SELECT value FROM json_each(?1))
+                       AND c.profile_id = ?2",
+                )
                 .map_err(Self::map_sqlite_error)?;
-            ids.into_iter()
-                .map(|id| Self::read_card(&connection, &id))
-                .collect()
+            let mut found = statement
+                .query_map(params![ids, profile_id.as_str()], |row| {
+                    Ok(CardSchedule {
+                        card_id: CardId::new(row.get::<_, String>(0)?),
+                        ease_permille: row.get(1)?,
+                        interval_days: row.get(2)?,
+                        repetitions: row.get(3)?,
+                        lapses: row.get(4)?,
+                        due_at: row.get(5)?,
+                        last_reviewed_at: row.get(6)?,
+                        introduced_at: row.get(7)?,
+                    })
+                })
+                .map_err(Self::map_sqlite_error)?
+                .map(|schedule| schedule.map(|schedule| (schedule.card_id.clone(), schedule)))
+                .collect::<Result<HashMap<_, _>, _>>()
+                .map_err(Self::map_sqlite_error)?;
+            let schedules = card_ids
+                .iter()
+                .filter_map(|card_id| found.remove(card_id))
+                .collect();
+            Ok(schedules)
+        })
+        .await
+        .map_err(Self::map_join_error)?
+    }
+
+    async fn select_due(&self, query: DueCardsQuery) -> Result<Vec<CardId>, CardRepositoryError> {
+        let repository = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let connection = repository.lock_connection()?;
+            if !Self::profile_belongs_to_user(&connection, &query.user_id, &query.profile_id)? {
+                return Err(CardRepositoryError::NotFound);
+            }
+            let direction = query.direction.as_ref().map(Self::direction_name);
+            let mut statement = connection
+                .prepare(
+                    "SELECT id FROM (
+                        SELECT c.id, 0 AS bucket, s.due_at AS position, c.created_at
+                        FROM cards c
+                        JOIN card_schedules s ON s.card_id = c.id
+                        WHERE c.profile_id = ?1
+                          AND (?2 IS NULL OR c.direction = ?2)
+                          AND (?3 IS NULL OR c.score >= ?3)
+                          AND (?4 IS NULL OR c.score <= ?4)
+                          AND c.suspended = 0
+                          AND (c.buried_until IS NULL OR c.buried_until <= ?5)
+                          AND s.due_at <= ?5
+                        UNION ALL
+                        SELECT * FROM (
+                            SELECT c.id, 1 AS bucket, c.created_at AS position, c.created_at
+                            FROM cards c
+                            LEFT JOIN card_schedules s ON s.card_id = c.id
+                            WHERE c.profile_id = ?1
+                              AND (?2 IS NULL OR c.direction = ?2)
+                              AND (?3 IS NULL OR c.score >= ?3)
+                              AND (?4 IS NULL OR c.score <= ?4)
+                              AND c.suspended = 0
+                              AND (c.buried_until IS NULL OR c.buried_until <= ?5)
+                              AND s.card_id IS NULL
+                            ORDER BY c.created_at ASC, c.id ASC
+                            LIMIT ?6
+                        )
+                     )
+                     ORDER BY bucket ASC, position ASC, created_at ASC, id ASC",
+                )
+                .map_err(Self::map_sqlite_error)?;
+            statement
+                .query_map(
+                    params![
+                        query.profile_id.as_str(),
+                        direction,
+                        query.min_score,
+                        query.max_score,
+                        query.due_at,
+                        query.new_card_limit as i64,
+                    ],
+                    |row| row.get::<_, String>(0).map(CardId::new),
+                )
+                .map_err(Self::map_sqlite_error)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(Self::map_sqlite_error)
+        })
+        .await
+        .map_err(Self::map_join_error)?
+    }
+
+    async fn count_introduced_since(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        since: i64,
+    ) -> Result<usize, CardRepositoryError> {
+        let repository = self.clone();
+        let user_id = user_id.clone();
+        let profile_id = profile_id.clone();
+        tokio::task::spawn_blocking(move || {
+            let connection = repository.lock_connection()?;
+            if !Self::profile_belongs_to_user(&connection, &user_id, &profile_id)? {
+                return Err(CardRepositoryError::NotFound);
+            }
+            connection
+                .query_row(
+                    "SELECT COUNT(*) FROM card_schedules s
+                     JOIN cards c ON c.id = s.card_id
+                     WHERE c.profile_id = ?1 AND s.introduced_at >= ?2",
+                    params![profile_id.as_str(), since],
+                    |row| row.get::<_, usize>(0),
+                )
+                .map_err(Self::map_sqlite_error)
         })
         .await
         .map_err(Self::map_join_error)?
@@ -845,16 +1272,19 @@ impl CardRepository for SqliteCardRepository {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use application::ports::{
         input::{
             card_catalog::models::{CardSortField, SortDirection},
-            language_profile::models::{LanguageProfile, ProfileId},
+            language_profile::models::{LanguageProfile, LeechAction, ProfileId},
             local_user::models::{LocalUser, UserId},
         },
         output::repository::{
             card::CardRepository, language_profile::LanguageProfileRepository, user::UserRepository,
         },
     };
+    use rusqlite::trace::TraceEventCodes;
     use tempfile::TempDir;
 
     use crate::output::persistence::{SqliteLanguageProfileRepository, SqliteUserRepository};
@@ -881,6 +1311,8 @@ mod tests {
                 name: "Japanese".to_string(),
                 source_language: "en-US".to_string(),
                 target_language: "ja-JP".to_string(),
+                leech_threshold: 8,
+                leech_action: LeechAction::Suspend,
                 version: 0,
             })
             .await
@@ -915,6 +1347,8 @@ mod tests {
                 }],
             }],
             score,
+            leech: LeechState::default(),
+            status: CardStatus::Active,
             created_at,
             version: 0,
         }
@@ -928,6 +1362,7 @@ mod tests {
             direction: None,
             min_score: None,
             max_score: None,
+            leech: None,
             sort_field: CardSortField::CreatedAt,
             sort_direction: SortDirection::Descending,
             cursor: None,
@@ -996,6 +1431,82 @@ mod tests {
         assert_eq!(loaded.version, 1);
     }
 
+    #[tokio::test]
+    async fn due_queue_puts_overdue_cards_first_and_caps_new_cards() {
+        let (_directory, database_path, repository) = setup().await;
+        let cards = vec![
+            card("late", "遅い", "おそい", CardDirection::Straight, 0, 1),
+            card("later", "後", "あと", CardDirection::Straight, 0, 2),
+            card("future", "未来", "みらい", CardDirection::Straight, 0, 3),
+            card("new-old", "古い", "ふるい", CardDirection::Straight, 0, 4),
+            card("new-young", "若い", "わかい", CardDirection::Straight, 0, 5),
+            card("reverse", "逆", "ぎゃく", CardDirection::Reverse, 0, 6),
+        ];
+        repository
+            .insert_batch(&UserId::new("alice"), &ProfileId::new("profile"), cards)
+            .await
+            .unwrap();
+        let connection = Connection::open(&database_path).unwrap();
+        for (card_id, due_at, introduced_at) in
+            [("late", 50, 10), ("later", 10, 90), ("future", 500, 95)]
+        {
+            connection
+                .execute(
+                    "INSERT INTO card_schedules (
+                        card_id, ease_permille, interval_days, repetitions, lapses,
+                        due_at, last_reviewed_at, introduced_at
+                     ) VALUES (?1, 2500, 1, 1, 0, ?2, ?3, ?3)",
+                    params![card_id, due_at, introduced_at],
+                )
+                .unwrap();
+        }
+        let query = DueCardsQuery {
+            user_id: UserId::new("alice"),
+            profile_id: ProfileId::new("profile"),
+            direction: Some(CardDirection::Straight),
+            min_score: None,
+            max_score: None,
+            due_at: 100,
+            new_card_limit: 1,
+        };
+
+        assert_eq!(
+            repository.select_due(query.clone()).await.unwrap(),
+            vec![
+                CardId::new("later"),
+                CardId::new("late"),
+                CardId::new("new-old")
+            ]
+        );
+        assert_eq!(
+            repository
+                .select_due(DueCardsQuery {
+                    new_card_limit: 0,
+                    ..query.clone()
+                })
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            repository
+                .count_introduced_since(&UserId::new("alice"), &ProfileId::new("profile"), 90)
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            repository
+                .select_due(DueCardsQuery {
+                    user_id: UserId::new("bob"),
+                    ..query
+                })
+                .await,
+            Err(CardRepositoryError::NotFound)
+        );
+    }
+
     #[tokio::test]
     async fn keeps_words_unique_across_directions() {
         let (_directory, _database_path, repository) = setup().await;
@@ -1083,6 +1594,375 @@ mod tests {
         assert!(third_page.next_cursor.is_none());
     }
 
+    #[tokio::test]
+    async fn leeches_are_listed_on_request_and_suspended_ones_are_not_studied() {
+        let (_directory, _database_path, repository) = setup().await;
+        let mut tagged = card("tagged", "tagged", "", CardDirection::Straight, -9, 1);
+        tagged.leech = LeechState {
+            consecutive_failures: 8,
+            is_leech: true,
+        };
+        let mut suspended = card("suspended", "suspended", "", CardDirection::Straight, -9, 2);
+        suspended.leech = LeechState {
+            consecutive_failures: 8,
+            is_leech: true,
+        };
+        suspended.status = CardStatus::Suspended;
+        let healthy = card("healthy", "healthy", "", CardDirection::Straight, 0, 3);
+        repository
+            .insert_batch(
+                &UserId::new("alice"),
+                &ProfileId::new("profile"),
+                vec![tagged, suspended.clone(), healthy],
+            )
+            .await
+            .unwrap();
+        let words = |page: CardPage| {
+            page.items
+                .into_iter()
+                .map(|card| card.word)
+                .collect::<Vec<_>>()
+        };
+
+        let mut leeches = list_query();
+        leeches.leech = Some(true);
+        let page = repository.list_summaries(leeches).await.unwrap();
+        assert_eq!(page.items[0].leech, suspended.leech);
+        assert_eq!(words(page), vec!["suspended", "tagged"]);
+        let mut others = list_query();
+        others.leech = Some(false);
+        assert_eq!(
+            words(repository.list_summaries(others).await.unwrap()),
+            vec!["healthy"]
+        );
+
+        let selected = repository
+            .select_for_session(CardSelectionQuery {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new("profile"),
+                direction: None,
+                min_score: None,
+                max_score: None,
+                order: CardOrder::OldestFirst,
+                limit: None,
+                available_at: 0,
+                excluded_ids: Vec::new(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            selected
+                .into_iter()
+                .map(|card| card.id.into_inner())
+                .collect::<Vec<_>>(),
+            vec!["tagged", "healthy"]
+        );
+        assert_eq!(
+            repository
+                .select_due(DueCardsQuery {
+                    user_id: UserId::new("alice"),
+                    profile_id: ProfileId::new("profile"),
+                    direction: None,
+                    min_score: None,
+                    max_score: None,
+                    due_at: 0,
+                    new_card_limit: 10,
+                })
+                .await
+                .unwrap(),
+            vec![CardId::new("tagged"), CardId::new("healthy")]
+        );
+    }
+
+    #[tokio::test]
+    async fn buried_cards_return_once_their_time_has_come() {
+        let (_directory, _database_path, repository) = setup().await;
+        let user_id = UserId::new("alice");
+        let profile_id = ProfileId::new("profile");
+        repository
+            .insert_batch(
+                &user_id,
+                &profile_id,
+                ["one", "two", "three"]
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, id)| card(id, id, "", CardDirection::Straight, 0, index as i64))
+                    .collect(),
+            )
+            .await
+            .unwrap();
+        let one = [CardId::new("one")];
+        let two = [CardId::new("two")];
+
+        assert_eq!(
+            repository
+                .set_status(&user_id, &profile_id, &one, CardStatus::Suspended)
+                .await,
+            Ok(1)
+        );
+        assert_eq!(
+            repository
+                .set_status(
+                    &user_id,
+                    &profile_id,
+                    &two,
+                    CardStatus::Buried { until: 100 }
+                )
+                .await,
+            Ok(1)
+        );
+        let buried = repository
+            .find(&user_id, &profile_id, &two[0])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(buried.status, CardStatus::Buried { until: 100 });
+        assert_eq!(buried.version, 1);
+        let selected = |available_at| {
+            let repository = repository.clone();
+            async move {
+                repository
+                    .select_for_session(CardSelectionQuery {
+                        user_id: UserId::new("alice"),
+                        profile_id: ProfileId::new("profile"),
+                        direction: None,
+                        min_score: None,
+                        max_score: None,
+                        order: CardOrder::OldestFirst,
+                        limit: None,
+                        available_at,
+                        excluded_ids: Vec::new(),
+                    })
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|card| card.id.into_inner())
+                    .collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(selected(99).await, vec!["three"]);
+        assert_eq!(selected(100).await, vec!["two", "three"]);
+        let due = |due_at| DueCardsQuery {
+            user_id: UserId::new("alice"),
+            profile_id: ProfileId::new("profile"),
+            direction: None,
+            min_score: None,
+            max_score: None,
+            due_at,
+            new_card_limit: 10,
+        };
+        assert_eq!(
+            repository.select_due(due(99)).await.unwrap(),
+            vec![CardId::new("three")]
+        );
+
+        repository
+            .set_status(&user_id, &profile_id, &one, CardStatus::Active)
+            .await
+            .unwrap();
+        assert_eq!(selected(0).await, vec!["one", "three"]);
+        assert_eq!(
+            repository
+                .set_status(
+                    &UserId::new("bob"),
+                    &profile_id,
+                    &one,
+                    CardStatus::Suspended
+                )
+                .await,
+            Err(CardRepositoryError::NotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn session_selection_orders_by_score_or_by_last_answer() {
+        let (_directory, database_path, repository) = setup().await;
+        let cards = vec![
+            card("strong", "強い", "つよい", CardDirection::Straight, 9, 1),
+            card("weak", "弱い", "よわい", CardDirection::Straight, -4, 2),
+            card("middle", "中", "なか", CardDirection::Straight, 0, 3),
+            card(
+                "unseen",
+                "新しい",
+                "あたらしい",
+                CardDirection::Straight,
+                3,
+                4,
+            ),
+        ];
+        repository
+            .insert_batch(&UserId::new("alice"), &ProfileId::new("profile"), cards)
+            .await
+            .unwrap();
+        let connection = Connection::open(&database_path).unwrap();
+        for (card_id, reviewed_at) in [("strong", 10), ("weak", 30), ("middle", 20), ("weak", 5)] {
+            connection
+                .execute(
+                    "INSERT INTO review_log (
+                        profile_id, session_id, card_id, mode, direction, outcome,
+                        is_correct, score_delta, reviewed_at
+                     ) VALUES ('profile', 'session', ?1, 'test', 'straight', 'correct', 1, 1, ?2)",
+                    params![card_id, reviewed_at],
+                )
+                .unwrap();
+        }
+        let selected = |order| {
+            let repository = repository.clone();
+            async move {
+                repository
+                    .select_for_session(CardSelectionQuery {
+                        user_id: UserId::new("alice"),
+                        profile_id: ProfileId::new("profile"),
+                        direction: None,
+                        min_score: None,
+                        max_score: None,
+                        order,
+                        limit: None,
+                        available_at: 0,
+                        excluded_ids: Vec::new(),
+                    })
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|card| card.id.into_inner())
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(
+            selected(CardOrder::WeakestFirst).await,
+            vec!["weak", "middle", "unseen", "strong"]
+        );
+        assert_eq!(
+            selected(CardOrder::LeastRecentlySeen).await,
+            vec!["unseen", "strong", "middle", "weak"]
+        );
+        assert_eq!(
+            selected(CardOrder::ScoreWeighted).await,
+            vec!["strong", "weak", "middle", "unseen"]
+        );
+
+        let candidates = repository
+            .select_for_session(CardSelectionQuery {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new("profile"),
+                direction: None,
+                min_score: None,
+                max_score: None,
+                order: CardOrder::LeastRecentlySeen,
+                limit: None,
+                available_at: 0,
+                excluded_ids: vec![CardId::new("unseen"), CardId::new("middle")],
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            candidates,
+            vec![
+                CardCandidate {
+                    id: CardId::new("strong"),
+                    direction: CardDirection::Straight,
+                    score: 9,
+                    last_seen_at: Some(10),
+                },
+                CardCandidate {
+                    id: CardId::new("weak"),
+                    direction: CardDirection::Straight,
+                    score: -4,
+                    last_seen_at: Some(30),
+                },
+            ]
+        );
+        let loaded = repository
+            .find_many(
+                &UserId::new("alice"),
+                &ProfileId::new("profile"),
+                &[
+                    CardId::new("weak"),
+                    CardId::new("missing"),
+                    CardId::new("strong"),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            loaded
+                .iter()
+                .map(|card| (card.id.as_str(), card.word.readings.len()))
+                .collect::<Vec<_>>(),
+            vec![("weak", 1), ("strong", 1)]
+        );
+        assert_eq!(
+            repository
+                .find_many(
+                    &UserId::new("bob"),
+                    &ProfileId::new("profile"),
+                    &[CardId::new("weak")]
+                )
+                .await,
+            Err(CardRepositoryError::NotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn loading_many_cards_takes_a_fixed_number_of_queries() {
+        static STATEMENTS: AtomicUsize = AtomicUsize::new(0);
+        let (_directory, _database_path, repository) = setup().await;
+        let user_id = UserId::new("alice");
+        let profile_id = ProfileId::new("profile");
+        let mut cards = (0..3_000)
+            .map(|index| {
+                let id = format!("card-{index}");
+                card(&id, &id, "reading", CardDirection::Straight, 0, index)
+            })
+            .collect::<Vec<_>>();
+        cards[1].meanings.push(Meaning {
+            definition: "second definition".to_string(),
+            translated_definition: "second translated definition".to_string(),
+            word_translations: vec!["second".to_string(), "other".to_string()],
+            examples: Vec::new(),
+        });
+        repository
+            .insert_batch(&user_id, &profile_id, cards.clone())
+            .await
+            .unwrap();
+        repository.lock_connection().unwrap().trace_v2(
+            TraceEventCodes::SQLITE_TRACE_STMT,
+            Some(|_| {
+                STATEMENTS.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+        let load = |count: usize| {
+            let repository = repository.clone();
+            let card_ids = cards
+                .iter()
+                .take(count)
+                .rev()
+                .map(|card| card.id.clone())
+                .collect::<Vec<_>>();
+            async move {
+                let before = STATEMENTS.load(Ordering::SeqCst);
+                let loaded = repository
+                    .find_many(&UserId::new("alice"), &ProfileId::new("profile"), &card_ids)
+                    .await
+                    .unwrap();
+                let statements = STATEMENTS.load(Ordering::SeqCst) - before;
+                (loaded, statements)
+            }
+        };
+
+        let (few, few_statements) = load(10).await;
+        let (all, all_statements) = load(cards.len()).await;
+
+        assert_eq!(few.len(), 10);
+        assert_eq!(few[0], cards[9]);
+        assert_eq!(few[8], cards[1]);
+        assert_eq!(all.len(), cards.len());
+        assert_eq!(all.last(), cards.first());
+        assert_eq!(all_statements, few_statements);
+        assert!(all_statements <= 6, "{all_statements} statements");
+    }
+
     #[tokio::test]
     async fn rejects_duplicates_and_cascades_deletion() {
         let (_directory, _database_path, repository) = setup().await;
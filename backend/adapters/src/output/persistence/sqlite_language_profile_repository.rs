@@ -6,7 +6,7 @@ use std::{
 
 use application::ports::{
     input::{
-        language_profile::models::{LanguageProfile, ProfileId},
+        language_profile::models::{LanguageProfile, LeechAction, ProfileId},
         local_user::models::UserId,
     },
     output::repository::language_profile::{
@@ -17,6 +17,8 @@ use async_trait::async_trait;
 use rusqlite::{Connection, ErrorCode, OptionalExtension, params};
 use thiserror::Error;
 
+use super::add_missing_column;
+
 #[derive(Debug, Error)]
 pub enum SqliteLanguageProfileRepositoryInitError {
     #[error("failed to create database directory {path:?}: {source}")]
@@ -85,6 +87,21 @@ impl SqliteLanguageProfileRepository {
                 ",
             )
             .map_err(SqliteLanguageProfileRepositoryInitError::Initialize)?;
+        add_missing_column(
+            &connection,
+            "language_profiles",
+            "leech_threshold",
+            "INTEGER NOT NULL DEFAULT 8",
+        )
+        .and_then(|()| {
+            add_missing_column(
+                &connection,
+                "language_profiles",
+                "leech_action",
+                "TEXT NOT NULL DEFAULT 'suspend'",
+            )
+        })
+        .map_err(SqliteLanguageProfileRepositoryInitError::Initialize)?;
 
         Ok(Self {
             connection: Arc::new(Mutex::new(connection)),
@@ -124,6 +141,21 @@ impl SqliteLanguageProfileRepository {
         ))
     }
 
+    fn leech_action_name(action: LeechAction) -> &'static str {
+        match action {
+            LeechAction::Suspend => "suspend",
+            LeechAction::Tag => "tag",
+        }
+    }
+
+    fn parse_leech_action(value: String) -> rusqlite::Result<LeechAction> {
+        match value.as_str() {
+            "suspend" => Ok(LeechAction::Suspend),
+            "tag" => Ok(LeechAction::Tag),
+            _ => Err(rusqlite::Error::InvalidQuery),
+        }
+    }
+
     fn read_profile(row: &rusqlite::Row<'_>) -> rusqlite::Result<LanguageProfile> {
         Ok(LanguageProfile {
             id: ProfileId::new(row.get::<_, String>(0)?),
@@ -131,7 +163,9 @@ impl SqliteLanguageProfileRepository {
             name: row.get(2)?,
             source_language: row.get(3)?,
             target_language: row.get(4)?,
-            version: row.get(5)?,
+            leech_threshold: row.get(5)?,
+            leech_action: Self::parse_leech_action(row.get(6)?)?,
+            version: row.get(7)?,
         })
     }
 }
@@ -149,14 +183,17 @@ impl LanguageProfileRepository for SqliteLanguageProfileRepository {
                 .lock_connection()?
                 .execute(
                     "INSERT INTO language_profiles (
-                        id, user_id, name, source_language, target_language, version
-                     ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        id, user_id, name, source_language, target_language,
+                        leech_threshold, leech_action, version
+                     ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
                     params![
                         stored.id.as_str(),
                         stored.owner_id.as_str(),
                         stored.name,
                         stored.source_language,
                         stored.target_language,
+                        stored.leech_threshold,
+                        Self::leech_action_name(stored.leech_action),
                         stored.version,
                     ],
                 )
@@ -179,7 +216,8 @@ impl LanguageProfileRepository for SqliteLanguageProfileRepository {
             repository
                 .lock_connection()?
                 .query_row(
-                    "SELECT id, user_id, name, source_language, target_language, version
+                    "SELECT id, user_id, name, source_language, target_language,
+                            leech_threshold, leech_action, version
                      FROM language_profiles
                      WHERE user_id = ?1 AND id = ?2",
                     params![user_id.as_str(), profile_id.as_str()],
@@ -202,7 +240,8 @@ impl LanguageProfileRepository for SqliteLanguageProfileRepository {
             let connection = repository.lock_connection()?;
             let mut statement = connection
                 .prepare(
-                    "SELECT id, user_id, name, source_language, target_language, version
+                    "SELECT id, user_id, name, source_language, target_language,
+                            leech_threshold, leech_action, version
                      FROM language_profiles
                      WHERE user_id = ?1
                      ORDER BY name ASC",
@@ -230,12 +269,14 @@ impl LanguageProfileRepository for SqliteLanguageProfileRepository {
                 .execute(
                     "UPDATE language_profiles
                      SET name = ?1, source_language = ?2, target_language = ?3,
-                         version = version + 1
-                     WHERE user_id = ?4 AND id = ?5 AND version = ?6",
+                         leech_threshold = ?4, leech_action = ?5, version = version + 1
+                     WHERE user_id = ?6 AND id = ?7 AND version = ?8",
                     params![
                         profile.name,
                         profile.source_language,
                         profile.target_language,
+                        profile.leech_threshold,
+                        Self::leech_action_name(profile.leech_action),
                         profile.owner_id.as_str(),
                         profile.id.as_str(),
                         expected_version,
@@ -308,6 +349,8 @@ mod tests {
             name: "Japanese".to_string(),
             source_language: "en-US".to_string(),
             target_language: "ja-JP".to_string(),
+            leech_threshold: 5,
+            leech_action: LeechAction::Tag,
             version: 0,
         };
         repository.insert(profile.clone()).await.unwrap();
@@ -0,0 +1,252 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use application::ports::{
+    input::{
+        card_catalog::models::{CardDirection, CardId},
+        study_session::models::{SessionId, StudySessionMode},
+    },
+    output::repository::review_log::{
+        ReviewLogRepository,
+        models::{ReviewLogEntry, ReviewLogQuery, ReviewLogRepositoryError, ReviewOutcome},
+    },
+};
+use async_trait::async_trait;
+use rusqlite::{Connection, ErrorCode, params};
+use thiserror::Error;
+
+use super::add_missing_column;
+
+/// Schema of the review log. The study session repository appends rows and, when it undoes a
+/// transition, sets their `undone_at`; no other column changes once a row is written.
+const REVIEW_LOG_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS review_log (
+        sequence INTEGER PRIMARY KEY AUTOINCREMENT,
+        profile_id TEXT NOT NULL,
+        session_id TEXT NOT NULL,
+        card_id TEXT NOT NULL,
+        mode TEXT NOT NULL,
+        direction TEXT NOT NULL,
+        submitted_answer TEXT,
+        matched_translation TEXT,
+        outcome TEXT NOT NULL,
+        is_correct INTEGER NOT NULL,
+        score_delta INTEGER NOT NULL,
+        pronunciation_strict_score INTEGER,
+        response_time_millis INTEGER,
+        reviewed_at INTEGER NOT NULL,
+        undone_at INTEGER,
+        regrades_sequence INTEGER,
+        FOREIGN KEY (profile_id) REFERENCES language_profiles(id) ON DELETE CASCADE
+    );
+
+    CREATE INDEX IF NOT EXISTS idx_review_log_profile_reviewed
+        ON review_log(profile_id, reviewed_at);
+    CREATE INDEX IF NOT EXISTS idx_review_log_card_reviewed
+        ON review_log(card_id, reviewed_at);
+";
+
+/// Creates the review log, or brings the one of an existing database up to date. Answers
+/// logged before outcomes were recorded could not be given up, so those without an answer
+/// failed their pronunciation check.
+pub(super) fn create_review_log(connection: &Connection) -> rusqlite::Result<()> {
+    connection.execute_batch(REVIEW_LOG_SCHEMA)?;
+    add_missing_column(connection, "review_log", "outcome", "TEXT")?;
+    add_missing_column(connection, "review_log", "undone_at", "INTEGER")?;
+    add_missing_column(connection, "review_log", "regrades_sequence", "INTEGER")?;
+    connection.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_review_log_regrades
+            ON review_log(regrades_sequence);
+
+         UPDATE review_log
+         SET outcome = CASE
+            WHEN is_correct THEN 'correct'
+            WHEN submitted_answer IS NULL THEN 'pronunciation_failed'
+            ELSE 'incorrect'
+         END
+         WHERE outcome IS NULL;",
+    )
+}
+
+pub(super) fn outcome_name(outcome: ReviewOutcome) -> &'static str {
+    match outcome {
+        ReviewOutcome::Correct => "correct",
+        ReviewOutcome::Incorrect => "incorrect",
+        ReviewOutcome::GaveUp => "gave_up",
+        ReviewOutcome::PronunciationFailed => "pronunciation_failed",
+        ReviewOutcome::Accepted => "accepted",
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SqliteReviewLogRepositoryInitError {
+    #[error("failed to create database directory {path:?}: {source}")]
+    CreateDirectory {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to open review log database: {0}")]
+    Open(#[source] rusqlite::Error),
+    #[error("failed to initialize review log database: {0}")]
+    Initialize(#[source] rusqlite::Error),
+}
+
+#[derive(Clone)]
+pub struct SqliteReviewLogRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteReviewLogRepository {
+    pub fn new(
+        database_path: impl AsRef<Path>,
+    ) -> Result<Self, SqliteReviewLogRepositoryInitError> {
+        let database_path = database_path.as_ref();
+        if let Some(parent) = database_path
+            .parent()
+            .filter(|path| !path.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|source| {
+                SqliteReviewLogRepositoryInitError::CreateDirectory {
+                    path: parent.to_path_buf(),
+                    source,
+                }
+            })?;
+        }
+
+        let connection =
+            Connection::open(database_path).map_err(SqliteReviewLogRepositoryInitError::Open)?;
+        connection
+            .execute_batch("PRAGMA foreign_keys = ON;")
+            .and_then(|()| create_review_log(&connection))
+            .map_err(SqliteReviewLogRepositoryInitError::Initialize)?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>, ReviewLogRepositoryError> {
+        self.connection
+            .lock()
+            .map_err(|_| ReviewLogRepositoryError::Unavailable)
+    }
+
+    fn map_error(error: rusqlite::Error) -> ReviewLogRepositoryError {
+        match &error {
+            rusqlite::Error::SqliteFailure(details, _)
+                if matches!(
+                    details.code,
+                    ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked
+                ) =>
+            {
+                ReviewLogRepositoryError::Unavailable
+            }
+            _ => ReviewLogRepositoryError::Unexpected(error.to_string()),
+        }
+    }
+
+    fn map_join(error: tokio::task::JoinError) -> ReviewLogRepositoryError {
+        ReviewLogRepositoryError::Unexpected(format!("review log repository task failed: {error}"))
+    }
+
+    fn parse_mode(value: String) -> rusqlite::Result<StudySessionMode> {
+        match value.as_str() {
+            "learning" => Ok(StudySessionMode::Learning),
+            "test" => Ok(StudySessionMode::Test),
+            "review" => Ok(StudySessionMode::Review),
+            _ => Err(rusqlite::Error::InvalidQuery),
+        }
+    }
+
+    fn parse_outcome(value: String) -> rusqlite::Result<ReviewOutcome> {
+        match value.as_str() {
+            "correct" => Ok(ReviewOutcome::Correct),
+            "incorrect" => Ok(ReviewOutcome::Incorrect),
+            "gave_up" => Ok(ReviewOutcome::GaveUp),
+            "pronunciation_failed" => Ok(ReviewOutcome::PronunciationFailed),
+            "accepted" => Ok(ReviewOutcome::Accepted),
+            _ => Err(rusqlite::Error::InvalidQuery),
+        }
+    }
+
+    fn parse_direction(value: String) -> rusqlite::Result<CardDirection> {
+        match value.as_str() {
+            "straight" => Ok(CardDirection::Straight),
+            "reverse" => Ok(CardDirection::Reverse),
+            _ => Err(rusqlite::Error::InvalidQuery),
+        }
+    }
+}
+
+#[async_trait]
+impl ReviewLogRepository for SqliteReviewLogRepository {
+    async fn list(
+        &self,
+        query: ReviewLogQuery,
+    ) -> Result<Vec<ReviewLogEntry>, ReviewLogRepositoryError> {
+        let repository = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let connection = repository.lock()?;
+            let owned = connection
+                .query_row(
+                    "SELECT EXISTS(
+                        SELECT 1 FROM language_profiles WHERE id = ?1 AND user_id = ?2
+                    )",
+                    params![query.profile_id.as_str(), query.user_id.as_str()],
+                    |row| row.get::<_, bool>(0),
+                )
+                .map_err(Self::map_error)?;
+            if !owned {
+                return Err(ReviewLogRepositoryError::NotFound);
+            }
+            let mut statement = connection
+                .prepare(
+                    "SELECT session_id, card_id, mode, direction, submitted_answer,
+                            matched_translation, outcome, is_correct, score_delta,
+                            pronunciation_strict_score, response_time_millis, reviewed_at
+                     FROM review_log
+                     WHERE profile_id = ?1
+                       AND undone_at IS NULL
+                       AND (?2 IS NULL OR card_id = ?2)
+                       AND (?3 IS NULL OR reviewed_at >= ?3)
+                     ORDER BY reviewed_at DESC, sequence DESC
+                     LIMIT ?4",
+                )
+                .map_err(Self::map_error)?;
+            statement
+                .query_map(
+                    params![
+                        query.profile_id.as_str(),
+                        query.card_id.as_ref().map(CardId::as_str),
+                        query.since,
+                        query.limit as i64,
+                    ],
+                    |row| {
+                        Ok(ReviewLogEntry {
+                            session_id: SessionId::new(row.get::<_, String>(0)?),
+                            card_id: CardId::new(row.get::<_, String>(1)?),
+                            mode: Self::parse_mode(row.get(2)?)?,
+                            direction: Self::parse_direction(row.get(3)?)?,
+                            submitted_answer: row.get(4)?,
+                            matched_translation: row.get(5)?,
+                            outcome: Self::parse_outcome(row.get(6)?)?,
+                            is_correct: row.get(7)?,
+                            score_delta: row.get(8)?,
+                            pronunciation_strict_score: row.get(9)?,
+                            response_time_millis: row.get(10)?,
+                            reviewed_at: row.get(11)?,
+                        })
+                    },
+                )
+                .map_err(Self::map_error)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(Self::map_error)
+        })
+        .await
+        .map_err(Self::map_join)?
+    }
+}
@@ -213,9 +213,11 @@ impl SpeechAudioRepository for SqliteSpeechAudioRepository {
 mod tests {
     use application::ports::{
         input::{
-            card_catalog::models::{Card, CardDirection, CardId, Meaning, Word},
+            card_catalog::models::{
+                Card, CardDirection, CardId, CardStatus, LeechState, Meaning, Word,
+            },
             card_speech::models::SpeechAudio,
-            language_profile::models::{LanguageProfile, ProfileId},
+            language_profile::models::{LanguageProfile, LeechAction, ProfileId},
             local_user::models::{LocalUser, UserId},
         },
         output::repository::{
@@ -253,6 +255,8 @@ mod tests {
                 name: "Japanese".to_string(),
                 source_language: "en-US".to_string(),
                 target_language: "ja-JP".to_string(),
+                leech_threshold: 8,
+                leech_action: LeechAction::Suspend,
                 version: 0,
             })
             .await
@@ -277,6 +281,8 @@ mod tests {
                         examples: vec![],
                     }],
                     score: 0,
+                    leech: LeechState::default(),
+                    status: CardStatus::Active,
                     created_at: 1,
                     version: 0,
                 }],
@@ -343,7 +349,7 @@ mod tests {
         );
 
         cards
-            .delete_batch(&owner, &profile, &[card.clone()])
+            .delete_batch(&owner, &profile, std::slice::from_ref(&card))
             .await
             .unwrap();
         assert!(
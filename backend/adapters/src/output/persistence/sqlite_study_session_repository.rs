@@ -6,19 +6,27 @@ use std::{
 
 use application::ports::{
     input::{
-        card_catalog::models::{CardDirection, CardId},
+        card_catalog::models::{
+            CardDirection, CardId, CardOrder, CardSchedule, CardStatus, LeechState,
+        },
         language_profile::models::ProfileId,
         local_user::models::UserId,
         study_session::models::{
-            PronunciationAssessmentIssue, PronunciationAssessmentReport, SessionAnswerResult,
-            SessionFilter, SessionId, StudySession, StudySessionMode, StudySessionPhase,
-            StudySessionPreferences, StudySessionStatus,
+            AnswerFormat, AnswerNormalization, AnswerOutcome, AnswerStrictness, GradedAnswer,
+            PronunciationAssessmentIssue, PronunciationAssessmentReport, RecordedTransition,
+            SessionAnswerResult, SessionFilter, SessionHint, SessionId, StudySession,
+            StudySessionEvent, StudySessionMode, StudySessionPhase, StudySessionPreferences,
+            StudySessionStatus,
         },
     },
-    output::repository::study_session::{
-        StudySessionRepository,
-        models::{
-            EndSessionRequest, StoreSessionRequest, StudySessionCommit, StudySessionRepositoryError,
+    output::repository::{
+        review_log::models::ReviewOutcome,
+        study_session::{
+            StudySessionRepository,
+            models::{
+                AcceptedTranslation, EndSessionRequest, StoreSessionRequest, StudySessionCommit,
+                StudySessionRepositoryError, UndoTransitionRequest,
+            },
         },
     },
 };
@@ -27,6 +35,14 @@ use rusqlite::{Connection, ErrorCode, OptionalExtension, params};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use super::{
+    add_missing_column,
+    sqlite_review_log_repository::{create_review_log, outcome_name},
+};
+
+/// Number of recent transitions of an active session that can be undone.
+const UNDO_HISTORY_LIMIT: usize = 10;
+
 #[derive(Debug, Error)]
 pub enum SqliteStudySessionRepositoryInitError {
     #[error("failed to create database directory {path:?}: {source}")]
@@ -52,10 +68,19 @@ struct StoredResult {
     card_id: String,
     word: String,
     is_correct: bool,
+    /// Absent from results stored before outcomes were recorded.
+    #[serde(default)]
+    outcome: Option<String>,
     submitted_answers: Vec<String>,
     #[serde(default)]
     pronunciation_reports: Vec<StoredPronunciationReport>,
     score_delta: i32,
+    #[serde(default)]
+    hints_used: Vec<String>,
+    #[serde(default)]
+    answered_at: Option<i64>,
+    #[serde(default)]
+    response_time_millis: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -83,6 +108,123 @@ struct StoredPronunciationReport {
     passed: bool,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredSchedule {
+    card_id: String,
+    ease_permille: u32,
+    interval_days: u32,
+    repetitions: u32,
+    lapses: u32,
+    due_at: i64,
+    last_reviewed_at: i64,
+    introduced_at: i64,
+}
+
+impl StoredSchedule {
+    fn from_domain(schedule: &CardSchedule) -> Self {
+        Self {
+            card_id: schedule.card_id.as_str().to_string(),
+            ease_permille: schedule.ease_permille,
+            interval_days: schedule.interval_days,
+            repetitions: schedule.repetitions,
+            lapses: schedule.lapses,
+            due_at: schedule.due_at,
+            last_reviewed_at: schedule.last_reviewed_at,
+            introduced_at: schedule.introduced_at,
+        }
+    }
+
+    fn into_domain(self) -> CardSchedule {
+        CardSchedule {
+            card_id: CardId::new(self.card_id),
+            ease_permille: self.ease_permille,
+            interval_days: self.interval_days,
+            repetitions: self.repetitions,
+            lapses: self.lapses,
+            due_at: self.due_at,
+            last_reviewed_at: self.last_reviewed_at,
+            introduced_at: self.introduced_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredLeechState {
+    consecutive_failures: u32,
+    is_leech: bool,
+}
+
+impl StoredLeechState {
+    fn from_domain(leech: &LeechState) -> Self {
+        Self {
+            consecutive_failures: leech.consecutive_failures,
+            is_leech: leech.is_leech,
+        }
+    }
+
+    fn into_domain(self) -> LeechState {
+        LeechState {
+            consecutive_failures: self.consecutive_failures,
+            is_leech: self.is_leech,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum StoredCardStatus {
+    Active,
+    Suspended,
+    Buried { until: i64 },
+}
+
+impl StoredCardStatus {
+    fn from_domain(status: CardStatus) -> Self {
+        match status {
+            CardStatus::Active => Self::Active,
+            CardStatus::Suspended => Self::Suspended,
+            CardStatus::Buried { until } => Self::Buried { until },
+        }
+    }
+
+    fn into_domain(self) -> CardStatus {
+        match self {
+            Self::Active => CardStatus::Active,
+            Self::Suspended => CardStatus::Suspended,
+            Self::Buried { until } => CardStatus::Buried { until },
+        }
+    }
+}
+
+/// What a committed transition changed on one card, kept to undo the transition.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredProgressUndo {
+    card_id: String,
+    score_delta: i32,
+    replaced_schedule: bool,
+    /// The schedule before the transition; `None` when the card had never been reviewed.
+    previous_schedule: Option<StoredSchedule>,
+    /// The leech state before the transition; `None` when the transition kept it.
+    #[serde(default)]
+    previous_leech: Option<StoredLeechState>,
+    /// The status before the transition; `None` when the transition kept it.
+    #[serde(default)]
+    previous_status: Option<StoredCardStatus>,
+    /// The translation the transition added to the card; `None` when it added none.
+    #[serde(default)]
+    added_translation: Option<StoredTranslationPosition>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredTranslationPosition {
+    meaning_position: usize,
+    position: usize,
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "camelCase")]
 enum StoredPronunciationIssue {
@@ -97,6 +239,240 @@ enum StoredPronunciationIssue {
     },
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum StoredEvent {
+    Started {
+        session: Box<StoredSession>,
+    },
+    StudyCardShown {
+        index: usize,
+    },
+    MiniTestStarted {
+        test_order: Vec<String>,
+    },
+    HintRevealed {
+        hint: String,
+    },
+    AnswerGraded(Box<StoredGradedAnswer>),
+    AnswerAccepted {
+        answer: String,
+        meaning_index: Option<usize>,
+        score_delta: i32,
+    },
+    PronunciationAttempted {
+        report: StoredPronunciationReport,
+    },
+    PronunciationCaptureFailed,
+    PronunciationDisabled,
+    FeedbackDismissed {
+        next_test_card: Option<String>,
+    },
+    CardPresented {
+        at: i64,
+    },
+    SetPassed,
+    SetRetried,
+    MissedCardsRetried {
+        card_ids: Vec<String>,
+    },
+    CardsRemoved {
+        card_ids: Vec<String>,
+        next_test_card: Option<String>,
+    },
+    Undone,
+    Ended {
+        status: String,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredGradedAnswer {
+    card_id: String,
+    word: String,
+    answer: Option<String>,
+    outcome: String,
+    matched_answer: Option<String>,
+    similarity_percent: u8,
+    meaning_index: Option<usize>,
+    card_completed: bool,
+    remaining_meanings: usize,
+    score_delta: i32,
+    misplaced_stress: bool,
+    normalizations: Vec<String>,
+    schedule_before_answer: Option<StoredSchedule>,
+    leech_before_answer: StoredLeechState,
+    #[serde(default)]
+    answered_at: i64,
+    #[serde(default)]
+    response_time_millis: Option<u64>,
+}
+
+impl StoredEvent {
+    fn from_domain(event: &StudySessionEvent) -> Self {
+        match event {
+            StudySessionEvent::Started { session } => Self::Started {
+                session: Box::new(StoredSession::from_domain(session)),
+            },
+            StudySessionEvent::StudyCardShown { index } => Self::StudyCardShown { index: *index },
+            StudySessionEvent::MiniTestStarted { test_order } => Self::MiniTestStarted {
+                test_order: test_order
+                    .iter()
+                    .map(|id| id.as_str().to_string())
+                    .collect(),
+            },
+            StudySessionEvent::HintRevealed { hint } => Self::HintRevealed {
+                hint: hint_name(*hint).to_string(),
+            },
+            StudySessionEvent::AnswerGraded(graded) => {
+                Self::AnswerGraded(Box::new(StoredGradedAnswer {
+                    card_id: graded.card_id.as_str().to_string(),
+                    word: graded.word.clone(),
+                    answer: graded.answer.clone(),
+                    outcome: answer_outcome_name(graded.outcome).to_string(),
+                    matched_answer: graded.matched_answer.clone(),
+                    similarity_percent: graded.similarity_percent,
+                    meaning_index: graded.meaning_index,
+                    card_completed: graded.card_completed,
+                    remaining_meanings: graded.remaining_meanings,
+                    score_delta: graded.score_delta,
+                    misplaced_stress: graded.misplaced_stress,
+                    normalizations: graded
+                        .normalizations
+                        .iter()
+                        .map(|normalization| answer_normalization_name(*normalization).to_string())
+                        .collect(),
+                    schedule_before_answer: graded
+                        .schedule_before_answer
+                        .as_ref()
+                        .map(StoredSchedule::from_domain),
+                    leech_before_answer: StoredLeechState::from_domain(&graded.leech_before_answer),
+                    answered_at: graded.answered_at,
+                    response_time_millis: graded.response_time_millis,
+                }))
+            }
+            StudySessionEvent::AnswerAccepted {
+                answer,
+                meaning_index,
+                score_delta,
+            } => Self::AnswerAccepted {
+                answer: answer.clone(),
+                meaning_index: *meaning_index,
+                score_delta: *score_delta,
+            },
+            StudySessionEvent::PronunciationAttempted { report } => Self::PronunciationAttempted {
+                report: StoredPronunciationReport::from_domain(report),
+            },
+            StudySessionEvent::PronunciationCaptureFailed => Self::PronunciationCaptureFailed,
+            StudySessionEvent::PronunciationDisabled => Self::PronunciationDisabled,
+            StudySessionEvent::FeedbackDismissed { next_test_card } => Self::FeedbackDismissed {
+                next_test_card: next_test_card.as_ref().map(|id| id.as_str().to_string()),
+            },
+            StudySessionEvent::CardPresented { at } => Self::CardPresented { at: *at },
+            StudySessionEvent::SetPassed => Self::SetPassed,
+            StudySessionEvent::SetRetried => Self::SetRetried,
+            StudySessionEvent::MissedCardsRetried { card_ids } => Self::MissedCardsRetried {
+                card_ids: card_ids.iter().map(|id| id.as_str().to_string()).collect(),
+            },
+            StudySessionEvent::CardsRemoved {
+                card_ids,
+                next_test_card,
+            } => Self::CardsRemoved {
+                card_ids: card_ids.iter().map(|id| id.as_str().to_string()).collect(),
+                next_test_card: next_test_card.as_ref().map(|id| id.as_str().to_string()),
+            },
+            StudySessionEvent::Undone => Self::Undone,
+            StudySessionEvent::Ended { status } => Self::Ended {
+                status: status_name(*status).to_string(),
+            },
+        }
+    }
+
+    fn into_domain(self) -> Result<StudySessionEvent, StudySessionRepositoryError> {
+        let invalid = || {
+            StudySessionRepositoryError::Unexpected(
+                "invalid persisted study session event".to_string(),
+            )
+        };
+        Ok(match self {
+            Self::Started { session } => StudySessionEvent::Started {
+                session: Box::new(session.into_domain()?),
+            },
+            Self::StudyCardShown { index } => StudySessionEvent::StudyCardShown { index },
+            Self::MiniTestStarted { test_order } => StudySessionEvent::MiniTestStarted {
+                test_order: test_order.into_iter().map(CardId::new).collect(),
+            },
+            Self::HintRevealed { hint } => StudySessionEvent::HintRevealed {
+                hint: parse_hint(&hint).ok_or_else(invalid)?,
+            },
+            Self::AnswerGraded(graded) => StudySessionEvent::AnswerGraded(Box::new(GradedAnswer {
+                card_id: CardId::new(graded.card_id),
+                word: graded.word,
+                answer: graded.answer,
+                outcome: parse_answer_outcome(&graded.outcome).ok_or_else(invalid)?,
+                matched_answer: graded.matched_answer,
+                similarity_percent: graded.similarity_percent,
+                meaning_index: graded.meaning_index,
+                card_completed: graded.card_completed,
+                remaining_meanings: graded.remaining_meanings,
+                score_delta: graded.score_delta,
+                misplaced_stress: graded.misplaced_stress,
+                normalizations: graded
+                    .normalizations
+                    .iter()
+                    .map(|normalization| parse_answer_normalization(normalization))
+                    .collect::<Option<_>>()
+                    .ok_or_else(invalid)?,
+                schedule_before_answer: graded
+                    .schedule_before_answer
+                    .map(StoredSchedule::into_domain),
+                leech_before_answer: graded.leech_before_answer.into_domain(),
+                answered_at: graded.answered_at,
+                response_time_millis: graded.response_time_millis,
+            })),
+            Self::AnswerAccepted {
+                answer,
+                meaning_index,
+                score_delta,
+            } => StudySessionEvent::AnswerAccepted {
+                answer,
+                meaning_index,
+                score_delta,
+            },
+            Self::PronunciationAttempted { report } => StudySessionEvent::PronunciationAttempted {
+                report: report.into_domain(),
+            },
+            Self::PronunciationCaptureFailed => StudySessionEvent::PronunciationCaptureFailed,
+            Self::PronunciationDisabled => StudySessionEvent::PronunciationDisabled,
+            Self::FeedbackDismissed { next_test_card } => StudySessionEvent::FeedbackDismissed {
+                next_test_card: next_test_card.map(CardId::new),
+            },
+            Self::CardPresented { at } => StudySessionEvent::CardPresented { at },
+            Self::SetPassed => StudySessionEvent::SetPassed,
+            Self::SetRetried => StudySessionEvent::SetRetried,
+            Self::MissedCardsRetried { card_ids } => StudySessionEvent::MissedCardsRetried {
+                card_ids: card_ids.into_iter().map(CardId::new).collect(),
+            },
+            Self::CardsRemoved {
+                card_ids,
+                next_test_card,
+            } => StudySessionEvent::CardsRemoved {
+                card_ids: card_ids.into_iter().map(CardId::new).collect(),
+                next_test_card: next_test_card.map(CardId::new),
+            },
+            Self::Undone => StudySessionEvent::Undone,
+            Self::Ended { status } => StudySessionEvent::Ended {
+                status: parse_status(&status).ok_or_else(invalid)?,
+            },
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct StoredSession {
@@ -109,13 +485,33 @@ struct StoredSession {
     direction: Option<String>,
     min_score: Option<i32>,
     max_score: Option<i32>,
+    #[serde(default = "default_answer_format")]
+    answer_format: String,
+    #[serde(default = "default_answer_strictness")]
+    answer_strictness: String,
     pronunciation_check_enabled: bool,
     #[serde(alias = "pronunciationAccuracyThreshold")]
     pronunciation_score_threshold: u8,
     cards_per_set: usize,
+    #[serde(default)]
+    retry_missed_only: bool,
+    #[serde(default = "default_card_order")]
+    card_order: String,
+    #[serde(default = "default_recent_ban_window")]
+    recent_ban_window: usize,
+    #[serde(default)]
+    max_cards: Option<usize>,
+    #[serde(default)]
+    ends_at: Option<i64>,
+    #[serde(default)]
+    correct_streak_goal: Option<usize>,
     card_ids: Vec<String>,
     test_order: Vec<String>,
+    #[serde(default)]
+    seed: u64,
     current_set_index: usize,
+    #[serde(default)]
+    retry_card_ids: Vec<String>,
     current_card_index: usize,
     provided_answers: Vec<String>,
     completed_meaning_indices: Vec<usize>,
@@ -128,6 +524,16 @@ struct StoredSession {
     #[serde(default)]
     pronunciation_disable_required: bool,
     awaiting_continue: bool,
+    #[serde(default)]
+    retype_pending: bool,
+    #[serde(default)]
+    schedule_before_answer: Option<StoredSchedule>,
+    #[serde(default)]
+    leech_before_answer: Option<StoredLeechState>,
+    #[serde(default)]
+    hints_used: Vec<String>,
+    #[serde(default)]
+    card_presented_at: Option<i64>,
     current_set_failed: bool,
     results: Vec<StoredResult>,
     version: u64,
@@ -142,6 +548,7 @@ impl StoredSession {
             mode: match session.mode {
                 StudySessionMode::Learning => "learning",
                 StudySessionMode::Test => "test",
+                StudySessionMode::Review => "review",
             }
             .to_string(),
             phase: match session.phase {
@@ -159,9 +566,17 @@ impl StoredSession {
             }),
             min_score: session.filter.min_score,
             max_score: session.filter.max_score,
+            answer_format: answer_format_name(session.answer_format).to_string(),
+            answer_strictness: answer_strictness_name(session.answer_strictness).to_string(),
             pronunciation_check_enabled: session.pronunciation_check_enabled,
             pronunciation_score_threshold: session.pronunciation_score_threshold,
             cards_per_set: session.cards_per_set,
+            retry_missed_only: session.retry_missed_only,
+            card_order: card_order_name(session.card_order).to_string(),
+            recent_ban_window: session.recent_ban_window,
+            max_cards: session.max_cards,
+            ends_at: session.ends_at,
+            correct_streak_goal: session.correct_streak_goal,
             card_ids: session
                 .card_ids
                 .iter()
@@ -172,7 +587,13 @@ impl StoredSession {
                 .iter()
                 .map(|id| id.as_str().to_string())
                 .collect(),
+            seed: session.seed,
             current_set_index: session.current_set_index,
+            retry_card_ids: session
+                .retry_card_ids
+                .iter()
+                .map(|id| id.as_str().to_string())
+                .collect(),
             current_card_index: session.current_card_index,
             provided_answers: session.provided_answers.clone(),
             completed_meaning_indices: session.completed_meaning_indices.clone(),
@@ -185,6 +606,17 @@ impl StoredSession {
             pronunciation_technical_failures: session.pronunciation_technical_failures,
             pronunciation_disable_required: session.pronunciation_disable_required,
             awaiting_continue: session.awaiting_continue,
+            retype_pending: session.retype_pending,
+            schedule_before_answer: session
+                .schedule_before_answer
+                .as_ref()
+                .map(StoredSchedule::from_domain),
+            leech_before_answer: session
+                .leech_before_answer
+                .as_ref()
+                .map(StoredLeechState::from_domain),
+            hints_used: hint_names(&session.hints_used),
+            card_presented_at: session.card_presented_at,
             current_set_failed: session.current_set_failed,
             results: session
                 .results
@@ -193,6 +625,7 @@ impl StoredSession {
                     card_id: result.card_id.as_str().to_string(),
                     word: result.word.clone(),
                     is_correct: result.is_correct,
+                    outcome: Some(answer_outcome_name(result.outcome).to_string()),
                     submitted_answers: result.submitted_answers.clone(),
                     pronunciation_reports: result
                         .pronunciation_reports
@@ -200,6 +633,9 @@ impl StoredSession {
                         .map(StoredPronunciationReport::from_domain)
                         .collect(),
                     score_delta: result.score_delta,
+                    hints_used: hint_names(&result.hints_used),
+                    answered_at: result.answered_at,
+                    response_time_millis: result.response_time_millis,
                 })
                 .collect(),
             version: session.version,
@@ -217,6 +653,7 @@ impl StoredSession {
             mode: match self.mode.as_str() {
                 "learning" => StudySessionMode::Learning,
                 "test" => StudySessionMode::Test,
+                "review" => StudySessionMode::Review,
                 _ => return Err(invalid()),
             },
             phase: match self.phase.as_str() {
@@ -235,12 +672,23 @@ impl StoredSession {
                 min_score: self.min_score,
                 max_score: self.max_score,
             },
+            answer_format: parse_answer_format(&self.answer_format).ok_or_else(invalid)?,
+            answer_strictness: parse_answer_strictness(&self.answer_strictness)
+                .ok_or_else(invalid)?,
             pronunciation_check_enabled: self.pronunciation_check_enabled,
             pronunciation_score_threshold: self.pronunciation_score_threshold,
             cards_per_set: self.cards_per_set,
+            retry_missed_only: self.retry_missed_only,
+            card_order: parse_card_order(&self.card_order).ok_or_else(invalid)?,
+            recent_ban_window: self.recent_ban_window,
+            max_cards: self.max_cards,
+            ends_at: self.ends_at,
+            correct_streak_goal: self.correct_streak_goal,
             card_ids: self.card_ids.into_iter().map(CardId::new).collect(),
             test_order: self.test_order.into_iter().map(CardId::new).collect(),
+            seed: self.seed,
             current_set_index: self.current_set_index,
+            retry_card_ids: self.retry_card_ids.into_iter().map(CardId::new).collect(),
             current_card_index: self.current_card_index,
             provided_answers: self.provided_answers,
             completed_meaning_indices: self.completed_meaning_indices,
@@ -253,23 +701,40 @@ impl StoredSession {
             pronunciation_technical_failures: self.pronunciation_technical_failures,
             pronunciation_disable_required: self.pronunciation_disable_required,
             awaiting_continue: self.awaiting_continue,
+            retype_pending: self.retype_pending,
+            schedule_before_answer: self.schedule_before_answer.map(StoredSchedule::into_domain),
+            leech_before_answer: self.leech_before_answer.map(StoredLeechState::into_domain),
+            hints_used: parse_hints(&self.hints_used).ok_or_else(invalid)?,
+            card_presented_at: self.card_presented_at,
             current_set_failed: self.current_set_failed,
             results: self
                 .results
                 .into_iter()
-                .map(|result| SessionAnswerResult {
-                    card_id: CardId::new(result.card_id),
-                    word: result.word,
-                    is_correct: result.is_correct,
-                    submitted_answers: result.submitted_answers,
-                    pronunciation_reports: result
-                        .pronunciation_reports
-                        .into_iter()
-                        .map(StoredPronunciationReport::into_domain)
-                        .collect(),
-                    score_delta: result.score_delta,
+                .map(|result| {
+                    let outcome = match result.outcome.as_deref() {
+                        Some(outcome) => parse_answer_outcome(outcome)?,
+                        None if result.is_correct => AnswerOutcome::Correct,
+                        None => AnswerOutcome::Incorrect,
+                    };
+                    Some(SessionAnswerResult {
+                        card_id: CardId::new(result.card_id),
+                        word: result.word,
+                        is_correct: result.is_correct,
+                        outcome,
+                        submitted_answers: result.submitted_answers,
+                        pronunciation_reports: result
+                            .pronunciation_reports
+                            .into_iter()
+                            .map(StoredPronunciationReport::into_domain)
+                            .collect(),
+                        score_delta: result.score_delta,
+                        hints_used: parse_hints(&result.hints_used)?,
+                        answered_at: result.answered_at,
+                        response_time_millis: result.response_time_millis,
+                    })
                 })
-                .collect(),
+                .collect::<Option<_>>()
+                .ok_or_else(invalid)?,
             version: self.version,
         })
     }
@@ -367,6 +832,7 @@ fn mode_name(mode: StudySessionMode) -> &'static str {
     match mode {
         StudySessionMode::Learning => "learning",
         StudySessionMode::Test => "test",
+        StudySessionMode::Review => "review",
     }
 }
 
@@ -377,6 +843,154 @@ fn direction_name(direction: CardDirection) -> &'static str {
     }
 }
 
+fn answer_format_name(format: AnswerFormat) -> &'static str {
+    match format {
+        AnswerFormat::Written => "written",
+        AnswerFormat::MultipleChoice => "multipleChoice",
+        AnswerFormat::Dictation => "dictation",
+        AnswerFormat::Cloze => "cloze",
+        AnswerFormat::Reading => "reading",
+    }
+}
+
+fn parse_answer_format(value: &str) -> Option<AnswerFormat> {
+    match value {
+        "written" => Some(AnswerFormat::Written),
+        "multipleChoice" => Some(AnswerFormat::MultipleChoice),
+        "dictation" => Some(AnswerFormat::Dictation),
+        "cloze" => Some(AnswerFormat::Cloze),
+        "reading" => Some(AnswerFormat::Reading),
+        _ => None,
+    }
+}
+
+fn default_answer_format() -> String {
+    answer_format_name(AnswerFormat::Written).to_string()
+}
+
+fn card_order_name(order: CardOrder) -> &'static str {
+    match order {
+        CardOrder::OldestFirst => "oldestFirst",
+        CardOrder::Random => "random",
+        CardOrder::WeakestFirst => "weakestFirst",
+        CardOrder::ScoreWeighted => "scoreWeighted",
+        CardOrder::LeastRecentlySeen => "leastRecentlySeen",
+    }
+}
+
+fn parse_card_order(value: &str) -> Option<CardOrder> {
+    match value {
+        "oldestFirst" => Some(CardOrder::OldestFirst),
+        "random" => Some(CardOrder::Random),
+        "weakestFirst" => Some(CardOrder::WeakestFirst),
+        "scoreWeighted" => Some(CardOrder::ScoreWeighted),
+        "leastRecentlySeen" => Some(CardOrder::LeastRecentlySeen),
+        _ => None,
+    }
+}
+
+/// Test sessions stored before the order was configurable picked cards at random.
+fn default_card_order() -> String {
+    card_order_name(CardOrder::Random).to_string()
+}
+
+fn default_recent_ban_window() -> usize {
+    10
+}
+
+fn answer_outcome_name(outcome: AnswerOutcome) -> &'static str {
+    match outcome {
+        AnswerOutcome::Correct => "correct",
+        AnswerOutcome::AlmostCorrect => "almostCorrect",
+        AnswerOutcome::Incorrect => "incorrect",
+        AnswerOutcome::GaveUp => "gaveUp",
+    }
+}
+
+fn parse_answer_outcome(value: &str) -> Option<AnswerOutcome> {
+    match value {
+        "correct" => Some(AnswerOutcome::Correct),
+        "almostCorrect" => Some(AnswerOutcome::AlmostCorrect),
+        "incorrect" => Some(AnswerOutcome::Incorrect),
+        "gaveUp" => Some(AnswerOutcome::GaveUp),
+        _ => None,
+    }
+}
+
+fn hint_name(hint: SessionHint) -> &'static str {
+    match hint {
+        SessionHint::FirstLetter => "firstLetter",
+        SessionHint::WordLength => "wordLength",
+        SessionHint::TranslatedDefinition => "translatedDefinition",
+        SessionHint::ExampleTranslation => "exampleTranslation",
+    }
+}
+
+fn parse_hint(value: &str) -> Option<SessionHint> {
+    match value {
+        "firstLetter" => Some(SessionHint::FirstLetter),
+        "wordLength" => Some(SessionHint::WordLength),
+        "translatedDefinition" => Some(SessionHint::TranslatedDefinition),
+        "exampleTranslation" => Some(SessionHint::ExampleTranslation),
+        _ => None,
+    }
+}
+
+fn hint_names(hints: &[SessionHint]) -> Vec<String> {
+    hints
+        .iter()
+        .map(|hint| hint_name(*hint).to_string())
+        .collect()
+}
+
+fn parse_hints(values: &[String]) -> Option<Vec<SessionHint>> {
+    values.iter().map(|value| parse_hint(value)).collect()
+}
+
+fn answer_normalization_name(normalization: AnswerNormalization) -> &'static str {
+    match normalization {
+        AnswerNormalization::FoldYo => "foldYo",
+        AnswerNormalization::StripStressMarks => "stripStressMarks",
+        AnswerNormalization::FoldWidth => "foldWidth",
+        AnswerNormalization::FoldKana => "foldKana",
+        AnswerNormalization::IgnoreOptionalParts => "ignoreOptionalParts",
+        AnswerNormalization::IgnoreLeadingArticle => "ignoreLeadingArticle",
+    }
+}
+
+fn parse_answer_normalization(value: &str) -> Option<AnswerNormalization> {
+    match value {
+        "foldYo" => Some(AnswerNormalization::FoldYo),
+        "stripStressMarks" => Some(AnswerNormalization::StripStressMarks),
+        "foldWidth" => Some(AnswerNormalization::FoldWidth),
+        "foldKana" => Some(AnswerNormalization::FoldKana),
+        "ignoreOptionalParts" => Some(AnswerNormalization::IgnoreOptionalParts),
+        "ignoreLeadingArticle" => Some(AnswerNormalization::IgnoreLeadingArticle),
+        _ => None,
+    }
+}
+
+fn answer_strictness_name(strictness: AnswerStrictness) -> &'static str {
+    match strictness {
+        AnswerStrictness::Exact => "exact",
+        AnswerStrictness::Tolerant => "tolerant",
+        AnswerStrictness::Lenient => "lenient",
+    }
+}
+
+fn parse_answer_strictness(value: &str) -> Option<AnswerStrictness> {
+    match value {
+        "exact" => Some(AnswerStrictness::Exact),
+        "tolerant" => Some(AnswerStrictness::Tolerant),
+        "lenient" => Some(AnswerStrictness::Lenient),
+        _ => None,
+    }
+}
+
+fn default_answer_strictness() -> String {
+    answer_strictness_name(AnswerStrictness::Tolerant).to_string()
+}
+
 fn parse_status(value: &str) -> Option<StudySessionStatus> {
     match value {
         "active" => Some(StudySessionStatus::Active),
@@ -416,6 +1030,7 @@ impl SqliteStudySessionRepository {
                     status TEXT NOT NULL,
                     version INTEGER NOT NULL,
                     state_json TEXT NOT NULL,
+                    updated_at INTEGER NOT NULL DEFAULT 0,
                     FOREIGN KEY (user_id) REFERENCES users(username) ON DELETE CASCADE,
                     FOREIGN KEY (profile_id) REFERENCES language_profiles(id) ON DELETE CASCADE
                 );
@@ -431,6 +1046,30 @@ impl SqliteStudySessionRepository {
                 CREATE INDEX IF NOT EXISTS idx_test_history_profile_sequence
                     ON test_selection_history(profile_id, sequence DESC);
 
+                CREATE TABLE IF NOT EXISTS study_session_undo (
+                    sequence INTEGER PRIMARY KEY AUTOINCREMENT,
+                    session_id TEXT NOT NULL,
+                    state_json TEXT NOT NULL,
+                    card_progress_json TEXT NOT NULL,
+                    selection_sequence INTEGER,
+                    review_log_sequence INTEGER NOT NULL,
+                    FOREIGN KEY (session_id) REFERENCES study_sessions(id) ON DELETE CASCADE
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_study_session_undo_session
+                    ON study_session_undo(session_id, sequence DESC);
+
+                CREATE TABLE IF NOT EXISTS study_session_events (
+                    sequence INTEGER PRIMARY KEY AUTOINCREMENT,
+                    session_id TEXT NOT NULL,
+                    version INTEGER NOT NULL,
+                    events_json TEXT NOT NULL,
+                    FOREIGN KEY (session_id) REFERENCES study_sessions(id) ON DELETE CASCADE
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_study_session_events_session
+                    ON study_session_events(session_id, sequence);
+
                 CREATE TABLE IF NOT EXISTS study_session_preferences (
                     user_id TEXT NOT NULL,
                     profile_id TEXT NOT NULL,
@@ -439,17 +1078,111 @@ impl SqliteStudySessionRepository {
                     min_score INTEGER,
                     max_score INTEGER,
                     cards_per_set INTEGER,
+                    new_cards_per_day INTEGER,
+                    card_order TEXT,
+                    recent_ban_window INTEGER,
+                    retry_missed_only INTEGER,
+                    max_cards INTEGER,
+                    time_limit_minutes INTEGER,
+                    correct_streak_goal INTEGER,
+                    answer_format TEXT NOT NULL DEFAULT 'written',
+                    answer_strictness TEXT NOT NULL DEFAULT 'tolerant',
                     pronunciation_check_enabled INTEGER NOT NULL,
                     pronunciation_score_threshold INTEGER NOT NULL,
                     PRIMARY KEY (profile_id, mode),
                     FOREIGN KEY (user_id) REFERENCES users(username) ON DELETE CASCADE,
                     FOREIGN KEY (profile_id) REFERENCES language_profiles(id) ON DELETE CASCADE
                 );
-
-                DELETE FROM study_sessions WHERE status = 'active';
                 ",
             )
             .map_err(SqliteStudySessionRepositoryInitError::Initialize)?;
+        create_review_log(&connection)
+            .map_err(SqliteStudySessionRepositoryInitError::Initialize)?;
+        add_missing_column(
+            &connection,
+            "study_session_preferences",
+            "new_cards_per_day",
+            "INTEGER",
+        )
+        .and_then(|()| {
+            add_missing_column(
+                &connection,
+                "study_session_preferences",
+                "answer_format",
+                "TEXT NOT NULL DEFAULT 'written'",
+            )
+        })
+        .and_then(|()| {
+            add_missing_column(
+                &connection,
+                "study_session_preferences",
+                "answer_strictness",
+                "TEXT NOT NULL DEFAULT 'tolerant'",
+            )
+        })
+        .and_then(|()| {
+            add_missing_column(
+                &connection,
+                "study_session_preferences",
+                "card_order",
+                "TEXT",
+            )
+        })
+        .and_then(|()| {
+            add_missing_column(
+                &connection,
+                "study_session_preferences",
+                "recent_ban_window",
+                "INTEGER",
+            )
+        })
+        .and_then(|()| {
+            add_missing_column(
+                &connection,
+                "study_session_preferences",
+                "retry_missed_only",
+                "INTEGER",
+            )
+        })
+        .and_then(|()| {
+            add_missing_column(
+                &connection,
+                "study_session_preferences",
+                "max_cards",
+                "INTEGER",
+            )
+        })
+        .and_then(|()| {
+            add_missing_column(
+                &connection,
+                "study_session_preferences",
+                "time_limit_minutes",
+                "INTEGER",
+            )
+        })
+        .and_then(|()| {
+            add_missing_column(
+                &connection,
+                "study_session_preferences",
+                "correct_streak_goal",
+                "INTEGER",
+            )
+        })
+        .and_then(|()| {
+            add_missing_column(
+                &connection,
+                "study_sessions",
+                "updated_at",
+                "INTEGER NOT NULL DEFAULT 0",
+            )
+        })
+        .and_then(|()| {
+            connection.execute_batch(
+                "CREATE INDEX IF NOT EXISTS idx_study_sessions_profile_status
+                    ON study_sessions(profile_id, status, updated_at DESC);",
+            )
+        })
+        .map_err(SqliteStudySessionRepositoryInitError::Initialize)?;
         Ok(Self {
             connection: Arc::new(Mutex::new(connection)),
         })
@@ -492,16 +1225,293 @@ impl SqliteStudySessionRepository {
             .into_domain()
     }
 
+    /// Records a test card selection and returns the sequence of its history row.
     fn record_selection(
         transaction: &rusqlite::Transaction<'_>,
         profile_id: &ProfileId,
         card_id: Option<&CardId>,
+    ) -> Result<Option<i64>, StudySessionRepositoryError> {
+        let Some(card_id) = card_id else {
+            return Ok(None);
+        };
+        transaction
+            .execute(
+                "INSERT INTO test_selection_history (profile_id, card_id) VALUES (?1, ?2)",
+                params![profile_id.as_str(), card_id.as_str()],
+            )
+            .map_err(Self::map_error)?;
+        Ok(Some(transaction.last_insert_rowid()))
+    }
+
+    fn find_schedule(
+        transaction: &rusqlite::Transaction<'_>,
+        card_id: &CardId,
+    ) -> Result<Option<CardSchedule>, StudySessionRepositoryError> {
+        transaction
+            .query_row(
+                "SELECT ease_permille, interval_days, repetitions, lapses, due_at,
+                        last_reviewed_at, introduced_at
+                 FROM card_schedules WHERE card_id = ?1",
+                params![card_id.as_str()],
+                |row| {
+                    Ok(CardSchedule {
+                        card_id: card_id.clone(),
+                        ease_permille: row.get(0)?,
+                        interval_days: row.get(1)?,
+                        repetitions: row.get(2)?,
+                        lapses: row.get(3)?,
+                        due_at: row.get(4)?,
+                        last_reviewed_at: row.get(5)?,
+                        introduced_at: row.get(6)?,
+                    })
+                },
+            )
+            .optional()
+            .map_err(Self::map_error)
+    }
+
+    fn save_schedule(
+        transaction: &rusqlite::Transaction<'_>,
+        schedule: &CardSchedule,
+    ) -> Result<(), StudySessionRepositoryError> {
+        transaction
+            .execute(
+                "INSERT INTO card_schedules (
+                    card_id, ease_permille, interval_days, repetitions, lapses,
+                    due_at, last_reviewed_at, introduced_at
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT(card_id) DO UPDATE SET
+                    ease_permille = excluded.ease_permille,
+                    interval_days = excluded.interval_days,
+                    repetitions = excluded.repetitions,
+                    lapses = excluded.lapses,
+                    due_at = excluded.due_at,
+                    last_reviewed_at = excluded.last_reviewed_at",
+                params![
+                    schedule.card_id.as_str(),
+                    schedule.ease_permille,
+                    schedule.interval_days,
+                    schedule.repetitions,
+                    schedule.lapses,
+                    schedule.due_at,
+                    schedule.last_reviewed_at,
+                    schedule.introduced_at,
+                ],
+            )
+            .map_err(Self::map_error)?;
+        Ok(())
+    }
+
+    fn find_leech(
+        transaction: &rusqlite::Transaction<'_>,
+        card_id: &CardId,
+    ) -> Result<LeechState, StudySessionRepositoryError> {
+        transaction
+            .query_row(
+                "SELECT consecutive_failures, is_leech FROM cards WHERE id = ?1",
+                params![card_id.as_str()],
+                |row| {
+                    Ok(LeechState {
+                        consecutive_failures: row.get(0)?,
+                        is_leech: row.get(1)?,
+                    })
+                },
+            )
+            .map_err(Self::map_error)
+    }
+
+    fn save_leech(
+        transaction: &rusqlite::Transaction<'_>,
+        card_id: &CardId,
+        leech: &LeechState,
+    ) -> Result<(), StudySessionRepositoryError> {
+        transaction
+            .execute(
+                "UPDATE cards SET consecutive_failures = ?1, is_leech = ?2 WHERE id = ?3",
+                params![leech.consecutive_failures, leech.is_leech, card_id.as_str()],
+            )
+            .map_err(Self::map_error)?;
+        Ok(())
+    }
+
+    fn find_status(
+        transaction: &rusqlite::Transaction<'_>,
+        card_id: &CardId,
+    ) -> Result<CardStatus, StudySessionRepositoryError> {
+        transaction
+            .query_row(
+                "SELECT suspended, buried_until FROM cards WHERE id = ?1",
+                params![card_id.as_str()],
+                |row| {
+                    Ok(match (row.get(0)?, row.get(1)?) {
+                        (true, _) => CardStatus::Suspended,
+                        (false, Some(until)) => CardStatus::Buried { until },
+                        (false, None) => CardStatus::Active,
+                    })
+                },
+            )
+            .map_err(Self::map_error)
+    }
+
+    fn save_status(
+        transaction: &rusqlite::Transaction<'_>,
+        card_id: &CardId,
+        status: CardStatus,
     ) -> Result<(), StudySessionRepositoryError> {
-        if let Some(card_id) = card_id {
+        let (suspended, buried_until) = match status {
+            CardStatus::Active => (false, None),
+            CardStatus::Suspended => (true, None),
+            CardStatus::Buried { until } => (false, Some(until)),
+        };
+        transaction
+            .execute(
+                "UPDATE cards SET suspended = ?1, buried_until = ?2 WHERE id = ?3",
+                params![suspended, buried_until, card_id.as_str()],
+            )
+            .map_err(Self::map_error)?;
+        Ok(())
+    }
+
+    /// Keeps the state a transition replaced and drops undo entries beyond the history limit.
+    fn record_undo(
+        transaction: &rusqlite::Transaction<'_>,
+        session_id: &SessionId,
+        previous_state: &str,
+        card_progress: &[StoredProgressUndo],
+        selection_sequence: Option<i64>,
+        review_log_sequence: i64,
+    ) -> Result<(), StudySessionRepositoryError> {
+        let card_progress = serde_json::to_string(card_progress)
+            .map_err(|error| StudySessionRepositoryError::Unexpected(error.to_string()))?;
+        transaction
+            .execute(
+                "INSERT INTO study_session_undo (
+                    session_id, state_json, card_progress_json, selection_sequence,
+                    review_log_sequence
+                 ) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    session_id.as_str(),
+                    previous_state,
+                    card_progress,
+                    selection_sequence,
+                    review_log_sequence,
+                ],
+            )
+            .map_err(Self::map_error)?;
+        transaction
+            .execute(
+                "DELETE FROM study_session_undo
+                 WHERE session_id = ?1 AND sequence NOT IN (
+                    SELECT sequence FROM study_session_undo
+                    WHERE session_id = ?1 ORDER BY sequence DESC LIMIT ?2
+                 )",
+                params![session_id.as_str(), UNDO_HISTORY_LIMIT],
+            )
+            .map_err(Self::map_error)?;
+        Ok(())
+    }
+
+    /// Records the events of a transition under the session version it produced.
+    fn record_events(
+        transaction: &rusqlite::Transaction<'_>,
+        session_id: &SessionId,
+        version: u64,
+        events: &[StudySessionEvent],
+    ) -> Result<(), StudySessionRepositoryError> {
+        let events = serde_json::to_string(
+            &events
+                .iter()
+                .map(StoredEvent::from_domain)
+                .collect::<Vec<_>>(),
+        )
+        .map_err(|error| StudySessionRepositoryError::Unexpected(error.to_string()))?;
+        transaction
+            .execute(
+                "INSERT INTO study_session_events (session_id, version, events_json)
+                 VALUES (?1, ?2, ?3)",
+                params![session_id.as_str(), version, events],
+            )
+            .map_err(Self::map_error)?;
+        Ok(())
+    }
+
+    /// Appends an accepted answer to the translations of its card meaning.
+    fn add_translation(
+        transaction: &rusqlite::Transaction<'_>,
+        profile_id: &ProfileId,
+        accepted: &AcceptedTranslation,
+    ) -> Result<StoredTranslationPosition, StudySessionRepositoryError> {
+        let affected = transaction
+            .execute(
+                "UPDATE cards SET version = version + 1 WHERE id = ?1 AND profile_id = ?2",
+                params![accepted.card_id.as_str(), profile_id.as_str()],
+            )
+            .map_err(Self::map_error)?;
+        if affected != 1 {
+            return Err(StudySessionRepositoryError::NotFound);
+        }
+        let position = transaction
+            .query_row(
+                "INSERT INTO card_translations (card_id, meaning_position, position, text)
+                 SELECT ?1, ?2, COALESCE(MAX(position) + 1, 0), ?3
+                 FROM card_translations
+                 WHERE card_id = ?1 AND meaning_position = ?2
+                 RETURNING position",
+                params![
+                    accepted.card_id.as_str(),
+                    accepted.meaning_index,
+                    accepted.translation
+                ],
+                |row| row.get(0),
+            )
+            .map_err(Self::map_error)?;
+        Ok(StoredTranslationPosition {
+            meaning_position: accepted.meaning_index,
+            position,
+        })
+    }
+
+    fn append_review_log(
+        transaction: &rusqlite::Transaction<'_>,
+        commit: &StudySessionCommit,
+    ) -> Result<(), StudySessionRepositoryError> {
+        for entry in &commit.review_log {
+            let regrades_sequence = if entry.outcome == ReviewOutcome::Accepted {
+                transaction
+                    .query_row(
+                        "SELECT MAX(sequence) FROM review_log
+                         WHERE session_id = ?1 AND card_id = ?2 AND undone_at IS NULL",
+                        params![entry.session_id.as_str(), entry.card_id.as_str()],
+                        |row| row.get::<_, Option<i64>>(0),
+                    )
+                    .map_err(Self::map_error)?
+            } else {
+                None
+            };
             transaction
                 .execute(
-                    "INSERT INTO test_selection_history (profile_id, card_id) VALUES (?1, ?2)",
-                    params![profile_id.as_str(), card_id.as_str()],
+                    "INSERT INTO review_log (
+                        profile_id, session_id, card_id, mode, direction, submitted_answer,
+                        matched_translation, outcome, is_correct, score_delta,
+                        pronunciation_strict_score, response_time_millis, reviewed_at,
+                        regrades_sequence
+                     ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                    params![
+                        commit.session.profile_id.as_str(),
+                        entry.session_id.as_str(),
+                        entry.card_id.as_str(),
+                        mode_name(entry.mode),
+                        direction_name(entry.direction),
+                        entry.submitted_answer,
+                        entry.matched_translation,
+                        outcome_name(entry.outcome),
+                        entry.is_correct,
+                        entry.score_delta,
+                        entry.pronunciation_strict_score,
+                        entry.response_time_millis,
+                        entry.reviewed_at,
+                        regrades_sequence,
+                    ],
                 )
                 .map_err(Self::map_error)?;
         }
@@ -523,8 +1533,8 @@ impl StudySessionRepository for SqliteStudySessionRepository {
             transaction
                 .execute(
                     "INSERT INTO study_sessions
-                     (id, user_id, profile_id, status, version, state_json)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                     (id, user_id, profile_id, status, version, state_json, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, CAST(unixepoch('subsec') * 1000 AS INTEGER))",
                     params![
                         request.session.id.as_str(),
                         request.session.owner_id.as_str(),
@@ -540,22 +1550,44 @@ impl StudySessionRepository for SqliteStudySessionRepository {
                 &request.session.profile_id,
                 request.selected_test_card.as_ref(),
             )?;
+            Self::record_events(
+                &transaction,
+                &request.session.id,
+                request.session.version,
+                &[StudySessionEvent::Started {
+                    session: Box::new(request.session.clone()),
+                }],
+            )?;
             let preferences = request.preferences;
             transaction
                 .execute(
                     "INSERT INTO study_session_preferences (
                         user_id, profile_id, mode, direction, min_score, max_score,
-                        cards_per_set, pronunciation_check_enabled,
-                        pronunciation_score_threshold
-                     ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                        cards_per_set, new_cards_per_day, answer_format, answer_strictness,
+                        pronunciation_check_enabled, pronunciation_score_threshold,
+                        card_order, recent_ban_window, retry_missed_only, max_cards,
+                        time_limit_minutes, correct_streak_goal
+                     ) VALUES (
+                        ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                        ?17, ?18
+                     )
                      ON CONFLICT(profile_id, mode) DO UPDATE SET
                         user_id = excluded.user_id,
                         direction = excluded.direction,
                         min_score = excluded.min_score,
                         max_score = excluded.max_score,
                         cards_per_set = excluded.cards_per_set,
+                        new_cards_per_day = excluded.new_cards_per_day,
+                        answer_format = excluded.answer_format,
+                        answer_strictness = excluded.answer_strictness,
                         pronunciation_check_enabled = excluded.pronunciation_check_enabled,
-                        pronunciation_score_threshold = excluded.pronunciation_score_threshold",
+                        pronunciation_score_threshold = excluded.pronunciation_score_threshold,
+                        card_order = excluded.card_order,
+                        recent_ban_window = excluded.recent_ban_window,
+                        retry_missed_only = excluded.retry_missed_only,
+                        max_cards = excluded.max_cards,
+                        time_limit_minutes = excluded.time_limit_minutes,
+                        correct_streak_goal = excluded.correct_streak_goal",
                     params![
                         request.session.owner_id.as_str(),
                         preferences.profile_id.as_str(),
@@ -564,8 +1596,17 @@ impl StudySessionRepository for SqliteStudySessionRepository {
                         preferences.min_score,
                         preferences.max_score,
                         preferences.cards_per_set,
+                        preferences.new_cards_per_day,
+                        answer_format_name(preferences.answer_format),
+                        answer_strictness_name(preferences.answer_strictness),
                         preferences.pronunciation_check_enabled,
                         preferences.pronunciation_score_threshold,
+                        preferences.card_order.map(card_order_name),
+                        preferences.recent_ban_window,
+                        preferences.retry_missed_only,
+                        preferences.max_cards,
+                        preferences.time_limit_minutes,
+                        preferences.correct_streak_goal,
                     ],
                 )
                 .map_err(Self::map_error)?;
@@ -601,6 +1642,50 @@ impl StudySessionRepository for SqliteStudySessionRepository {
         .map_err(Self::map_join)?
     }
 
+    async fn list_active(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+    ) -> Result<Vec<StudySession>, StudySessionRepositoryError> {
+        let repository = self.clone();
+        let user_id = user_id.clone();
+        let profile_id = profile_id.clone();
+        tokio::task::spawn_blocking(move || {
+            let connection = repository.lock()?;
+            let mut statement = connection
+                .prepare(
+                    "SELECT state_json FROM study_sessions
+                     WHERE user_id = ?1 AND profile_id = ?2 AND status = 'active'
+                     ORDER BY updated_at DESC, id ASC",
+                )
+                .map_err(Self::map_error)?;
+            statement
+                .query_map(params![user_id.as_str(), profile_id.as_str()], |row| {
+                    row.get::<_, String>(0)
+                })
+                .map_err(Self::map_error)?
+                .map(|state| state.map_err(Self::map_error).and_then(Self::decode))
+                .collect()
+        })
+        .await
+        .map_err(Self::map_join)?
+    }
+
+    async fn expire_idle(&self, idle_before: i64) -> Result<usize, StudySessionRepositoryError> {
+        let repository = self.clone();
+        tokio::task::spawn_blocking(move || {
+            repository
+                .lock()?
+                .execute(
+                    "DELETE FROM study_sessions WHERE status = 'active' AND updated_at < ?1",
+                    params![idle_before],
+                )
+                .map_err(Self::map_error)
+        })
+        .await
+        .map_err(Self::map_join)?
+    }
+
     async fn find_preferences(
         &self,
         user_id: &UserId,
@@ -615,7 +1700,10 @@ impl StudySessionRepository for SqliteStudySessionRepository {
                 .lock()?
                 .query_row(
                     "SELECT direction, min_score, max_score, cards_per_set,
-                            pronunciation_check_enabled, pronunciation_score_threshold
+                            pronunciation_check_enabled, pronunciation_score_threshold,
+                            new_cards_per_day, answer_format, answer_strictness, card_order,
+                            recent_ban_window, retry_missed_only, max_cards,
+                            time_limit_minutes, correct_streak_goal
                      FROM study_session_preferences
                      WHERE user_id = ?1 AND profile_id = ?2 AND mode = ?3",
                     params![user_id.as_str(), profile_id.as_str(), mode_name(mode),],
@@ -626,6 +1714,16 @@ impl StudySessionRepository for SqliteStudySessionRepository {
                             Some("reverse") => Some(CardDirection::Reverse),
                             Some(_) => return Err(rusqlite::Error::InvalidQuery),
                         };
+                        let answer_format = parse_answer_format(&row.get::<_, String>(7)?)
+                            .ok_or(rusqlite::Error::InvalidQuery)?;
+                        let answer_strictness = parse_answer_strictness(&row.get::<_, String>(8)?)
+                            .ok_or(rusqlite::Error::InvalidQuery)?;
+                        let card_order = row
+                            .get::<_, Option<String>>(9)?
+                            .map(|order| {
+                                parse_card_order(&order).ok_or(rusqlite::Error::InvalidQuery)
+                            })
+                            .transpose()?;
                         Ok(StudySessionPreferences {
                             profile_id: profile_id.clone(),
                             mode,
@@ -633,6 +1731,15 @@ impl StudySessionRepository for SqliteStudySessionRepository {
                             min_score: row.get(1)?,
                             max_score: row.get(2)?,
                             cards_per_set: row.get(3)?,
+                            retry_missed_only: row.get(11)?,
+                            new_cards_per_day: row.get(6)?,
+                            card_order,
+                            recent_ban_window: row.get(10)?,
+                            max_cards: row.get(12)?,
+                            time_limit_minutes: row.get(13)?,
+                            correct_streak_goal: row.get(14)?,
+                            answer_format,
+                            answer_strictness,
                             pronunciation_check_enabled: row.get(4)?,
                             pronunciation_score_threshold: row.get(5)?,
                         })
@@ -683,11 +1790,11 @@ impl StudySessionRepository for SqliteStudySessionRepository {
         tokio::task::spawn_blocking(move || {
             let mut connection = repository.lock()?;
             let transaction = connection.transaction().map_err(Self::map_error)?;
-            let current_version = transaction
+            let (current_version, previous_state) = transaction
                 .query_row(
-                    "SELECT version FROM study_sessions WHERE id = ?1 AND user_id = ?2",
+                    "SELECT version, state_json FROM study_sessions WHERE id = ?1 AND user_id = ?2",
                     params![commit.session.id.as_str(), commit.session.owner_id.as_str()],
-                    |row| row.get::<_, u64>(0),
+                    |row| Ok((row.get::<_, u64>(0)?, row.get::<_, String>(1)?)),
                 )
                 .optional()
                 .map_err(Self::map_error)?
@@ -695,6 +1802,14 @@ impl StudySessionRepository for SqliteStudySessionRepository {
             if current_version != commit.expected_version {
                 return Err(StudySessionRepositoryError::Conflict);
             }
+            let review_log_sequence = transaction
+                .query_row(
+                    "SELECT COALESCE(MAX(sequence), 0) FROM review_log",
+                    [],
+                    |row| row.get::<_, i64>(0),
+                )
+                .map_err(Self::map_error)?;
+            let mut undo_progress = Vec::with_capacity(commit.card_progress.len());
             for progress in &commit.card_progress {
                 let affected = transaction
                     .execute(
@@ -710,13 +1825,66 @@ impl StudySessionRepository for SqliteStudySessionRepository {
                 if affected != 1 {
                     return Err(StudySessionRepositoryError::NotFound);
                 }
+                let previous_schedule = match &progress.schedule {
+                    Some(schedule) => {
+                        let previous = Self::find_schedule(&transaction, &progress.card_id)?;
+                        Self::save_schedule(
+                            &transaction,
+                            &CardSchedule {
+                                card_id: progress.card_id.clone(),
+                                ..schedule.clone()
+                            },
+                        )?;
+                        previous
+                    }
+                    None => None,
+                };
+                let previous_leech = match &progress.leech {
+                    Some(leech) => {
+                        let previous = Self::find_leech(&transaction, &progress.card_id)?;
+                        Self::save_leech(&transaction, &progress.card_id, leech)?;
+                        Some(previous)
+                    }
+                    None => None,
+                };
+                let previous_status = match progress.status {
+                    Some(status) => {
+                        let previous = Self::find_status(&transaction, &progress.card_id)?;
+                        Self::save_status(&transaction, &progress.card_id, status)?;
+                        Some(previous)
+                    }
+                    None => None,
+                };
+                undo_progress.push(StoredProgressUndo {
+                    card_id: progress.card_id.as_str().to_string(),
+                    score_delta: progress.score_delta,
+                    replaced_schedule: progress.schedule.is_some(),
+                    previous_schedule: previous_schedule.as_ref().map(StoredSchedule::from_domain),
+                    previous_leech: previous_leech.as_ref().map(StoredLeechState::from_domain),
+                    previous_status: previous_status.map(StoredCardStatus::from_domain),
+                    added_translation: None,
+                });
+            }
+            if let Some(accepted) = &commit.accepted_translation {
+                let added_translation =
+                    Self::add_translation(&transaction, &commit.session.profile_id, accepted)?;
+                undo_progress.push(StoredProgressUndo {
+                    card_id: accepted.card_id.as_str().to_string(),
+                    score_delta: 0,
+                    replaced_schedule: false,
+                    previous_schedule: None,
+                    previous_leech: None,
+                    previous_status: None,
+                    added_translation: Some(added_translation),
+                });
             }
             commit.session.version = commit.expected_version + 1;
             let state = Self::encode(&commit.session)?;
             transaction
                 .execute(
                     "UPDATE study_sessions
-                     SET status = ?1, version = ?2, state_json = ?3
+                     SET status = ?1, version = ?2, state_json = ?3,
+                         updated_at = CAST(unixepoch('subsec') * 1000 AS INTEGER)
                      WHERE id = ?4 AND version = ?5",
                     params![
                         status_name(commit.session.status),
@@ -727,11 +1895,35 @@ impl StudySessionRepository for SqliteStudySessionRepository {
                     ],
                 )
                 .map_err(Self::map_error)?;
-            Self::record_selection(
+            let selection_sequence = Self::record_selection(
                 &transaction,
                 &commit.session.profile_id,
                 commit.selected_test_card.as_ref(),
             )?;
+            Self::append_review_log(&transaction, &commit)?;
+            Self::record_events(
+                &transaction,
+                &commit.session.id,
+                commit.session.version,
+                &commit.events,
+            )?;
+            if commit.session.status != StudySessionStatus::Active {
+                transaction
+                    .execute(
+                        "DELETE FROM study_session_undo WHERE session_id = ?1",
+                        params![commit.session.id.as_str()],
+                    )
+                    .map_err(Self::map_error)?;
+            } else if commit.undoable {
+                Self::record_undo(
+                    &transaction,
+                    &commit.session.id,
+                    &previous_state,
+                    &undo_progress,
+                    selection_sequence,
+                    review_log_sequence,
+                )?;
+            }
             transaction.commit().map_err(Self::map_error)?;
             Ok(commit.session)
         })
@@ -757,15 +1949,847 @@ impl StudySessionRepository for SqliteStudySessionRepository {
             expected_version: request.expected_version,
             card_progress: Vec::new(),
             selected_test_card: None,
+            review_log: Vec::new(),
+            accepted_translation: None,
+            undoable: false,
+            events: vec![StudySessionEvent::Ended {
+                status: request.status,
+            }],
+        })
+        .await
+    }
+
+    async fn undo_transition(
+        &self,
+        request: UndoTransitionRequest,
+    ) -> Result<Option<StudySession>, StudySessionRepositoryError> {
+        let repository = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = repository.lock()?;
+            let transaction = connection.transaction().map_err(Self::map_error)?;
+            let (current_version, profile_id) = transaction
+                .query_row(
+                    "SELECT version, profile_id FROM study_sessions WHERE id = ?1 AND user_id = ?2",
+                    params![request.session_id.as_str(), request.user_id.as_str()],
+                    |row| Ok((row.get::<_, u64>(0)?, row.get::<_, String>(1)?)),
+                )
+                .optional()
+                .map_err(Self::map_error)?
+                .ok_or(StudySessionRepositoryError::NotFound)?;
+            if current_version != request.expected_version {
+                return Err(StudySessionRepositoryError::Conflict);
+            }
+            let Some((sequence, state, card_progress, selection_sequence, review_log_sequence)) =
+                transaction
+                    .query_row(
+                        "SELECT sequence, state_json, card_progress_json, selection_sequence,
+                                review_log_sequence
+                         FROM study_session_undo
+                         WHERE session_id = ?1 ORDER BY sequence DESC LIMIT 1",
+                        params![request.session_id.as_str()],
+                        |row| {
+                            Ok((
+                                row.get::<_, i64>(0)?,
+                                row.get::<_, String>(1)?,
+                                row.get::<_, String>(2)?,
+                                row.get::<_, Option<i64>>(3)?,
+                                row.get::<_, i64>(4)?,
+                            ))
+                        },
+                    )
+                    .optional()
+                    .map_err(Self::map_error)?
+            else {
+                return Ok(None);
+            };
+            let card_progress = serde_json::from_str::<Vec<StoredProgressUndo>>(&card_progress)
+                .map_err(|error| StudySessionRepositoryError::Unexpected(error.to_string()))?;
+            for progress in card_progress.into_iter().rev() {
+                // Cards deleted since the transition have nothing left to revert.
+                let affected = transaction
+                    .execute(
+                        "UPDATE cards SET score = score - ?1, version = version + 1
+                         WHERE id = ?2 AND profile_id = ?3",
+                        params![progress.score_delta, progress.card_id, profile_id],
+                    )
+                    .map_err(Self::map_error)?;
+                if affected == 0 {
+                    continue;
+                }
+                let card_id = CardId::new(progress.card_id.as_str());
+                if let Some(leech) = progress.previous_leech {
+                    Self::save_leech(&transaction, &card_id, &leech.into_domain())?;
+                }
+                if let Some(status) = progress.previous_status {
+                    Self::save_status(&transaction, &card_id, status.into_domain())?;
+                }
+                if let Some(added) = progress.added_translation {
+                    transaction
+                        .execute(
+                            "DELETE FROM card_translations
+                             WHERE card_id = ?1 AND meaning_position = ?2 AND position = ?3",
+                            params![progress.card_id, added.meaning_position, added.position],
+                        )
+                        .map_err(Self::map_error)?;
+                }
+                if !progress.replaced_schedule {
+                    continue;
+                }
+                match progress.previous_schedule {
+                    Some(schedule) => Self::save_schedule(&transaction, &schedule.into_domain())?,
+                    None => {
+                        transaction
+                            .execute(
+                                "DELETE FROM card_schedules WHERE card_id = ?1",
+                                params![progress.card_id],
+                            )
+                            .map_err(Self::map_error)?;
+                    }
+                }
+            }
+            transaction
+                .execute(
+                    "DELETE FROM test_selection_history WHERE sequence = ?1",
+                    params![selection_sequence],
+                )
+                .map_err(Self::map_error)?;
+            transaction
+                .execute(
+                    "UPDATE review_log
+                     SET undone_at = CAST(unixepoch('subsec') * 1000 AS INTEGER)
+                     WHERE session_id = ?1 AND sequence > ?2 AND undone_at IS NULL",
+                    params![request.session_id.as_str(), review_log_sequence],
+                )
+                .map_err(Self::map_error)?;
+            transaction
+                .execute(
+                    "DELETE FROM study_session_undo WHERE sequence = ?1",
+                    params![sequence],
+                )
+                .map_err(Self::map_error)?;
+            let mut session = Self::decode(state)?;
+            session.version = current_version + 1;
+            transaction
+                .execute(
+                    "UPDATE study_sessions
+                     SET status = ?1, version = ?2, state_json = ?3,
+                         updated_at = CAST(unixepoch('subsec') * 1000 AS INTEGER)
+                     WHERE id = ?4 AND version = ?5",
+                    params![
+                        status_name(session.status),
+                        session.version,
+                        Self::encode(&session)?,
+                        session.id.as_str(),
+                        current_version
+                    ],
+                )
+                .map_err(Self::map_error)?;
+            Self::record_events(
+                &transaction,
+                &session.id,
+                session.version,
+                &[StudySessionEvent::Undone],
+            )?;
+            transaction.commit().map_err(Self::map_error)?;
+            Ok(Some(session))
         })
         .await
+        .map_err(Self::map_join)?
+    }
+
+    async fn events(
+        &self,
+        user_id: &UserId,
+        session_id: &SessionId,
+    ) -> Result<Vec<RecordedTransition>, StudySessionRepositoryError> {
+        let repository = self.clone();
+        let user_id = user_id.clone();
+        let session_id = session_id.clone();
+        tokio::task::spawn_blocking(move || {
+            let connection = repository.lock()?;
+            let mut statement = connection
+                .prepare(
+                    "SELECT events.version, events.events_json
+                     FROM study_session_events events
+                     JOIN study_sessions sessions ON sessions.id = events.session_id
+                     WHERE events.session_id = ?1 AND sessions.user_id = ?2
+                     ORDER BY events.sequence",
+                )
+                .map_err(Self::map_error)?;
+            let rows = statement
+                .query_map(params![session_id.as_str(), user_id.as_str()], |row| {
+                    Ok((row.get::<_, u64>(0)?, row.get::<_, String>(1)?))
+                })
+                .map_err(Self::map_error)?;
+            rows.map(|row| {
+                let (version, events) = row.map_err(Self::map_error)?;
+                let events = serde_json::from_str::<Vec<StoredEvent>>(&events)
+                    .map_err(|error| StudySessionRepositoryError::Unexpected(error.to_string()))?
+                    .into_iter()
+                    .map(StoredEvent::into_domain)
+                    .collect::<Result<_, _>>()?;
+                Ok(RecordedTransition { version, events })
+            })
+            .collect()
+        })
+        .await
+        .map_err(Self::map_join)?
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use application::ports::{
+        input::{
+            card_catalog::models::{Card, CardSchedule, LeechState, Meaning, Word},
+            language_profile::models::{LanguageProfile, LeechAction},
+            local_user::models::LocalUser,
+        },
+        output::repository::{
+            card::{CardRepository, models::CardRepositoryError},
+            language_profile::LanguageProfileRepository,
+            review_log::{
+                ReviewLogRepository,
+                models::{ReviewLogEntry, ReviewLogQuery, ReviewLogRepositoryError, ReviewOutcome},
+            },
+            study_session::models::CardProgressUpdate,
+            user::UserRepository,
+        },
+    };
+    use application::usecases::study_session_reducer::{apply, replay};
+    use tempfile::TempDir;
+
+    use crate::output::persistence::{
+        SqliteCardRepository, SqliteLanguageProfileRepository, SqliteReviewLogRepository,
+        SqliteUserRepository,
+    };
+
     use super::*;
 
+    async fn setup() -> (TempDir, SqliteCardRepository, SqliteStudySessionRepository) {
+        let directory = TempDir::new().unwrap();
+        let database_path = directory.path().join("sessions.db");
+        SqliteUserRepository::new(&database_path)
+            .unwrap()
+            .insert(LocalUser {
+                id: UserId::new("alice"),
+            })
+            .await
+            .unwrap();
+        SqliteLanguageProfileRepository::new(&database_path)
+            .unwrap()
+            .insert(LanguageProfile {
+                id: ProfileId::new("profile"),
+                owner_id: UserId::new("alice"),
+                name: "Japanese".to_string(),
+                source_language: "en-US".to_string(),
+                target_language: "ja-JP".to_string(),
+                leech_threshold: 8,
+                leech_action: LeechAction::Suspend,
+                version: 0,
+            })
+            .await
+            .unwrap();
+        let cards = SqliteCardRepository::new(&database_path).unwrap();
+        cards
+            .insert_batch(
+                &UserId::new("alice"),
+                &ProfileId::new("profile"),
+                vec![Card {
+                    id: CardId::new("card"),
+                    profile_id: ProfileId::new("profile"),
+                    direction: CardDirection::Straight,
+                    word: Word {
+                        text: "水".to_string(),
+                        readings: vec!["みず".to_string()],
+                    },
+                    meanings: vec![Meaning {
+                        definition: "water".to_string(),
+                        translated_definition: String::new(),
+                        word_translations: vec!["water".to_string()],
+                        examples: Vec::new(),
+                    }],
+                    score: 0,
+                    leech: LeechState::default(),
+                    status: CardStatus::Active,
+                    created_at: 0,
+                    version: 0,
+                }],
+            )
+            .await
+            .unwrap();
+        let sessions = SqliteStudySessionRepository::new(&database_path).unwrap();
+        (directory, cards, sessions)
+    }
+
+    fn session() -> StudySession {
+        StudySession {
+            id: SessionId::new("session"),
+            owner_id: UserId::new("alice"),
+            profile_id: ProfileId::new("profile"),
+            mode: StudySessionMode::Test,
+            phase: StudySessionPhase::Test,
+            status: StudySessionStatus::Active,
+            filter: SessionFilter {
+                direction: None,
+                min_score: None,
+                max_score: None,
+            },
+            answer_format: AnswerFormat::MultipleChoice,
+            answer_strictness: AnswerStrictness::Lenient,
+            pronunciation_check_enabled: false,
+            pronunciation_score_threshold: 80,
+            cards_per_set: 1,
+            retry_missed_only: false,
+            card_order: CardOrder::ScoreWeighted,
+            recent_ban_window: 10,
+            max_cards: Some(20),
+            ends_at: Some(600_000),
+            correct_streak_goal: None,
+            card_ids: vec![CardId::new("card")],
+            test_order: Vec::new(),
+            seed: 42,
+            current_set_index: 0,
+            retry_card_ids: Vec::new(),
+            current_card_index: 0,
+            provided_answers: Vec::new(),
+            completed_meaning_indices: Vec::new(),
+            pronunciation_attempts: Vec::new(),
+            pronunciation_passed: false,
+            pronunciation_technical_failures: 0,
+            pronunciation_disable_required: false,
+            awaiting_continue: false,
+            retype_pending: true,
+            schedule_before_answer: Some(schedule(1, 5)),
+            leech_before_answer: Some(LeechState {
+                consecutive_failures: 2,
+                is_leech: false,
+            }),
+            hints_used: vec![SessionHint::FirstLetter],
+            card_presented_at: Some(3),
+            current_set_failed: false,
+            results: Vec::new(),
+            version: 0,
+        }
+    }
+
+    async fn insert_session(sessions: &SqliteStudySessionRepository) -> StudySession {
+        let session = session();
+        sessions
+            .insert(StoreSessionRequest {
+                preferences: StudySessionPreferences::defaults(
+                    session.profile_id.clone(),
+                    session.mode,
+                ),
+                session,
+                selected_test_card: Some(CardId::new("card")),
+            })
+            .await
+            .unwrap()
+    }
+
+    fn schedule(interval_days: u32, introduced_at: i64) -> CardSchedule {
+        CardSchedule {
+            card_id: CardId::new("card"),
+            ease_permille: 2_500,
+            interval_days,
+            repetitions: interval_days,
+            lapses: 0,
+            due_at: i64::from(interval_days) * 86_400_000,
+            last_reviewed_at: introduced_at,
+            introduced_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn committing_progress_updates_scores_and_schedules_together() {
+        let (_directory, cards, sessions) = setup().await;
+        let session = insert_session(&sessions).await;
+
+        let session = sessions
+            .commit_transition(StudySessionCommit {
+                session,
+                expected_version: 0,
+                card_progress: vec![CardProgressUpdate {
+                    card_id: CardId::new("card"),
+                    score_delta: 1,
+                    schedule: Some(schedule(1, 5)),
+                    leech: None,
+                    status: None,
+                }],
+                selected_test_card: None,
+                review_log: Vec::new(),
+                accepted_translation: None,
+                undoable: false,
+                events: Vec::new(),
+            })
+            .await
+            .unwrap();
+        let mut second = schedule(6, 5);
+        second.last_reviewed_at = 9;
+        sessions
+            .commit_transition(StudySessionCommit {
+                session,
+                expected_version: 1,
+                card_progress: vec![CardProgressUpdate {
+                    card_id: CardId::new("card"),
+                    score_delta: 1,
+                    schedule: Some(second.clone()),
+                    leech: Some(LeechState {
+                        consecutive_failures: 0,
+                        is_leech: true,
+                    }),
+                    status: None,
+                }],
+                selected_test_card: None,
+                review_log: Vec::new(),
+                accepted_translation: None,
+                undoable: false,
+                events: Vec::new(),
+            })
+            .await
+            .unwrap();
+
+        let user_id = UserId::new("alice");
+        let profile_id = ProfileId::new("profile");
+        let card_ids = [CardId::new("card"), CardId::new("missing")];
+        assert_eq!(
+            cards
+                .find_schedules(&user_id, &profile_id, &card_ids)
+                .await
+                .unwrap(),
+            vec![second]
+        );
+        let card = cards
+            .find(&user_id, &profile_id, &CardId::new("card"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(card.score, 2);
+        assert!(card.leech.is_leech);
+        assert_eq!(
+            sessions
+                .find(&user_id, &SessionId::new("session"))
+                .await
+                .unwrap()
+                .unwrap()
+                .schedule_before_answer,
+            Some(schedule(1, 5))
+        );
+        assert_eq!(
+            cards
+                .find_schedules(&UserId::new("bob"), &profile_id, &card_ids)
+                .await,
+            Err(CardRepositoryError::NotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn committed_answers_are_appended_to_the_review_log() {
+        let (directory, _cards, sessions) = setup().await;
+        let session = insert_session(&sessions).await;
+        let entry = |answer: Option<&str>, outcome, reviewed_at| ReviewLogEntry {
+            session_id: SessionId::new("session"),
+            card_id: CardId::new("card"),
+            mode: StudySessionMode::Test,
+            direction: CardDirection::Straight,
+            submitted_answer: answer.map(str::to_string),
+            matched_translation: (outcome == ReviewOutcome::Correct).then(|| "water".to_string()),
+            outcome,
+            is_correct: outcome == ReviewOutcome::Correct,
+            score_delta: if outcome == ReviewOutcome::Correct {
+                1
+            } else {
+                -2
+            },
+            pronunciation_strict_score: Some(91),
+            response_time_millis: None,
+            reviewed_at,
+        };
+        sessions
+            .commit_transition(StudySessionCommit {
+                session,
+                expected_version: 0,
+                card_progress: Vec::new(),
+                selected_test_card: None,
+                review_log: vec![
+                    entry(None, ReviewOutcome::PronunciationFailed, 4),
+                    entry(None, ReviewOutcome::GaveUp, 6),
+                    entry(Some("fire"), ReviewOutcome::Incorrect, 10),
+                    entry(Some("watr"), ReviewOutcome::Correct, 20),
+                ],
+                accepted_translation: None,
+                undoable: false,
+                events: Vec::new(),
+            })
+            .await
+            .unwrap();
+        let log = SqliteReviewLogRepository::new(directory.path().join("sessions.db")).unwrap();
+        let query = ReviewLogQuery {
+            user_id: UserId::new("alice"),
+            profile_id: ProfileId::new("profile"),
+            card_id: Some(CardId::new("card")),
+            since: None,
+            limit: 10,
+        };
+
+        assert_eq!(
+            log.list(query.clone()).await.unwrap(),
+            vec![
+                entry(Some("watr"), ReviewOutcome::Correct, 20),
+                entry(Some("fire"), ReviewOutcome::Incorrect, 10),
+                entry(None, ReviewOutcome::GaveUp, 6),
+                entry(None, ReviewOutcome::PronunciationFailed, 4),
+            ]
+        );
+        assert_eq!(
+            log.list(ReviewLogQuery {
+                since: Some(15),
+                ..query.clone()
+            })
+            .await
+            .unwrap()
+            .len(),
+            1
+        );
+        assert_eq!(
+            log.list(ReviewLogQuery {
+                user_id: UserId::new("bob"),
+                ..query
+            })
+            .await,
+            Err(ReviewLogRepositoryError::NotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn undo_reverts_scores_schedules_selections_and_review_log() {
+        let (directory, cards, sessions) = setup().await;
+        let session = insert_session(&sessions).await;
+        let user_id = UserId::new("alice");
+        let profile_id = ProfileId::new("profile");
+        let card_id = CardId::new("card");
+        let mut answered = session.clone();
+        answered.awaiting_continue = true;
+        sessions
+            .commit_transition(StudySessionCommit {
+                session: answered,
+                expected_version: 0,
+                card_progress: vec![CardProgressUpdate {
+                    card_id: card_id.clone(),
+                    score_delta: -2,
+                    schedule: Some(schedule(0, 5)),
+                    leech: Some(LeechState {
+                        consecutive_failures: 1,
+                        is_leech: true,
+                    }),
+                    status: Some(CardStatus::Suspended),
+                }],
+                selected_test_card: Some(card_id.clone()),
+                review_log: vec![ReviewLogEntry {
+                    session_id: session.id.clone(),
+                    card_id: card_id.clone(),
+                    mode: StudySessionMode::Test,
+                    direction: CardDirection::Straight,
+                    submitted_answer: Some("fire".to_string()),
+                    matched_translation: None,
+                    outcome: ReviewOutcome::Incorrect,
+                    is_correct: false,
+                    score_delta: -2,
+                    pronunciation_strict_score: None,
+                    response_time_millis: None,
+                    reviewed_at: 10,
+                }],
+                accepted_translation: Some(AcceptedTranslation {
+                    card_id: card_id.clone(),
+                    meaning_index: 0,
+                    translation: "aqua".to_string(),
+                }),
+                undoable: true,
+                events: Vec::new(),
+            })
+            .await
+            .unwrap();
+        let undo = |expected_version| UndoTransitionRequest {
+            user_id: user_id.clone(),
+            session_id: session.id.clone(),
+            expected_version,
+        };
+        let translations = || async {
+            cards
+                .find(&user_id, &profile_id, &card_id)
+                .await
+                .unwrap()
+                .unwrap()
+                .meanings[0]
+                .word_translations
+                .clone()
+        };
+
+        assert_eq!(translations().await, ["water", "aqua"]);
+        assert_eq!(
+            sessions.undo_transition(undo(0)).await,
+            Err(StudySessionRepositoryError::Conflict)
+        );
+        let restored = sessions.undo_transition(undo(1)).await.unwrap().unwrap();
+
+        assert_eq!(
+            restored,
+            StudySession {
+                version: 2,
+                ..session.clone()
+            }
+        );
+        assert_eq!(
+            sessions.find(&user_id, &session.id).await.unwrap(),
+            Some(restored)
+        );
+        let card = cards
+            .find(&user_id, &profile_id, &card_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(card.score, 0);
+        assert_eq!(card.leech, LeechState::default());
+        assert_eq!(card.status, CardStatus::Active);
+        assert_eq!(translations().await, ["water"]);
+        assert!(
+            cards
+                .find_schedules(&user_id, &profile_id, std::slice::from_ref(&card_id))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            sessions.recent_test_cards(&profile_id, 10).await.unwrap(),
+            vec![card_id.clone()]
+        );
+        let log = SqliteReviewLogRepository::new(directory.path().join("sessions.db")).unwrap();
+        assert!(
+            log.list(ReviewLogQuery {
+                user_id: user_id.clone(),
+                profile_id: profile_id.clone(),
+                card_id: None,
+                since: None,
+                limit: 10,
+            })
+            .await
+            .unwrap()
+            .is_empty()
+        );
+        let kept = rusqlite::Connection::open(directory.path().join("sessions.db"))
+            .unwrap()
+            .query_row(
+                "SELECT COUNT(*) FROM review_log WHERE undone_at IS NOT NULL",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .unwrap();
+        assert_eq!(kept, 1, "undone answers stay in the log");
+        assert_eq!(sessions.undo_transition(undo(2)).await, Ok(None));
+    }
+
+    #[tokio::test]
+    async fn undo_history_is_bounded_and_dropped_when_the_session_ends() {
+        let (_directory, _cards, sessions) = setup().await;
+        let mut session = insert_session(&sessions).await;
+        for step in 0..UNDO_HISTORY_LIMIT + 2 {
+            session.current_card_index = step + 1;
+            session = sessions
+                .commit_transition(StudySessionCommit {
+                    expected_version: session.version,
+                    session,
+                    card_progress: Vec::new(),
+                    selected_test_card: None,
+                    review_log: Vec::new(),
+                    accepted_translation: None,
+                    undoable: true,
+                    events: Vec::new(),
+                })
+                .await
+                .unwrap();
+        }
+        let undo = |expected_version| UndoTransitionRequest {
+            user_id: UserId::new("alice"),
+            session_id: SessionId::new("session"),
+            expected_version,
+        };
+
+        for _ in 0..UNDO_HISTORY_LIMIT {
+            session = sessions
+                .undo_transition(undo(session.version))
+                .await
+                .unwrap()
+                .unwrap();
+        }
+        assert_eq!(session.current_card_index, 2);
+        assert_eq!(
+            sessions.undo_transition(undo(session.version)).await,
+            Ok(None)
+        );
+
+        session.current_card_index = 3;
+        let session = sessions
+            .commit_transition(StudySessionCommit {
+                expected_version: session.version,
+                session,
+                card_progress: Vec::new(),
+                selected_test_card: None,
+                review_log: Vec::new(),
+                accepted_translation: None,
+                undoable: true,
+                events: Vec::new(),
+            })
+            .await
+            .unwrap();
+        let ended = sessions
+            .end(EndSessionRequest {
+                user_id: UserId::new("alice"),
+                session_id: session.id,
+                expected_version: session.version,
+                status: StudySessionStatus::Completed,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            sessions.undo_transition(undo(ended.version)).await,
+            Ok(None)
+        );
+    }
+
+    #[tokio::test]
+    async fn recorded_events_replay_to_the_stored_session() {
+        let (_directory, _cards, sessions) = setup().await;
+        let mut session = insert_session(&sessions).await;
+        let user_id = UserId::new("alice");
+        for hint in [SessionHint::WordLength, SessionHint::TranslatedDefinition] {
+            let events = vec![StudySessionEvent::HintRevealed { hint }];
+            let expected_version = session.version;
+            for event in &events {
+                apply(&mut session, event);
+            }
+            session = sessions
+                .commit_transition(StudySessionCommit {
+                    session,
+                    expected_version,
+                    card_progress: Vec::new(),
+                    selected_test_card: None,
+                    review_log: Vec::new(),
+                    accepted_translation: None,
+                    undoable: true,
+                    events,
+                })
+                .await
+                .unwrap();
+        }
+        let session = sessions
+            .undo_transition(UndoTransitionRequest {
+                user_id: user_id.clone(),
+                session_id: session.id.clone(),
+                expected_version: session.version,
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        sessions
+            .end(EndSessionRequest {
+                user_id: user_id.clone(),
+                session_id: session.id.clone(),
+                expected_version: session.version,
+                status: StudySessionStatus::Cancelled,
+            })
+            .await
+            .unwrap();
+
+        let transitions = sessions.events(&user_id, &session.id).await.unwrap();
+
+        assert_eq!(
+            transitions
+                .iter()
+                .map(|transition| transition.version)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert_eq!(
+            replay(&transitions),
+            sessions.find(&user_id, &session.id).await.unwrap()
+        );
+        assert!(
+            sessions
+                .events(&UserId::new("bob"), &session.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn active_sessions_are_kept_until_they_go_idle() {
+        let (_directory, _cards, sessions) = setup().await;
+        let session = insert_session(&sessions).await;
+        let user_id = UserId::new("alice");
+        let profile_id = ProfileId::new("profile");
+
+        assert_eq!(sessions.expire_idle(0).await.unwrap(), 0);
+        assert_eq!(
+            sessions.list_active(&user_id, &profile_id).await.unwrap(),
+            vec![session.clone()]
+        );
+        assert_eq!(sessions.expire_idle(i64::MAX).await.unwrap(), 1);
+        assert!(
+            sessions
+                .list_active(&user_id, &profile_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(sessions.find(&user_id, &session.id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn upgrades_preferences_saved_before_review_mode() {
+        let directory = TempDir::new().unwrap();
+        let database_path = directory.path().join("sessions.db");
+        SqliteUserRepository::new(&database_path).unwrap();
+        SqliteLanguageProfileRepository::new(&database_path).unwrap();
+        SqliteCardRepository::new(&database_path).unwrap();
+        Connection::open(&database_path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE study_session_preferences (
+                    user_id TEXT NOT NULL,
+                    profile_id TEXT NOT NULL,
+                    mode TEXT NOT NULL,
+                    direction TEXT,
+                    min_score INTEGER,
+                    max_score INTEGER,
+                    cards_per_set INTEGER,
+                    pronunciation_check_enabled INTEGER NOT NULL,
+                    pronunciation_score_threshold INTEGER NOT NULL,
+                    PRIMARY KEY (profile_id, mode)
+                );
+                INSERT INTO study_session_preferences
+                VALUES ('alice', 'profile', 'learning', NULL, NULL, NULL, 7, 0, 75);",
+            )
+            .unwrap();
+
+        let preferences = SqliteStudySessionRepository::new(&database_path)
+            .unwrap()
+            .find_preferences(
+                &UserId::new("alice"),
+                &ProfileId::new("profile"),
+                StudySessionMode::Learning,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(preferences.cards_per_set, Some(7));
+        assert_eq!(preferences.new_cards_per_day, None);
+        assert_eq!(preferences.max_cards, None);
+        assert_eq!(preferences.answer_strictness, AnswerStrictness::Tolerant);
+    }
+
     #[test]
     fn decodes_sessions_and_reports_written_before_strict_scoring() {
         let json = r#"{
@@ -799,13 +2823,24 @@ mod tests {
             "pronunciationDisableRequired":false,
             "awaitingContinue":false,
             "currentSetFailed":false,
-            "results":[],
+            "results":[{
+                "cardId":"card",
+                "word":"水",
+                "isCorrect":false,
+                "submittedAnswers":["fire"],
+                "scoreDelta":-2
+            }],
             "version":1
         }"#;
 
         let session = SqliteStudySessionRepository::decode(json.to_string()).unwrap();
 
         assert_eq!(session.pronunciation_score_threshold, 75);
+        assert_eq!(session.answer_format, AnswerFormat::Written);
+        assert_eq!(session.answer_strictness, AnswerStrictness::Tolerant);
+        assert!(!session.retype_pending);
+        assert!(session.hints_used.is_empty());
+        assert_eq!(session.results[0].outcome, AnswerOutcome::Incorrect);
         assert_eq!(session.pronunciation_attempts[0].strict_score, 87);
         assert_eq!(session.pronunciation_attempts[0].scoring_version, 1);
         assert!(session.pronunciation_attempts[0].issues.is_empty());
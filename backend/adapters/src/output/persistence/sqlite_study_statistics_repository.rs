@@ -0,0 +1,498 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use application::{
+    ports::{
+        input::{
+            card_catalog::models::{CardDirection, CardId},
+            language_profile::models::ProfileId,
+            local_user::models::UserId,
+            study_statistics::models::{HardCard, ScoreBucket},
+        },
+        output::repository::study_statistics::{
+            StudyStatisticsRepository,
+            models::{DailyAnswerCounts, StudyStatisticsRepositoryError},
+        },
+    },
+    usecases::card_scheduling::DAY_MILLIS,
+};
+use async_trait::async_trait;
+use rusqlite::{Connection, ErrorCode, params};
+use thiserror::Error;
+
+use super::sqlite_review_log_repository::create_review_log;
+
+#[derive(Debug, Error)]
+pub enum SqliteStudyStatisticsRepositoryInitError {
+    #[error("failed to create database directory {path:?}: {source}")]
+    CreateDirectory {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to open study statistics database: {0}")]
+    Open(#[source] rusqlite::Error),
+    #[error("failed to initialize study statistics database: {0}")]
+    Initialize(#[source] rusqlite::Error),
+}
+
+#[derive(Clone)]
+pub struct SqliteStudyStatisticsRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteStudyStatisticsRepository {
+    pub fn new(
+        database_path: impl AsRef<Path>,
+    ) -> Result<Self, SqliteStudyStatisticsRepositoryInitError> {
+        let database_path = database_path.as_ref();
+        if let Some(parent) = database_path
+            .parent()
+            .filter(|path| !path.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|source| {
+                SqliteStudyStatisticsRepositoryInitError::CreateDirectory {
+                    path: parent.to_path_buf(),
+                    source,
+                }
+            })?;
+        }
+
+        let connection = Connection::open(database_path)
+            .map_err(SqliteStudyStatisticsRepositoryInitError::Open)?;
+        connection
+            .execute_batch("PRAGMA foreign_keys = ON;")
+            .and_then(|()| create_review_log(&connection))
+            .map_err(SqliteStudyStatisticsRepositoryInitError::Initialize)?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>, StudyStatisticsRepositoryError> {
+        self.connection
+            .lock()
+            .map_err(|_| StudyStatisticsRepositoryError::Unavailable)
+    }
+
+    fn map_error(error: rusqlite::Error) -> StudyStatisticsRepositoryError {
+        match &error {
+            rusqlite::Error::SqliteFailure(details, _)
+                if matches!(
+                    details.code,
+                    ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked
+                ) =>
+            {
+                StudyStatisticsRepositoryError::Unavailable
+            }
+            _ => StudyStatisticsRepositoryError::Unexpected(error.to_string()),
+        }
+    }
+
+    fn map_join(error: tokio::task::JoinError) -> StudyStatisticsRepositoryError {
+        StudyStatisticsRepositoryError::Unexpected(format!(
+            "study statistics repository task failed: {error}"
+        ))
+    }
+
+    fn ensure_profile(
+        connection: &Connection,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+    ) -> Result<(), StudyStatisticsRepositoryError> {
+        let owned = connection
+            .query_row(
+                "SELECT EXISTS(
+                    SELECT 1 FROM language_profiles WHERE id = ?1 AND user_id = ?2
+                )",
+                params![profile_id.as_str(), user_id.as_str()],
+                |row| row.get::<_, bool>(0),
+            )
+            .map_err(Self::map_error)?;
+        if owned {
+            Ok(())
+        } else {
+            Err(StudyStatisticsRepositoryError::NotFound)
+        }
+    }
+
+    fn parse_direction(value: String) -> rusqlite::Result<CardDirection> {
+        match value.as_str() {
+            "straight" => Ok(CardDirection::Straight),
+            "reverse" => Ok(CardDirection::Reverse),
+            _ => Err(rusqlite::Error::InvalidQuery),
+        }
+    }
+}
+
+#[async_trait]
+impl StudyStatisticsRepository for SqliteStudyStatisticsRepository {
+    async fn daily_answers(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        since: i64,
+        utc_offset_minutes: i32,
+    ) -> Result<Vec<DailyAnswerCounts>, StudyStatisticsRepositoryError> {
+        let repository = self.clone();
+        let user_id = user_id.clone();
+        let profile_id = profile_id.clone();
+        tokio::task::spawn_blocking(move || {
+            let connection = repository.lock()?;
+            Self::ensure_profile(&connection, &user_id, &profile_id)?;
+            let mut statement = connection
+                .prepare(
+                    "SELECT (r.reviewed_at + ?3) / ?4 AS day, COUNT(*), SUM(r.is_correct),
+                            COUNT(DISTINCT r.card_id),
+                            CAST(AVG(CASE WHEN r.is_correct THEN r.response_time_millis END)
+                                AS INTEGER)
+                     FROM review_log r
+                     WHERE r.profile_id = ?1 AND r.reviewed_at >= ?2 AND r.undone_at IS NULL
+                       AND NOT EXISTS (
+                            SELECT 1 FROM review_log g
+                            WHERE g.regrades_sequence = r.sequence AND g.undone_at IS NULL
+                       )
+                     GROUP BY day
+                     ORDER BY day ASC",
+                )
+                .map_err(Self::map_error)?;
+            statement
+                .query_map(
+                    params![
+                        profile_id.as_str(),
+                        since,
+                        i64::from(utc_offset_minutes) * 60_000,
+                        DAY_MILLIS,
+                    ],
+                    |row| {
+                        Ok(DailyAnswerCounts {
+                            day: row.get(0)?,
+                            answers: row.get(1)?,
+                            correct: row.get(2)?,
+                            distinct_cards: row.get(3)?,
+                            average_response_millis: row.get(4)?,
+                        })
+                    },
+                )
+                .map_err(Self::map_error)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(Self::map_error)
+        })
+        .await
+        .map_err(Self::map_join)?
+    }
+
+    async fn score_distribution(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+    ) -> Result<Vec<ScoreBucket>, StudyStatisticsRepositoryError> {
+        let repository = self.clone();
+        let user_id = user_id.clone();
+        let profile_id = profile_id.clone();
+        tokio::task::spawn_blocking(move || {
+            let connection = repository.lock()?;
+            Self::ensure_profile(&connection, &user_id, &profile_id)?;
+            let mut statement = connection
+                .prepare(
+                    "SELECT score, COUNT(*) FROM cards
+                     WHERE profile_id = ?1
+                     GROUP BY score
+                     ORDER BY score ASC",
+                )
+                .map_err(Self::map_error)?;
+            statement
+                .query_map(params![profile_id.as_str()], |row| {
+                    Ok(ScoreBucket {
+                        score: row.get(0)?,
+                        cards: row.get(1)?,
+                    })
+                })
+                .map_err(Self::map_error)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(Self::map_error)
+        })
+        .await
+        .map_err(Self::map_join)?
+    }
+
+    async fn hardest_cards(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        limit: usize,
+    ) -> Result<Vec<HardCard>, StudyStatisticsRepositoryError> {
+        let repository = self.clone();
+        let user_id = user_id.clone();
+        let profile_id = profile_id.clone();
+        tokio::task::spawn_blocking(move || {
+            let connection = repository.lock()?;
+            Self::ensure_profile(&connection, &user_id, &profile_id)?;
+            let mut statement = connection
+                .prepare(
+                    "SELECT c.id, c.word, c.direction, c.score, COUNT(*) AS answers,
+                            SUM(1 - r.is_correct) AS failures
+                     FROM review_log r
+                     JOIN cards c ON c.id = r.card_id
+                     WHERE r.profile_id = ?1 AND c.profile_id = ?1 AND r.undone_at IS NULL
+                       AND NOT EXISTS (
+                            SELECT 1 FROM review_log g
+                            WHERE g.regrades_sequence = r.sequence AND g.undone_at IS NULL
+                       )
+                     GROUP BY c.id
+                     HAVING failures > 0
+                     ORDER BY CAST(failures AS REAL) / answers DESC, failures DESC,
+                              c.score ASC, c.id ASC
+                     LIMIT ?2",
+                )
+                .map_err(Self::map_error)?;
+            statement
+                .query_map(params![profile_id.as_str(), limit as i64], |row| {
+                    Ok(HardCard {
+                        card_id: CardId::new(row.get::<_, String>(0)?),
+                        word: row.get(1)?,
+                        direction: Self::parse_direction(row.get(2)?)?,
+                        score: row.get(3)?,
+                        answers: row.get(4)?,
+                        failures: row.get(5)?,
+                    })
+                })
+                .map_err(Self::map_error)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(Self::map_error)
+        })
+        .await
+        .map_err(Self::map_join)?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use application::ports::{
+        input::{
+            card_catalog::models::{Card, CardStatus, LeechState, Meaning, Word},
+            language_profile::models::{LanguageProfile, LeechAction},
+            local_user::models::LocalUser,
+        },
+        output::repository::{
+            card::CardRepository, language_profile::LanguageProfileRepository, user::UserRepository,
+        },
+    };
+    use tempfile::TempDir;
+
+    use crate::output::persistence::{
+        SqliteCardRepository, SqliteLanguageProfileRepository, SqliteUserRepository,
+    };
+
+    use super::*;
+
+    const HOUR_MILLIS: i64 = 60 * 60 * 1_000;
+
+    async fn setup() -> (TempDir, PathBuf, SqliteStudyStatisticsRepository) {
+        let directory = TempDir::new().unwrap();
+        let database_path = directory.path().join("statistics.db");
+        SqliteUserRepository::new(&database_path)
+            .unwrap()
+            .insert(LocalUser {
+                id: UserId::new("alice"),
+            })
+            .await
+            .unwrap();
+        SqliteLanguageProfileRepository::new(&database_path)
+            .unwrap()
+            .insert(LanguageProfile {
+                id: ProfileId::new("profile"),
+                owner_id: UserId::new("alice"),
+                name: "Japanese".to_string(),
+                source_language: "en-US".to_string(),
+                target_language: "ja-JP".to_string(),
+                leech_threshold: 8,
+                leech_action: LeechAction::Suspend,
+                version: 0,
+            })
+            .await
+            .unwrap();
+        SqliteCardRepository::new(&database_path)
+            .unwrap()
+            .insert_batch(
+                &UserId::new("alice"),
+                &ProfileId::new("profile"),
+                [("water", "水"), ("fire", "火")]
+                    .into_iter()
+                    .map(|(id, word)| Card {
+                        id: CardId::new(id),
+                        profile_id: ProfileId::new("profile"),
+                        direction: CardDirection::Straight,
+                        word: Word {
+                            text: word.to_string(),
+                            readings: Vec::new(),
+                        },
+                        meanings: vec![Meaning {
+                            definition: id.to_string(),
+                            translated_definition: String::new(),
+                            word_translations: vec![id.to_string()],
+                            examples: Vec::new(),
+                        }],
+                        score: 0,
+                        leech: LeechState::default(),
+                        status: CardStatus::Active,
+                        created_at: 0,
+                        version: 0,
+                    })
+                    .collect(),
+            )
+            .await
+            .unwrap();
+        let repository = SqliteStudyStatisticsRepository::new(&database_path).unwrap();
+        (directory, database_path, repository)
+    }
+
+    /// Logs an answer the way the study session repository does and returns its sequence.
+    fn log_answer(
+        connection: &Connection,
+        card_id: &str,
+        is_correct: bool,
+        reviewed_at: i64,
+        regrades_sequence: Option<i64>,
+    ) -> i64 {
+        connection
+            .execute(
+                "INSERT INTO review_log (
+                    profile_id, session_id, card_id, mode, direction, outcome, is_correct,
+                    score_delta, response_time_millis, reviewed_at, regrades_sequence
+                 ) VALUES ('profile', 'session', ?1, 'review', 'straight', ?2, ?3, 0, 1000, ?4, ?5)",
+                params![
+                    card_id,
+                    if is_correct { "correct" } else { "incorrect" },
+                    is_correct,
+                    reviewed_at,
+                    regrades_sequence,
+                ],
+            )
+            .unwrap();
+        connection.last_insert_rowid()
+    }
+
+    fn undo(connection: &Connection, sequence: i64) {
+        connection
+            .execute(
+                "UPDATE review_log SET undone_at = 1 WHERE sequence = ?1",
+                params![sequence],
+            )
+            .unwrap();
+    }
+
+    async fn answers_per_day(
+        repository: &SqliteStudyStatisticsRepository,
+        utc_offset_minutes: i32,
+    ) -> Vec<(i64, usize, usize)> {
+        repository
+            .daily_answers(
+                &UserId::new("alice"),
+                &ProfileId::new("profile"),
+                0,
+                utc_offset_minutes,
+            )
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|counts| (counts.day, counts.answers, counts.correct))
+            .collect()
+    }
+
+    async fn hardest_card_ids(repository: &SqliteStudyStatisticsRepository) -> Vec<CardId> {
+        repository
+            .hardest_cards(&UserId::new("alice"), &ProfileId::new("profile"), 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|card| card.card_id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn daily_answers_are_grouped_by_local_day() {
+        let (_directory, database_path, repository) = setup().await;
+        let connection = Connection::open(&database_path).unwrap();
+        log_answer(
+            &connection,
+            "water",
+            true,
+            DAY_MILLIS - 3 * HOUR_MILLIS - 1,
+            None,
+        );
+        log_answer(
+            &connection,
+            "water",
+            false,
+            DAY_MILLIS - 3 * HOUR_MILLIS,
+            None,
+        );
+        log_answer(&connection, "fire", true, DAY_MILLIS + HOUR_MILLIS, None);
+
+        assert_eq!(
+            answers_per_day(&repository, 0).await,
+            vec![(0, 2, 1), (1, 1, 1)]
+        );
+        assert_eq!(
+            answers_per_day(&repository, 180).await,
+            vec![(0, 1, 1), (1, 2, 1)]
+        );
+        assert_eq!(
+            repository
+                .daily_answers(&UserId::new("alice"), &ProfileId::new("profile"), 0, 180)
+                .await
+                .unwrap()[1],
+            DailyAnswerCounts {
+                day: 1,
+                answers: 2,
+                correct: 1,
+                distinct_cards: 2,
+                average_response_millis: Some(1000),
+            }
+        );
+        assert_eq!(
+            repository
+                .daily_answers(&UserId::new("bob"), &ProfileId::new("profile"), 0, 0)
+                .await,
+            Err(StudyStatisticsRepositoryError::NotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn undone_answers_are_left_out() {
+        let (_directory, database_path, repository) = setup().await;
+        let connection = Connection::open(&database_path).unwrap();
+        log_answer(&connection, "water", false, HOUR_MILLIS, None);
+        let undone = log_answer(&connection, "fire", false, 2 * HOUR_MILLIS, None);
+        undo(&connection, undone);
+
+        assert_eq!(answers_per_day(&repository, 0).await, vec![(0, 1, 0)]);
+        assert_eq!(
+            hardest_card_ids(&repository).await,
+            vec![CardId::new("water")]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_regraded_answer_counts_once_as_its_regrade() {
+        let (_directory, database_path, repository) = setup().await;
+        let connection = Connection::open(&database_path).unwrap();
+        let failed = log_answer(&connection, "water", false, HOUR_MILLIS, None);
+        let accepted = log_answer(&connection, "water", true, HOUR_MILLIS, Some(failed));
+
+        assert_eq!(answers_per_day(&repository, 0).await, vec![(0, 1, 1)]);
+        assert!(hardest_card_ids(&repository).await.is_empty());
+
+        undo(&connection, accepted);
+        assert_eq!(answers_per_day(&repository, 0).await, vec![(0, 1, 0)]);
+        assert_eq!(
+            hardest_card_ids(&repository).await,
+            vec![CardId::new("water")]
+        );
+    }
+}
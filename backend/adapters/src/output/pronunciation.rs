@@ -117,7 +117,7 @@ impl AzurePronunciationAssessor {
                 return Err(PronunciationAssessmentError::InvalidAudio);
             }
             if &audio[offset..offset + 4] == b"data" {
-                if chunk_size == 0 || chunk_size > MAX_PCM_BYTES || chunk_size % 2 != 0 {
+                if chunk_size == 0 || chunk_size > MAX_PCM_BYTES || !chunk_size.is_multiple_of(2) {
                     return Err(PronunciationAssessmentError::InvalidAudio);
                 }
                 return Ok(());
@@ -159,7 +159,7 @@ impl AiSpeechSynthesizer {
     }
 
     fn pcm_to_wav(pcm: &[u8]) -> Result<Vec<u8>, SpeechSynthesisError> {
-        if pcm.is_empty() || pcm.len() > MAX_AUDIO_BYTES - 44 || pcm.len() % 2 != 0 {
+        if pcm.is_empty() || pcm.len() > MAX_AUDIO_BYTES - 44 || !pcm.len().is_multiple_of(2) {
             return Err(SpeechSynthesisError::InvalidResponse);
         }
         let data_size =
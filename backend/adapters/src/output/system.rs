@@ -0,0 +1,32 @@
+use std::{
+    hash::{BuildHasher, RandomState},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use application::ports::output::{Clock, RandomSource};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as i64)
+    }
+}
+
+/// Draws seeds from the randomly keyed hasher of the standard library.
+#[derive(Debug, Default)]
+pub struct SystemRandomSource {
+    state: RandomState,
+    counter: AtomicU64,
+}
+
+impl RandomSource for SystemRandomSource {
+    fn next_seed(&self) -> u64 {
+        self.state
+            .hash_one(self.counter.fetch_add(1, Ordering::Relaxed))
+    }
+}
@@ -8,6 +8,7 @@ pub mod language_profile;
 pub mod local_user;
 pub mod pronunciation_settings;
 pub mod study_session;
+pub mod study_statistics;
 
 pub use ai_settings::AiSettingsUsecase;
 pub use card_catalog::CardCatalogUsecase;
@@ -17,3 +18,4 @@ pub use language_profile::LanguageProfileUsecase;
 pub use local_user::LocalUserUsecase;
 pub use pronunciation_settings::PronunciationSettingsUsecase;
 pub use study_session::StudySessionUsecase;
+pub use study_statistics::StudyStatisticsUsecase;
@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use self::models::{
     Card, CardCatalogError, CardPage, CreateCardsCommand, DeleteCardsCommand, DeleteCardsResult,
     GetCardQuery, ListCardsQuery, PendingInverseCard, PrepareInverseCardsQuery,
-    SaveInverseCardsCommand, UpdateCardCommand,
+    SaveInverseCardsCommand, SetCardStatusCommand, SetCardStatusResult, UpdateCardCommand,
 };
 
 pub mod models;
@@ -21,6 +21,12 @@ pub trait CardCatalogUsecase: Send + Sync {
         command: DeleteCardsCommand,
     ) -> Result<DeleteCardsResult, CardCatalogError>;
 
+    /// Suspends, buries or reactivates the requested cards and returns the number changed.
+    async fn set_card_status(
+        &self,
+        command: SetCardStatusCommand,
+    ) -> Result<SetCardStatusResult, CardCatalogError>;
+
     async fn list_cards(&self, query: ListCardsQuery) -> Result<CardPage, CardCatalogError>;
 
     async fn get_card(&self, query: GetCardQuery) -> Result<Card, CardCatalogError>;
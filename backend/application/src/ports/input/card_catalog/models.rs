@@ -65,10 +65,57 @@ pub struct Card {
     pub word: Word,
     pub meanings: Vec<Meaning>,
     pub score: i32,
+    pub leech: LeechState,
+    pub status: CardStatus,
     pub created_at: i64,
     pub version: u64,
 }
 
+/// Tracks a card that keeps being forgotten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LeechState {
+    /// Graded failures since the last correct answer.
+    pub consecutive_failures: u32,
+    /// Set once the failures reach the leech threshold of the profile; cleared only by the
+    /// learner.
+    pub is_leech: bool,
+}
+
+/// Whether a card takes part in study sessions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CardStatus {
+    #[default]
+    Active,
+    /// Out of rotation until the learner unsuspends it.
+    Suspended,
+    /// Out of rotation until `until`, in Unix milliseconds.
+    Buried { until: i64 },
+}
+
+/// How well a graded answer was recalled, in SM-2 terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewGrade {
+    Again,
+    Hard,
+    Good,
+    Easy,
+}
+
+/// Spaced-repetition memory state of a card that has been reviewed at least once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardSchedule {
+    pub card_id: CardId,
+    /// SM-2 ease factor in thousandths, so `2500` is an ease of 2.5.
+    pub ease_permille: u32,
+    pub interval_days: u32,
+    /// Successful reviews since the last lapse.
+    pub repetitions: u32,
+    pub lapses: u32,
+    pub due_at: i64,
+    pub last_reviewed_at: i64,
+    pub introduced_at: i64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NewCard {
     pub direction: CardDirection,
@@ -82,6 +129,8 @@ pub struct CardSummary {
     pub word: String,
     pub direction: CardDirection,
     pub score: i32,
+    pub leech: LeechState,
+    pub status: CardStatus,
     pub created_at: i64,
 }
 
@@ -144,6 +193,19 @@ pub struct DeleteCardsResult {
     pub deleted_count: usize,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetCardStatusCommand {
+    pub user_id: UserId,
+    pub profile_id: ProfileId,
+    pub card_ids: Vec<CardId>,
+    pub status: CardStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetCardStatusResult {
+    pub updated_count: usize,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ListCardsQuery {
     pub user_id: UserId,
@@ -152,6 +214,8 @@ pub struct ListCardsQuery {
     pub direction: Option<CardDirection>,
     pub min_score: Option<i32>,
     pub max_score: Option<i32>,
+    /// `Some(true)` lists only leeches and `Some(false)` only the other cards.
+    pub leech: Option<bool>,
     pub sort_field: CardSortField,
     pub sort_direction: SortDirection,
     pub cursor: Option<CardListCursor>,
@@ -169,6 +233,8 @@ pub struct GetCardQuery {
 pub struct CardChanges {
     pub word: Option<Word>,
     pub meanings: Option<Vec<Meaning>>,
+    /// Unflags a leech and resets its failure count.
+    pub clear_leech: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -180,10 +246,18 @@ pub struct UpdateCardCommand {
     pub changes: CardChanges,
 }
 
+/// How session cards are ordered, or in test sessions how the next card is picked.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CardOrder {
     OldestFirst,
     Random,
+    /// Lowest score first.
+    WeakestFirst,
+    /// At random, with lower scores more likely. Repositories return the candidates oldest
+    /// first and leave the draw to the caller.
+    ScoreWeighted,
+    /// Never answered cards first, then the ones answered longest ago.
+    LeastRecentlySeen,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -208,6 +282,33 @@ pub struct CardSelectionQuery {
     pub max_score: Option<i32>,
     pub order: CardOrder,
     pub limit: Option<usize>,
+    /// Cards buried until after this instant are skipped; suspended cards always are.
+    pub available_at: i64,
+    /// Cards left out of the selection, such as the recently tested ones.
+    pub excluded_ids: Vec<CardId>,
+}
+
+/// The columns session selection needs to choose a card without loading all of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardCandidate {
+    pub id: CardId,
+    pub direction: CardDirection,
+    pub score: i32,
+    /// When the card was last answered, if it ever was.
+    pub last_seen_at: Option<i64>,
+}
+
+/// Selects a review queue of overdue cards followed by never-reviewed ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DueCardsQuery {
+    pub user_id: UserId,
+    pub profile_id: ProfileId,
+    pub direction: Option<CardDirection>,
+    pub min_score: Option<i32>,
+    pub max_score: Option<i32>,
+    /// Reviewed cards whose next review is at or before this instant are due.
+    pub due_at: i64,
+    pub new_card_limit: usize,
 }
 
 #[derive(Debug, Error, PartialEq, Eq)]
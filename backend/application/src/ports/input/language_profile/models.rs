@@ -31,6 +31,16 @@ impl From<&str> for ProfileId {
     }
 }
 
+/// What happens to a card once it has failed `leech_threshold` times in a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LeechAction {
+    /// Keeps the card out of study sessions until the learner returns it to rotation.
+    #[default]
+    Suspend,
+    /// Only marks the card so that it can be found and reworked.
+    Tag,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LanguageProfile {
     pub id: ProfileId,
@@ -38,6 +48,9 @@ pub struct LanguageProfile {
     pub name: String,
     pub source_language: String,
     pub target_language: String,
+    /// Consecutive failures that make a card a leech; `0` turns leech detection off.
+    pub leech_threshold: u32,
+    pub leech_action: LeechAction,
     pub version: u64,
 }
 
@@ -73,6 +86,8 @@ pub struct LanguageProfileChanges {
     pub name: Option<String>,
     pub source_language: Option<String>,
     pub target_language: Option<String>,
+    pub leech_threshold: Option<u32>,
+    pub leech_action: Option<LeechAction>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -2,8 +2,9 @@ use async_trait::async_trait;
 
 use self::models::{
     ApplyStudySessionActionCommand, AssessPronunciationCommand, CreateStudySessionCommand,
-    EndStudySessionCommand, GetStudySessionPreferencesQuery, StudySessionError,
-    StudySessionPreferences, StudySessionTransition, StudySessionView,
+    EndStudySessionCommand, GetStudySessionPreferencesQuery, ListActiveStudySessionsQuery,
+    ResumeStudySessionQuery, StudySessionError, StudySessionPreferences, StudySessionTransition,
+    StudySessionView,
 };
 
 pub mod models;
@@ -20,6 +21,18 @@ pub trait StudySessionUsecase: Send + Sync {
         command: CreateStudySessionCommand,
     ) -> Result<StudySessionView, StudySessionError>;
 
+    /// Lists the unfinished sessions of a profile, most recently used first.
+    async fn list_active_sessions(
+        &self,
+        query: ListActiveStudySessionsQuery,
+    ) -> Result<Vec<StudySessionView>, StudySessionError>;
+
+    /// Reopens an unfinished session, skipping cards deleted since it was last used.
+    async fn resume_session(
+        &self,
+        query: ResumeStudySessionQuery,
+    ) -> Result<StudySessionView, StudySessionError>;
+
     async fn apply_action(
         &self,
         command: ApplyStudySessionActionCommand,
@@ -39,4 +52,8 @@ pub trait StudySessionUsecase: Send + Sync {
         &self,
         command: EndStudySessionCommand,
     ) -> Result<StudySessionView, StudySessionError>;
+
+    /// Discards the unfinished sessions left idle longer than the configured expiry and returns
+    /// how many were discarded. Inbound adapters run it once at startup.
+    async fn expire_idle_sessions(&self) -> Result<usize, StudySessionError>;
 }
@@ -1,7 +1,7 @@
 use thiserror::Error;
 
 use crate::ports::input::{
-    card_catalog::models::{Card, CardDirection, CardId},
+    card_catalog::models::{Card, CardDirection, CardId, CardOrder, CardSchedule, LeechState},
     language_profile::models::ProfileId,
     local_user::models::UserId,
 };
@@ -27,6 +27,35 @@ impl SessionId {
 pub enum StudySessionMode {
     Learning,
     Test,
+    /// Works through the cards that are due for spaced-repetition review.
+    Review,
+}
+
+/// How answers are given while a card is being tested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnswerFormat {
+    #[default]
+    Written,
+    /// The translation is picked from options that mix it with distractors from other cards.
+    MultipleChoice,
+    /// Only the card audio is played and the word itself is typed back.
+    Dictation,
+    /// The word is typed into the blank of one of its usage examples.
+    Cloze,
+    /// The word is shown and its reading (kana, stressed spelling or IPA) is typed back.
+    Reading,
+}
+
+/// How close a typed answer must be to an expected one to be accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnswerStrictness {
+    /// Only answers that are identical once normalized are accepted.
+    Exact,
+    /// Small typos are accepted.
+    #[default]
+    Tolerant,
+    /// Answers with several typos are accepted.
+    Lenient,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -54,9 +83,32 @@ pub struct SessionAnswerResult {
     pub card_id: CardId,
     pub word: String,
     pub is_correct: bool,
+    pub outcome: AnswerOutcome,
     pub submitted_answers: Vec<String>,
     pub pronunciation_reports: Vec<PronunciationAssessmentReport>,
     pub score_delta: i32,
+    /// Hints revealed before the card was answered, in order.
+    pub hints_used: Vec<SessionHint>,
+    /// When the answer that completed the card was given, in milliseconds since the Unix epoch;
+    /// `None` for results recorded before answers were timed.
+    pub answered_at: Option<i64>,
+    /// Time from presenting the card to the answer that completed it.
+    pub response_time_millis: Option<u64>,
+}
+
+/// Help about the expected answer of a test card, offered in declaration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionHint {
+    FirstLetter,
+    WordLength,
+    TranslatedDefinition,
+    ExampleTranslation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevealedHint {
+    pub hint: SessionHint,
+    pub text: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -96,12 +148,32 @@ pub struct StudySession {
     pub phase: StudySessionPhase,
     pub status: StudySessionStatus,
     pub filter: SessionFilter,
+    pub answer_format: AnswerFormat,
+    pub answer_strictness: AnswerStrictness,
     pub pronunciation_check_enabled: bool,
     pub pronunciation_score_threshold: u8,
     pub cards_per_set: usize,
+    /// Whether a failed Learn set is retried with only the cards missed in its mini-test.
+    pub retry_missed_only: bool,
+    /// How the next card of a test session is picked.
+    pub card_order: CardOrder,
+    /// Number of recently tested cards of the profile that a test session avoids.
+    pub recent_ban_window: usize,
+    /// Completes a test session once this many cards are answered.
+    pub max_cards: Option<usize>,
+    /// Completes a test session at the first card finished at or after this instant, in
+    /// milliseconds since the Unix epoch.
+    pub ends_at: Option<i64>,
+    /// Completes a test session after this many correct answers in a row.
+    pub correct_streak_goal: Option<usize>,
     pub card_ids: Vec<CardId>,
     pub test_order: Vec<CardId>,
+    /// Seeds the card order, so that replaying the same actions yields the same order.
+    pub seed: u64,
     pub current_set_index: usize,
+    /// The missed cards of the current set while they are retried on their own, in set order;
+    /// empty while the whole set is studied.
+    pub retry_card_ids: Vec<CardId>,
     pub current_card_index: usize,
     pub provided_answers: Vec<String>,
     pub completed_meaning_indices: Vec<usize>,
@@ -110,6 +182,18 @@ pub struct StudySession {
     pub pronunciation_technical_failures: u8,
     pub pronunciation_disable_required: bool,
     pub awaiting_continue: bool,
+    /// Set after an almost correct answer, while the learner retypes the current card.
+    pub retype_pending: bool,
+    /// The schedule of the card under feedback from before it was graded, so that an accepted
+    /// answer can be re-graded; `None` for cards that had never been reviewed.
+    pub schedule_before_answer: Option<CardSchedule>,
+    /// The leech state of the card under feedback from before it was graded.
+    pub leech_before_answer: Option<LeechState>,
+    /// Hints revealed for the current card, in order.
+    pub hints_used: Vec<SessionHint>,
+    /// When the current test card was presented for an answer, in milliseconds since the Unix
+    /// epoch; `None` in the study phase.
+    pub card_presented_at: Option<i64>,
     pub current_set_failed: bool,
     pub results: Vec<SessionAnswerResult>,
     pub version: u64,
@@ -121,19 +205,50 @@ pub enum CurrentCardView {
     Test {
         id: CardId,
         direction: CardDirection,
-        prompt: String,
+        /// Hidden in dictation and cloze drills, where the word itself is the answer.
+        prompt: Option<String>,
         readings: Vec<String>,
         remaining_meanings: usize,
         total_meanings: usize,
+        /// Answer options in multiple-choice sessions; empty when the answer must be typed.
+        options: Vec<String>,
+        /// The usage example to fill in during cloze drills; `None` when no example of the card
+        /// contains the word and its translation is asked for instead.
+        cloze: Option<ClozePrompt>,
+        hints: Vec<RevealedHint>,
+        /// Whether another hint can be revealed.
+        hint_available: bool,
     },
 }
 
+/// A usage example with the card word blanked out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClozePrompt {
+    pub sentence_before: String,
+    pub sentence_after: String,
+    /// The translation of the whole example, shown as a hint.
+    pub translation: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StudySessionProgress {
     pub current_card: usize,
     pub total_cards: usize,
     pub current_set: usize,
     pub total_sets: usize,
+    /// Position of the current card within the current pass over its set, which is shorter
+    /// than the set when only missed cards are retried; zero outside learn mode.
+    pub current_set_card: usize,
+    pub set_cards: usize,
+}
+
+/// What is left of the limits of a bounded test session; each part is `None` when its limit
+/// is not set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemainingBudget {
+    pub cards: Option<usize>,
+    pub time_millis: Option<u64>,
+    pub correct_in_a_row: Option<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -148,6 +263,8 @@ pub struct StudySessionView {
     pub id: SessionId,
     pub profile_id: ProfileId,
     pub mode: StudySessionMode,
+    pub answer_format: AnswerFormat,
+    pub answer_strictness: AnswerStrictness,
     pub phase: StudySessionPhase,
     pub status: StudySessionStatus,
     pub pronunciation_check_enabled: bool,
@@ -157,22 +274,68 @@ pub struct StudySessionView {
     pub pronunciation_technical_failures: u8,
     pub pronunciation_disable_required: bool,
     pub awaiting_continue: bool,
+    pub retype_pending: bool,
     pub current_card: Option<CurrentCardView>,
+    /// Review-queue cards still waiting for an answer; `None` outside review mode.
+    pub due_today: Option<usize>,
     pub progress: StudySessionProgress,
+    /// `None` unless the session is a test session with limits.
+    pub remaining_budget: Option<RemainingBudget>,
     pub summary: StudySessionSummary,
     pub version: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AnswerFeedback {
+    pub outcome: AnswerOutcome,
     pub is_correct: bool,
+    /// The accepted translation or spelling; for almost correct answers, the expected spelling
+    /// closest to the answer.
     pub matched_answer: Option<String>,
+    /// Similarity of the answer to the closest expected answer, in percent.
+    pub similarity_percent: u8,
     pub card: Card,
     pub matched_meaning_index: Option<usize>,
     pub completed_meaning_indices: Vec<usize>,
     pub card_completed: bool,
     pub remaining_meanings: usize,
     pub score_delta: i32,
+    /// Set when a typed reading is spelled right but stresses the wrong syllable.
+    pub misplaced_stress: bool,
+    /// The normalizations of the answer language that were needed for the answer to match.
+    pub normalizations: Vec<AnswerNormalization>,
+    /// Time from presenting the card to the answer.
+    pub response_time_millis: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnswerOutcome {
+    Correct,
+    /// Close to an expected answer without reaching the strictness threshold. The card stays
+    /// open so that the learner can retype it once.
+    AlmostCorrect,
+    Incorrect,
+    /// The learner gave up on the card without answering.
+    GaveUp,
+}
+
+/// A language-specific rewrite applied to both the answer and the expected text before they
+/// are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnswerNormalization {
+    /// Russian ё is compared as е.
+    FoldYo,
+    /// Combining accents that mark Russian stress are ignored.
+    StripStressMarks,
+    /// Full-width Latin letters and digits and half-width katakana are compared in their
+    /// usual width.
+    FoldWidth,
+    /// Katakana is compared as hiragana.
+    FoldKana,
+    /// Parenthesised parts such as the "(to)" of "(to) run" are optional.
+    IgnoreOptionalParts,
+    /// A leading "to", "a", "an" or "the" is ignored.
+    IgnoreLeadingArticle,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -217,8 +380,28 @@ pub struct CreateStudySessionCommand {
     pub min_score: Option<i32>,
     pub max_score: Option<i32>,
     pub cards_per_set: Option<usize>,
+    /// Whether failed sets retry only their missed cards; learn mode only, where `None` uses
+    /// the default.
+    pub retry_missed_only: Option<bool>,
+    /// Daily cap of never-reviewed cards added to a review queue; review mode only.
+    pub new_cards_per_day: Option<usize>,
+    /// How the next card of a test session is picked; test mode only, where `None` uses the
+    /// default.
+    pub card_order: Option<CardOrder>,
+    /// Number of recently tested cards a test session avoids picking again; test mode only.
+    pub recent_ban_window: Option<usize>,
+    /// Limits that complete a test session on their own, whichever is reached first; test
+    /// mode only, where `None` leaves the session unbounded.
+    pub max_cards: Option<usize>,
+    pub time_limit_minutes: Option<u32>,
+    pub correct_streak_goal: Option<usize>,
+    pub answer_format: AnswerFormat,
+    pub answer_strictness: AnswerStrictness,
     pub pronunciation_check_enabled: bool,
     pub pronunciation_score_threshold: u8,
+    /// The learner's offset from UTC; the daily new-card cap of a review queue counts from
+    /// local midnight.
+    pub utc_offset_minutes: i32,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -236,6 +419,15 @@ pub struct StudySessionPreferences {
     pub min_score: Option<i32>,
     pub max_score: Option<i32>,
     pub cards_per_set: Option<usize>,
+    pub retry_missed_only: Option<bool>,
+    pub new_cards_per_day: Option<usize>,
+    pub card_order: Option<CardOrder>,
+    pub recent_ban_window: Option<usize>,
+    pub max_cards: Option<usize>,
+    pub time_limit_minutes: Option<u32>,
+    pub correct_streak_goal: Option<usize>,
+    pub answer_format: AnswerFormat,
+    pub answer_strictness: AnswerStrictness,
     pub pronunciation_check_enabled: bool,
     pub pronunciation_score_threshold: u8,
 }
@@ -249,6 +441,15 @@ impl StudySessionPreferences {
             min_score: None,
             max_score: None,
             cards_per_set: (mode == StudySessionMode::Learning).then_some(5),
+            retry_missed_only: (mode == StudySessionMode::Learning).then_some(false),
+            new_cards_per_day: (mode == StudySessionMode::Review).then_some(20),
+            card_order: (mode == StudySessionMode::Test).then_some(CardOrder::ScoreWeighted),
+            recent_ban_window: (mode == StudySessionMode::Test).then_some(10),
+            max_cards: None,
+            time_limit_minutes: None,
+            correct_streak_goal: None,
+            answer_format: AnswerFormat::Written,
+            answer_strictness: AnswerStrictness::Tolerant,
             pronunciation_check_enabled: false,
             pronunciation_score_threshold: 75,
         }
@@ -260,12 +461,119 @@ pub enum StudySessionAction {
     PreviousStudyCard,
     NextStudyCard,
     StartMiniTest,
-    SubmitWrittenAnswer { answer: String },
+    SubmitWrittenAnswer {
+        answer: String,
+    },
+    /// Picks one of the options offered by a multiple-choice test card.
+    SelectAnswerOption {
+        index: usize,
+    },
     ContinueAfterFeedback,
-    RegisterPronunciationCaptureFailure { message: String },
+    /// Fails the current test card without an answer and reveals it in the feedback.
+    GiveUp,
+    /// Reveals the next hint about the current test card; each hint reduces the credit for
+    /// answering it correctly.
+    RevealHint,
+    /// Re-grades a rejected written answer as correct while its feedback is shown, optionally
+    /// adding it to the translations of the meaning it was closest to.
+    AcceptLastAnswer {
+        add_to_translations: bool,
+    },
+    /// Returns the session to its state before the latest action, reverting the score and
+    /// schedule changes that action made.
+    UndoLastAction,
+    RegisterPronunciationCaptureFailure {
+        message: String,
+    },
     DisablePronunciation,
 }
 
+/// A change recorded by a session transition. Applying the events of every transition in
+/// order to an empty history rebuilds the session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StudySessionEvent {
+    Started {
+        session: Box<StudySession>,
+    },
+    StudyCardShown {
+        index: usize,
+    },
+    MiniTestStarted {
+        test_order: Vec<CardId>,
+    },
+    HintRevealed {
+        hint: SessionHint,
+    },
+    AnswerGraded(Box<GradedAnswer>),
+    /// A rejected written answer was accepted while its feedback was shown.
+    AnswerAccepted {
+        answer: String,
+        meaning_index: Option<usize>,
+        score_delta: i32,
+    },
+    PronunciationAttempted {
+        report: PronunciationAssessmentReport,
+    },
+    PronunciationCaptureFailed,
+    PronunciationDisabled,
+    /// Moves past the card under feedback; `next_test_card` is the card picked next in test
+    /// mode, where `None` completes the session.
+    FeedbackDismissed {
+        next_test_card: Option<CardId>,
+    },
+    /// A new test card was put in front of the learner.
+    CardPresented {
+        at: i64,
+    },
+    SetPassed,
+    SetRetried,
+    /// The set failed and only the cards missed in its mini-test are studied again.
+    MissedCardsRetried {
+        card_ids: Vec<CardId>,
+    },
+    /// Cards deleted since the session was last used were dropped from its queue.
+    CardsRemoved {
+        card_ids: Vec<CardId>,
+        next_test_card: Option<CardId>,
+    },
+    /// The latest undoable transition was reverted.
+    Undone,
+    Ended {
+        status: StudySessionStatus,
+    },
+}
+
+/// An answer graded against the current test card, or the card failed on pronunciation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GradedAnswer {
+    pub card_id: CardId,
+    pub word: String,
+    /// `None` when the learner gave up or failed the pronunciation check.
+    pub answer: Option<String>,
+    pub outcome: AnswerOutcome,
+    pub matched_answer: Option<String>,
+    pub similarity_percent: u8,
+    /// The meaning the answer completed; `None` when it answered the whole card or none.
+    pub meaning_index: Option<usize>,
+    pub card_completed: bool,
+    pub remaining_meanings: usize,
+    pub score_delta: i32,
+    pub misplaced_stress: bool,
+    pub normalizations: Vec<AnswerNormalization>,
+    pub schedule_before_answer: Option<CardSchedule>,
+    pub leech_before_answer: LeechState,
+    pub answered_at: i64,
+    /// `None` when the card was presented before answers were timed.
+    pub response_time_millis: Option<u64>,
+}
+
+/// The events one transition recorded, stored under the session version it produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedTransition {
+    pub version: u64,
+    pub events: Vec<StudySessionEvent>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ApplyStudySessionActionCommand {
     pub user_id: UserId,
@@ -282,6 +590,18 @@ pub struct AssessPronunciationCommand {
     pub audio: Vec<u8>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListActiveStudySessionsQuery {
+    pub user_id: UserId,
+    pub profile_id: ProfileId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResumeStudySessionQuery {
+    pub user_id: UserId,
+    pub session_id: SessionId,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EndStudySessionCommand {
     pub user_id: UserId,
@@ -303,6 +623,8 @@ pub enum StudySessionError {
     Conflict,
     #[error("pronunciation assessment is not configured")]
     PronunciationNotConfigured,
+    #[error("speech generation is not configured")]
+    SpeechNotConfigured,
     #[error("study session operation failed: {0}")]
     Unexpected(String),
 }
@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+
+use self::models::{
+    AccuracyPoint, DailyReviewCount, HardCard, HardestCardsQuery, ProfileStatisticsQuery,
+    ScoreBucket, StatisticsPeriodQuery, StudyStatisticsError, StudyStreak, StudyStreakQuery,
+};
+
+pub mod models;
+
+/// Progress statistics of a language profile built from the review log.
+#[async_trait]
+pub trait StudyStatisticsUsecase: Send + Sync {
+    /// Returns the share of correct answers for every day with answers, oldest first.
+    async fn accuracy_over_time(
+        &self,
+        query: StatisticsPeriodQuery,
+    ) -> Result<Vec<AccuracyPoint>, StudyStatisticsError>;
+
+    /// Returns how many distinct cards were reviewed on every day with answers, oldest first.
+    async fn cards_reviewed_per_day(
+        &self,
+        query: StatisticsPeriodQuery,
+    ) -> Result<Vec<DailyReviewCount>, StudyStatisticsError>;
+
+    /// Returns how many cards of the profile have each score, lowest score first.
+    async fn score_distribution(
+        &self,
+        query: ProfileStatisticsQuery,
+    ) -> Result<Vec<ScoreBucket>, StudyStatisticsError>;
+
+    async fn streak(&self, query: StudyStreakQuery) -> Result<StudyStreak, StudyStatisticsError>;
+
+    /// Returns the existing cards that were answered wrong most often relative to their reviews.
+    async fn hardest_cards(
+        &self,
+        query: HardestCardsQuery,
+    ) -> Result<Vec<HardCard>, StudyStatisticsError>;
+}
@@ -0,0 +1,88 @@
+use thiserror::Error;
+
+use crate::ports::input::{
+    card_catalog::models::{CardDirection, CardId},
+    language_profile::models::ProfileId,
+    local_user::models::UserId,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatisticsPeriodQuery {
+    pub user_id: UserId,
+    pub profile_id: ProfileId,
+    /// Answers given before this instant are ignored.
+    pub since: i64,
+    /// Offset of the user's local time from UTC, used to split answers into days.
+    pub utc_offset_minutes: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileStatisticsQuery {
+    pub user_id: UserId,
+    pub profile_id: ProfileId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StudyStreakQuery {
+    pub user_id: UserId,
+    pub profile_id: ProfileId,
+    pub utc_offset_minutes: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HardestCardsQuery {
+    pub user_id: UserId,
+    pub profile_id: ProfileId,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccuracyPoint {
+    /// Start of the local day in milliseconds since the Unix epoch.
+    pub day_start: i64,
+    pub answers: usize,
+    pub correct: usize,
+    /// Mean time taken by the timed correct answers of the day.
+    pub average_response_millis: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyReviewCount {
+    /// Start of the local day in milliseconds since the Unix epoch.
+    pub day_start: i64,
+    pub cards: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreBucket {
+    pub score: i32,
+    pub cards: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StudyStreak {
+    /// Consecutive days with answers ending today, or yesterday when nothing was answered yet today.
+    pub current_days: usize,
+    pub longest_days: usize,
+    pub studied_today: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HardCard {
+    pub card_id: CardId,
+    pub word: String,
+    pub direction: CardDirection,
+    pub score: i32,
+    pub answers: usize,
+    pub failures: usize,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum StudyStatisticsError {
+    #[error("statistics query is invalid")]
+    InvalidQuery,
+    #[error("language profile was not found")]
+    NotFound,
+    #[error("study statistics operation failed: {0}")]
+    Unexpected(String),
+}
@@ -1,12 +1,16 @@
 //! Outbound ports used by application use cases.
 
 pub mod ai_card_normalizer;
+pub mod clock;
 pub mod pronunciation_assessor;
+pub mod random_source;
 pub mod repository;
 pub mod speech_synthesizer;
 
 pub use ai_card_normalizer::AiCardNormalizer;
+pub use clock::Clock;
 pub use pronunciation_assessor::PronunciationAssessor;
+pub use random_source::RandomSource;
 pub use repository::{
     CardRepository, LanguageProfileRepository, PronunciationSettingsRepository,
     SpeechAudioRepository, StudySessionRepository, UserRepository,
@@ -0,0 +1,5 @@
+/// The source of the current time used by the study engine.
+pub trait Clock: Send + Sync {
+    /// Milliseconds since the Unix epoch.
+    fn now_millis(&self) -> i64;
+}
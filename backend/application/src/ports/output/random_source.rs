@@ -0,0 +1,4 @@
+/// The source of the seeds that order study sessions.
+pub trait RandomSource: Send + Sync {
+    fn next_seed(&self) -> u64;
+}
@@ -4,14 +4,18 @@ pub mod ai_settings;
 pub mod card;
 pub mod language_profile;
 pub mod pronunciation_settings;
+pub mod review_log;
 pub mod speech_audio;
 pub mod study_session;
+pub mod study_statistics;
 pub mod user;
 
 pub use ai_settings::AiSettingsRepository;
 pub use card::CardRepository;
 pub use language_profile::LanguageProfileRepository;
 pub use pronunciation_settings::PronunciationSettingsRepository;
+pub use review_log::ReviewLogRepository;
 pub use speech_audio::SpeechAudioRepository;
 pub use study_session::StudySessionRepository;
+pub use study_statistics::StudyStatisticsRepository;
 pub use user::UserRepository;
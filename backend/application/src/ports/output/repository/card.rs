@@ -2,7 +2,8 @@ use async_trait::async_trait;
 
 use crate::ports::input::{
     card_catalog::models::{
-        Card, CardId, CardPage, CardSelectionQuery, ListCardsQuery, PendingInverseCard,
+        Card, CardCandidate, CardId, CardPage, CardSchedule, CardSelectionQuery, CardStatus,
+        DueCardsQuery, ListCardsQuery, PendingInverseCard,
     },
     language_profile::models::ProfileId,
     local_user::models::UserId,
@@ -31,6 +32,15 @@ pub trait CardRepository: Send + Sync {
         card_ids: &[CardId],
     ) -> Result<usize, CardRepositoryError>;
 
+    /// Sets the status of the requested cards atomically and returns the number updated.
+    async fn set_status(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        card_ids: &[CardId],
+        status: CardStatus,
+    ) -> Result<usize, CardRepositoryError>;
+
     async fn find(
         &self,
         user_id: &UserId,
@@ -62,8 +72,46 @@ pub trait CardRepository: Send + Sync {
 
     async fn list_summaries(&self, query: ListCardsQuery) -> Result<CardPage, CardRepositoryError>;
 
+    /// Returns the matching cards as candidates in the query order; callers load the full
+    /// cards they pick with `find_many`.
     async fn select_for_session(
         &self,
         query: CardSelectionQuery,
+    ) -> Result<Vec<CardCandidate>, CardRepositoryError>;
+
+    /// Returns those of the requested cards that belong to the profile, in request order.
+    async fn find_many(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        card_ids: &[CardId],
     ) -> Result<Vec<Card>, CardRepositoryError>;
+
+    /// Returns those of the requested ids that still belong to the profile.
+    async fn existing_ids(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        card_ids: &[CardId],
+    ) -> Result<Vec<CardId>, CardRepositoryError>;
+
+    /// Returns the stored schedules of the requested cards; unreviewed cards are omitted.
+    async fn find_schedules(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        card_ids: &[CardId],
+    ) -> Result<Vec<CardSchedule>, CardRepositoryError>;
+
+    /// Returns overdue cards, most overdue first, followed by at most
+    /// `new_card_limit` never-reviewed cards, oldest first.
+    async fn select_due(&self, query: DueCardsQuery) -> Result<Vec<CardId>, CardRepositoryError>;
+
+    /// Counts cards whose first review happened at or after `since`.
+    async fn count_introduced_since(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        since: i64,
+    ) -> Result<usize, CardRepositoryError>;
 }
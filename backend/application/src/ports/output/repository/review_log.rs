@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+
+use self::models::{ReviewLogEntry, ReviewLogQuery, ReviewLogRepositoryError};
+
+pub mod models;
+
+/// Read access to the log of graded answers.
+///
+/// Entries are appended by [`StudySessionRepository::commit_transition`] so that the log
+/// always agrees with the committed session state, and are never deleted or rewritten. The one
+/// change an entry can see afterwards is its undo mark: undoing a transition stamps the undo
+/// time on its entries, and undone entries are left out of every read.
+///
+/// [`StudySessionRepository::commit_transition`]: super::StudySessionRepository::commit_transition
+#[async_trait]
+pub trait ReviewLogRepository: Send + Sync {
+    /// Returns matching entries of the profile, newest first.
+    async fn list(
+        &self,
+        query: ReviewLogQuery,
+    ) -> Result<Vec<ReviewLogEntry>, ReviewLogRepositoryError>;
+}
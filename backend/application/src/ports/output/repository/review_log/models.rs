@@ -0,0 +1,60 @@
+use thiserror::Error;
+
+use crate::ports::input::{
+    card_catalog::models::{CardDirection, CardId},
+    language_profile::models::ProfileId,
+    local_user::models::UserId,
+    study_session::models::{SessionId, StudySessionMode},
+};
+
+/// How a logged answer was graded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewOutcome {
+    Correct,
+    Incorrect,
+    /// The learner gave up on the card without answering.
+    GaveUp,
+    /// The card failed its pronunciation check before an answer was typed.
+    PronunciationFailed,
+    /// The learner accepted their rejected answer afterwards. The entry re-grades the answer
+    /// logged just before it for the card, which statistics then no longer count.
+    Accepted,
+}
+
+/// One graded answer of a study session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewLogEntry {
+    pub session_id: SessionId,
+    pub card_id: CardId,
+    pub mode: StudySessionMode,
+    pub direction: CardDirection,
+    /// `None` when the card was graded on pronunciation alone or given up.
+    pub submitted_answer: Option<String>,
+    pub matched_translation: Option<String>,
+    pub outcome: ReviewOutcome,
+    pub is_correct: bool,
+    pub score_delta: i32,
+    pub pronunciation_strict_score: Option<u8>,
+    pub response_time_millis: Option<u64>,
+    pub reviewed_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewLogQuery {
+    pub user_id: UserId,
+    pub profile_id: ProfileId,
+    pub card_id: Option<CardId>,
+    /// Only entries reviewed at or after this instant are returned when present.
+    pub since: Option<i64>,
+    pub limit: usize,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ReviewLogRepositoryError {
+    #[error("profile was not found")]
+    NotFound,
+    #[error("review log repository is unavailable")]
+    Unavailable,
+    #[error("review log repository failed: {0}")]
+    Unexpected(String),
+}
@@ -4,11 +4,14 @@ use crate::ports::input::{
     card_catalog::models::CardId,
     language_profile::models::ProfileId,
     local_user::models::UserId,
-    study_session::models::{SessionId, StudySession, StudySessionMode, StudySessionPreferences},
+    study_session::models::{
+        RecordedTransition, SessionId, StudySession, StudySessionMode, StudySessionPreferences,
+    },
 };
 
 use self::models::{
     EndSessionRequest, StoreSessionRequest, StudySessionCommit, StudySessionRepositoryError,
+    UndoTransitionRequest,
 };
 
 pub mod models;
@@ -26,6 +29,16 @@ pub trait StudySessionRepository: Send + Sync {
         session_id: &SessionId,
     ) -> Result<Option<StudySession>, StudySessionRepositoryError>;
 
+    /// Returns the active sessions of a profile, most recently updated first.
+    async fn list_active(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+    ) -> Result<Vec<StudySession>, StudySessionRepositoryError>;
+
+    /// Deletes active sessions that were last updated before `idle_before`.
+    async fn expire_idle(&self, idle_before: i64) -> Result<usize, StudySessionRepositoryError>;
+
     async fn find_preferences(
         &self,
         user_id: &UserId,
@@ -44,8 +57,25 @@ pub trait StudySessionRepository: Send + Sync {
         commit: StudySessionCommit,
     ) -> Result<StudySession, StudySessionRepositoryError>;
 
+    /// Reverts the latest undoable transition of an active session together with the card
+    /// scores, schedules and test selections it wrote, and marks its review log entries as
+    /// undone. Only the most recent transitions are kept; `None` means that none is left to
+    /// undo.
+    async fn undo_transition(
+        &self,
+        request: UndoTransitionRequest,
+    ) -> Result<Option<StudySession>, StudySessionRepositoryError>;
+
     async fn end(
         &self,
         request: EndSessionRequest,
     ) -> Result<StudySession, StudySessionRepositoryError>;
+
+    /// Returns the transitions recorded for a session, oldest first, starting with the one
+    /// that created it; replaying them rebuilds the session.
+    async fn events(
+        &self,
+        user_id: &UserId,
+        session_id: &SessionId,
+    ) -> Result<Vec<RecordedTransition>, StudySessionRepositoryError>;
 }
@@ -1,15 +1,32 @@
 use thiserror::Error;
 
 use crate::ports::input::{
-    card_catalog::models::CardId,
+    card_catalog::models::{CardId, CardSchedule, CardStatus, LeechState},
     local_user::models::UserId,
-    study_session::models::{SessionId, StudySession, StudySessionPreferences, StudySessionStatus},
+    study_session::models::{
+        SessionId, StudySession, StudySessionEvent, StudySessionPreferences, StudySessionStatus,
+    },
 };
+use crate::ports::output::repository::review_log::models::ReviewLogEntry;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CardProgressUpdate {
     pub card_id: CardId,
     pub score_delta: i32,
+    /// Replaces the stored spaced-repetition state of the card when present.
+    pub schedule: Option<CardSchedule>,
+    /// Replaces the stored leech state of the card when present.
+    pub leech: Option<LeechState>,
+    /// Replaces the stored status of the card when present.
+    pub status: Option<CardStatus>,
+}
+
+/// A rejected answer the learner kept as a translation of a card meaning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptedTranslation {
+    pub card_id: CardId,
+    pub meaning_index: usize,
+    pub translation: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -25,6 +42,21 @@ pub struct StudySessionCommit {
     pub expected_version: u64,
     pub card_progress: Vec<CardProgressUpdate>,
     pub selected_test_card: Option<CardId>,
+    /// Graded answers appended to the review log in the same transaction.
+    pub review_log: Vec<ReviewLogEntry>,
+    /// Added to the card in the same transaction and removed again when it is undone.
+    pub accepted_translation: Option<AcceptedTranslation>,
+    /// Keeps what the transition replaces so that it can be undone while the session is active.
+    pub undoable: bool,
+    /// Recorded under the new session version, so that the session can be replayed.
+    pub events: Vec<StudySessionEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoTransitionRequest {
+    pub user_id: UserId,
+    pub session_id: SessionId,
+    pub expected_version: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
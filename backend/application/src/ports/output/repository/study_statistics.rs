@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+
+use crate::ports::input::{
+    language_profile::models::ProfileId,
+    local_user::models::UserId,
+    study_statistics::models::{HardCard, ScoreBucket},
+};
+
+use self::models::{DailyAnswerCounts, StudyStatisticsRepositoryError};
+
+pub mod models;
+
+/// Aggregates over the review log and the card catalog of a profile.
+#[async_trait]
+pub trait StudyStatisticsRepository: Send + Sync {
+    /// Returns answer counts grouped by local day for days with answers at or after `since`.
+    async fn daily_answers(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        since: i64,
+        utc_offset_minutes: i32,
+    ) -> Result<Vec<DailyAnswerCounts>, StudyStatisticsRepositoryError>;
+
+    async fn score_distribution(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+    ) -> Result<Vec<ScoreBucket>, StudyStatisticsRepositoryError>;
+
+    async fn hardest_cards(
+        &self,
+        user_id: &UserId,
+        profile_id: &ProfileId,
+        limit: usize,
+    ) -> Result<Vec<HardCard>, StudyStatisticsRepositoryError>;
+}
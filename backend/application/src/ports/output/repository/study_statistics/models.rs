@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyAnswerCounts {
+    /// Whole local days since the Unix epoch.
+    pub day: i64,
+    pub answers: usize,
+    pub correct: usize,
+    pub distinct_cards: usize,
+    /// Mean response time of the timed correct answers; `None` when there were none.
+    pub average_response_millis: Option<u64>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum StudyStatisticsRepositoryError {
+    #[error("profile was not found")]
+    NotFound,
+    #[error("study statistics repository is unavailable")]
+    Unavailable,
+    #[error("study statistics repository failed: {0}")]
+    Unexpected(String),
+}
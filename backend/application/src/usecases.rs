@@ -1,14 +1,21 @@
 //! Use-case implementations.
 
 pub mod ai_settings;
+pub mod answer_normalization;
 pub mod card_catalog;
 pub mod card_normalization;
+pub mod card_scheduling;
 pub mod card_speech;
+pub mod cloze;
 pub mod language_profile;
 pub mod local_user;
 pub mod pronunciation_scoring;
 pub mod pronunciation_settings;
+pub mod reading_comparison;
 pub mod study_session;
+pub mod study_session_reducer;
+pub mod study_statistics;
+pub mod test_card_selection;
 
 pub use ai_settings::AiSettingsService;
 pub use card_catalog::CardCatalogService;
@@ -18,3 +25,4 @@ pub use language_profile::LanguageProfileService;
 pub use local_user::LocalUserService;
 pub use pronunciation_settings::PronunciationSettingsService;
 pub use study_session::StudySessionService;
+pub use study_statistics::StudyStatisticsService;
@@ -0,0 +1,155 @@
+use crate::ports::input::study_session::models::AnswerNormalization;
+
+const ENGLISH_LEADING_WORDS: &[&str] = &["to ", "a ", "an ", "the "];
+/// Half-width katakana U+FF66..=U+FF9D in code point order.
+const HALF_WIDTH_KATAKANA: &str = "ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン";
+const VOICEABLE_KATAKANA: &str = "カキクケコサシスセソタチツテトハヒフヘホ";
+const SEMI_VOICEABLE_KATAKANA: &str = "ハヒフヘホ";
+
+/// The normalizations applied, in order, to answers written in `language`.
+pub fn answer_normalizations(language: &str) -> &'static [AnswerNormalization] {
+    match language {
+        "ru-RU" => &[
+            AnswerNormalization::FoldYo,
+            AnswerNormalization::StripStressMarks,
+        ],
+        "ja-JP" => &[
+            AnswerNormalization::FoldWidth,
+            AnswerNormalization::FoldKana,
+        ],
+        _ => &[
+            AnswerNormalization::IgnoreOptionalParts,
+            AnswerNormalization::IgnoreLeadingArticle,
+        ],
+    }
+}
+
+pub fn normalize(normalization: AnswerNormalization, text: &str) -> String {
+    match normalization {
+        AnswerNormalization::FoldYo => text.replace('ё', "е").replace('Ё', "Е"),
+        AnswerNormalization::StripStressMarks => text
+            .chars()
+            .filter(|character| !matches!(character, '\u{300}' | '\u{301}'))
+            .collect(),
+        AnswerNormalization::FoldWidth => fold_width(text),
+        AnswerNormalization::FoldKana => fold_kana(text),
+        AnswerNormalization::IgnoreOptionalParts => strip_parenthesized(text),
+        AnswerNormalization::IgnoreLeadingArticle => {
+            let trimmed = text.trim_start();
+            ENGLISH_LEADING_WORDS
+                .iter()
+                .find_map(|word| {
+                    trimmed
+                        .get(..word.len())
+                        .filter(|prefix| prefix.eq_ignore_ascii_case(word))
+                        .map(|_| trimmed[word.len()..].trim_start().to_string())
+                })
+                .unwrap_or_else(|| text.to_string())
+        }
+    }
+}
+
+/// Writes katakana as hiragana.
+pub fn fold_kana(text: &str) -> String {
+    text.chars()
+        .map(|character| {
+            if ('\u{30a1}'..='\u{30f6}').contains(&character) {
+                char::from_u32(character as u32 - 0x60).unwrap_or(character)
+            } else {
+                character
+            }
+        })
+        .collect()
+}
+
+/// Writes full-width ASCII as ASCII and half-width katakana as full-width katakana, merging
+/// half-width voicing marks into the preceding kana.
+pub fn fold_width(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for character in text.chars() {
+        let code = character as u32;
+        match character {
+            '\u{ff01}'..='\u{ff5e}' => folded.extend(char::from_u32(code - 0xfee0)),
+            '\u{3000}' => folded.push(' '),
+            '\u{ff66}'..='\u{ff9d}' => {
+                folded.extend(HALF_WIDTH_KATAKANA.chars().nth((code - 0xff66) as usize))
+            }
+            '\u{ff9e}' | '\u{ff9f}' => {
+                let voiced = folded.pop().and_then(|previous| {
+                    let offset = match character {
+                        '\u{ff9e}' if previous == 'ウ' => return Some('ヴ'),
+                        '\u{ff9e}' if VOICEABLE_KATAKANA.contains(previous) => 1,
+                        '\u{ff9f}' if SEMI_VOICEABLE_KATAKANA.contains(previous) => 2,
+                        _ => {
+                            folded.push(previous);
+                            return None;
+                        }
+                    };
+                    char::from_u32(previous as u32 + offset)
+                });
+                folded.push(voiced.unwrap_or(if character == '\u{ff9e}' {
+                    '゛'
+                } else {
+                    '゜'
+                }));
+            }
+            _ => folded.push(character),
+        }
+    }
+    folded
+}
+
+fn strip_parenthesized(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut depth = 0usize;
+    for character in text.chars() {
+        match character {
+            '(' => depth += 1,
+            ')' if depth > 0 => depth -= 1,
+            _ if depth == 0 => stripped.push(character),
+            _ => {}
+        }
+    }
+    stripped.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn russian_answers_fold_yo_and_drop_stress_marks() {
+        assert_eq!(
+            normalize(AnswerNormalization::FoldYo, "Ёжик ёлки"),
+            "Ежик елки"
+        );
+        assert_eq!(
+            normalize(AnswerNormalization::StripStressMarks, "молоко\u{301}"),
+            "молоко"
+        );
+    }
+
+    #[test]
+    fn japanese_answers_fold_width_and_kana() {
+        assert_eq!(fold_width("ＡＢＣ　１２３"), "ABC 123");
+        assert_eq!(fold_width("ｶﾞｯｺｳ"), "ガッコウ");
+        assert_eq!(fold_width("ﾊﾟﾝ ｳﾞｧ ｱﾞ"), "パン ヴァ ア゛");
+        assert_eq!(fold_kana("ガッコウ"), "がっこう");
+        assert_eq!(fold_kana("コーヒー"), "こーひー");
+    }
+
+    #[test]
+    fn english_answers_ignore_leading_words_and_optional_parts() {
+        let article = |text| normalize(AnswerNormalization::IgnoreLeadingArticle, text);
+        let optional = |text| normalize(AnswerNormalization::IgnoreOptionalParts, text);
+
+        assert_eq!(article("to run"), "run");
+        assert_eq!(article("The  cat"), "cat");
+        assert_eq!(article("an apple"), "apple");
+        assert_eq!(article("anchor"), "anchor");
+        assert_eq!(article("toast"), "toast");
+        assert_eq!(optional("(to) run"), "run");
+        assert_eq!(optional("run (fast) away"), "run away");
+        assert_eq!(optional("(only optional)"), "");
+    }
+}
@@ -1,7 +1,4 @@
-use std::{
-    sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
-};
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use uuid::Uuid;
@@ -10,13 +7,16 @@ use crate::ports::{
     input::card_catalog::{
         CardCatalogUsecase,
         models::{
-            Card, CardCatalogError, CardChanges, CardPage, CreateCardsCommand, DeleteCardsCommand,
-            DeleteCardsResult, GetCardQuery, Meaning, NewCard, PendingInverseCard,
-            PrepareInverseCardsQuery, SaveInverseCardsCommand, UpdateCardCommand, UsageExample,
-            Word,
+            Card, CardCatalogError, CardChanges, CardPage, CardStatus, CreateCardsCommand,
+            DeleteCardsCommand, DeleteCardsResult, GetCardQuery, LeechState, Meaning, NewCard,
+            PendingInverseCard, PrepareInverseCardsQuery, SaveInverseCardsCommand,
+            SetCardStatusCommand, SetCardStatusResult, UpdateCardCommand, UsageExample, Word,
         },
     },
-    output::repository::card::{CardRepository, models::CardRepositoryError},
+    output::{
+        Clock,
+        repository::card::{CardRepository, models::CardRepositoryError},
+    },
 };
 
 const MAX_WORD_LENGTH: usize = 200;
@@ -26,11 +26,12 @@ const MAX_EXAMPLES: usize = 5;
 
 pub struct CardCatalogService {
     repository: Arc<dyn CardRepository>,
+    clock: Arc<dyn Clock>,
 }
 
 impl CardCatalogService {
-    pub fn new(repository: Arc<dyn CardRepository>) -> Self {
-        Self { repository }
+    pub fn new(repository: Arc<dyn CardRepository>, clock: Arc<dyn Clock>) -> Self {
+        Self { repository, clock }
     }
 
     fn map_repository_error(error: CardRepositoryError) -> CardCatalogError {
@@ -125,6 +126,8 @@ impl CardCatalogService {
             word,
             meanings,
             score: 0,
+            leech: LeechState::default(),
+            status: CardStatus::Active,
             created_at,
             version: 0,
         })
@@ -136,6 +139,9 @@ impl CardCatalogService {
         let (word, meanings) = Self::normalize_parts(word, meanings)?;
         card.word = word;
         card.meanings = meanings;
+        if changes.clear_leech {
+            card.leech = LeechState::default();
+        }
         Ok(card)
     }
 
@@ -169,10 +175,7 @@ impl CardCatalogUsecase for CardCatalogService {
         if command.cards.is_empty() {
             return Err(CardCatalogError::InvalidCard);
         }
-        let created_at = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|error| CardCatalogError::Unexpected(error.to_string()))?
-            .as_millis() as i64;
+        let created_at = self.clock.now_millis();
         let cards = command
             .cards
             .into_iter()
@@ -199,6 +202,30 @@ impl CardCatalogUsecase for CardCatalogService {
             .map_err(Self::map_repository_error)
     }
 
+    async fn set_card_status(
+        &self,
+        command: SetCardStatusCommand,
+    ) -> Result<SetCardStatusResult, CardCatalogError> {
+        if command.card_ids.is_empty() {
+            return Err(CardCatalogError::InvalidCard);
+        }
+        if let CardStatus::Buried { until } = command.status
+            && until <= self.clock.now_millis()
+        {
+            return Err(CardCatalogError::InvalidCard);
+        }
+        self.repository
+            .set_status(
+                &command.user_id,
+                &command.profile_id,
+                &command.card_ids,
+                command.status,
+            )
+            .await
+            .map(|updated_count| SetCardStatusResult { updated_count })
+            .map_err(Self::map_repository_error)
+    }
+
     async fn list_cards(
         &self,
         query: crate::ports::input::card_catalog::models::ListCardsQuery,
@@ -284,10 +311,7 @@ impl CardCatalogUsecase for CardCatalogService {
             }
         }
 
-        let created_at = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|error| CardCatalogError::Unexpected(error.to_string()))?
-            .as_millis() as i64;
+        let created_at = self.clock.now_millis();
         let mut pending = Vec::with_capacity(grouped.len());
         for (word, inverse_direction, meanings) in grouped {
             if let Some(mut existing) = self
@@ -364,8 +388,9 @@ mod tests {
     use crate::ports::{
         input::{
             card_catalog::models::{
-                CardDirection, CardId, CardPage, CardSelectionQuery, CreateCardsCommand,
-                GetCardQuery, ListCardsQuery, Meaning, NewCard, UpdateCardCommand, Word,
+                CardCandidate, CardDirection, CardId, CardPage, CardSchedule, CardSelectionQuery,
+                CreateCardsCommand, DueCardsQuery, GetCardQuery, ListCardsQuery, Meaning, NewCard,
+                UpdateCardCommand, Word,
             },
             language_profile::models::ProfileId,
             local_user::models::UserId,
@@ -375,6 +400,16 @@ mod tests {
 
     use super::*;
 
+    const NOW: i64 = 1_700_000_000_000;
+
+    struct FixedClock;
+
+    impl Clock for FixedClock {
+        fn now_millis(&self) -> i64 {
+            NOW
+        }
+    }
+
     #[derive(Default)]
     struct InMemoryRepository {
         cards: Mutex<HashMap<CardId, Card>>,
@@ -419,6 +454,27 @@ mod tests {
                 .count())
         }
 
+        async fn set_status(
+            &self,
+            _user_id: &UserId,
+            profile_id: &ProfileId,
+            card_ids: &[CardId],
+            status: CardStatus,
+        ) -> Result<usize, CardRepositoryError> {
+            let mut cards = self.cards.lock().unwrap();
+            let mut updated = 0;
+            for card_id in card_ids {
+                let card = cards
+                    .get_mut(card_id)
+                    .filter(|card| &card.profile_id == profile_id)
+                    .ok_or(CardRepositoryError::NotFound)?;
+                card.status = status;
+                card.version += 1;
+                updated += 1;
+            }
+            Ok(updated)
+        }
+
         async fn find(
             &self,
             _user_id: &UserId,
@@ -510,9 +566,58 @@ mod tests {
         async fn select_for_session(
             &self,
             _query: CardSelectionQuery,
+        ) -> Result<Vec<CardCandidate>, CardRepositoryError> {
+            Ok(vec![])
+        }
+
+        async fn find_many(
+            &self,
+            _user_id: &UserId,
+            profile_id: &ProfileId,
+            card_ids: &[CardId],
         ) -> Result<Vec<Card>, CardRepositoryError> {
+            let cards = self.cards.lock().unwrap();
+            Ok(card_ids
+                .iter()
+                .filter_map(|card_id| cards.get(card_id))
+                .filter(|card| &card.profile_id == profile_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn existing_ids(
+            &self,
+            _user_id: &UserId,
+            _profile_id: &ProfileId,
+            card_ids: &[CardId],
+        ) -> Result<Vec<CardId>, CardRepositoryError> {
+            Ok(card_ids.to_vec())
+        }
+
+        async fn find_schedules(
+            &self,
+            _user_id: &UserId,
+            _profile_id: &ProfileId,
+            _card_ids: &[CardId],
+        ) -> Result<Vec<CardSchedule>, CardRepositoryError> {
+            Ok(vec![])
+        }
+
+        async fn select_due(
+            &self,
+            _query: DueCardsQuery,
+        ) -> Result<Vec<CardId>, CardRepositoryError> {
             Ok(vec![])
         }
+
+        async fn count_introduced_since(
+            &self,
+            _user_id: &UserId,
+            _profile_id: &ProfileId,
+            _since: i64,
+        ) -> Result<usize, CardRepositoryError> {
+            Ok(0)
+        }
     }
 
     fn new_card(word: &str) -> NewCard {
@@ -541,7 +646,10 @@ mod tests {
 
     #[tokio::test]
     async fn creates_and_normalizes_nested_card_data() {
-        let service = CardCatalogService::new(Arc::new(InMemoryRepository::default()));
+        let service = CardCatalogService::new(
+            Arc::new(InMemoryRepository::default()),
+            Arc::new(FixedClock),
+        );
         let card = service
             .create_cards(create_command(" word "))
             .await
@@ -558,7 +666,10 @@ mod tests {
 
     #[tokio::test]
     async fn rejects_invalid_and_duplicate_cards() {
-        let service = CardCatalogService::new(Arc::new(InMemoryRepository::default()));
+        let service = CardCatalogService::new(
+            Arc::new(InMemoryRepository::default()),
+            Arc::new(FixedClock),
+        );
         assert_eq!(
             service.create_cards(create_command("")).await,
             Err(CardCatalogError::InvalidCard)
@@ -573,7 +684,7 @@ mod tests {
     #[tokio::test]
     async fn rejects_duplicate_words_inside_a_batch_without_saving_anything() {
         let repository = Arc::new(InMemoryRepository::default());
-        let service = CardCatalogService::new(repository.clone());
+        let service = CardCatalogService::new(repository.clone(), Arc::new(FixedClock));
         let mut reverse = new_card("same");
         reverse.direction = CardDirection::Reverse;
         let result = service
@@ -589,7 +700,10 @@ mod tests {
 
     #[tokio::test]
     async fn preserves_direction_and_detects_stale_updates() {
-        let service = CardCatalogService::new(Arc::new(InMemoryRepository::default()));
+        let service = CardCatalogService::new(
+            Arc::new(InMemoryRepository::default()),
+            Arc::new(FixedClock),
+        );
         let card = service
             .create_cards(create_command("word"))
             .await
@@ -607,6 +721,7 @@ mod tests {
                         readings: vec![],
                     }),
                     meanings: None,
+                    clear_leech: false,
                 },
             })
             .await
@@ -641,9 +756,113 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn clearing_a_leech_resets_its_failure_count() {
+        let repository = Arc::new(InMemoryRepository::default());
+        let service = CardCatalogService::new(repository.clone(), Arc::new(FixedClock));
+        let card = service
+            .create_cards(create_command("word"))
+            .await
+            .unwrap()
+            .remove(0);
+        repository
+            .cards
+            .lock()
+            .unwrap()
+            .get_mut(&card.id)
+            .unwrap()
+            .leech = LeechState {
+            consecutive_failures: 8,
+            is_leech: true,
+        };
+
+        let updated = service
+            .update_card(UpdateCardCommand {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new("profile"),
+                card_id: card.id,
+                expected_version: 0,
+                changes: CardChanges {
+                    clear_leech: true,
+                    ..CardChanges::default()
+                },
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(updated.leech, LeechState::default());
+        assert_eq!(updated.word.text, "word");
+    }
+
+    #[tokio::test]
+    async fn suspends_and_buries_cards_in_bulk() {
+        let service = CardCatalogService::new(
+            Arc::new(InMemoryRepository::default()),
+            Arc::new(FixedClock),
+        );
+        let mut command = create_command("first");
+        command.cards.extend(create_command("second").cards);
+        let cards = service.create_cards(command).await.unwrap();
+        let card_ids = cards.iter().map(|card| card.id.clone()).collect::<Vec<_>>();
+        let set_status = |card_ids: Vec<CardId>, status| SetCardStatusCommand {
+            user_id: UserId::new("alice"),
+            profile_id: ProfileId::new("profile"),
+            card_ids,
+            status,
+        };
+
+        assert_eq!(
+            service
+                .set_card_status(set_status(card_ids.clone(), CardStatus::Suspended))
+                .await
+                .unwrap()
+                .updated_count,
+            2
+        );
+        let suspended = service
+            .get_card(GetCardQuery {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new("profile"),
+                card_id: card_ids[0].clone(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(suspended.status, CardStatus::Suspended);
+        assert_eq!(suspended.version, 1);
+
+        assert_eq!(
+            service
+                .set_card_status(set_status(Vec::new(), CardStatus::Active))
+                .await,
+            Err(CardCatalogError::InvalidCard)
+        );
+        assert_eq!(
+            service
+                .set_card_status(set_status(
+                    card_ids.clone(),
+                    CardStatus::Buried { until: 1_000 }
+                ))
+                .await,
+            Err(CardCatalogError::InvalidCard),
+            "burying into the past is rejected"
+        );
+        service
+            .set_card_status(set_status(
+                card_ids,
+                CardStatus::Buried {
+                    until: NOW + 86_400_000,
+                },
+            ))
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn prepares_and_saves_new_and_merged_inverse_cards() {
-        let service = CardCatalogService::new(Arc::new(InMemoryRepository::default()));
+        let service = CardCatalogService::new(
+            Arc::new(InMemoryRepository::default()),
+            Arc::new(FixedClock),
+        );
         let source = service
             .create_cards(CreateCardsCommand {
                 user_id: UserId::new("alice"),
@@ -0,0 +1,235 @@
+use crate::ports::input::{
+    card_catalog::models::{CardId, CardSchedule, CardStatus, LeechState, ReviewGrade},
+    language_profile::models::LeechAction,
+};
+
+pub const INITIAL_EASE_PERMILLE: u32 = 2_500;
+pub const MIN_EASE_PERMILLE: u32 = 1_300;
+pub const DAY_MILLIS: i64 = 86_400_000;
+/// The widest offset from UTC a learner's clock can have.
+pub const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+const RELEARN_DELAY_MILLIS: i64 = 10 * 60 * 1_000;
+const HARD_INTERVAL_PERMILLE: u64 = 1_200;
+const EASY_BONUS_PERMILLE: u64 = 1_300;
+
+/// Applies one graded review to the SM-2 memory state of a card.
+///
+/// `previous` is `None` for a card that has never been reviewed.
+pub fn review_card(
+    previous: Option<&CardSchedule>,
+    card_id: &CardId,
+    grade: ReviewGrade,
+    reviewed_at: i64,
+) -> CardSchedule {
+    let mut schedule = previous.cloned().unwrap_or_else(|| CardSchedule {
+        card_id: card_id.clone(),
+        ease_permille: INITIAL_EASE_PERMILLE,
+        interval_days: 0,
+        repetitions: 0,
+        lapses: 0,
+        due_at: reviewed_at,
+        last_reviewed_at: reviewed_at,
+        introduced_at: reviewed_at,
+    });
+    schedule.last_reviewed_at = reviewed_at;
+
+    if grade == ReviewGrade::Again {
+        if schedule.repetitions > 0 {
+            schedule.lapses += 1;
+        }
+        schedule.repetitions = 0;
+        schedule.interval_days = 0;
+        schedule.ease_permille = adjust_ease(schedule.ease_permille, -200);
+        schedule.due_at = reviewed_at + RELEARN_DELAY_MILLIS;
+        return schedule;
+    }
+
+    let interval = u64::from(schedule.interval_days);
+    schedule.interval_days = match (grade, schedule.repetitions) {
+        (ReviewGrade::Hard, 0) | (ReviewGrade::Good, 0) => 1,
+        (ReviewGrade::Easy, 0) => 4,
+        (ReviewGrade::Hard, _) => scale(interval, HARD_INTERVAL_PERMILLE),
+        (ReviewGrade::Good, 1) => 6,
+        (ReviewGrade::Good, _) => scale(interval, u64::from(schedule.ease_permille)),
+        (ReviewGrade::Easy, 1) => scale(6, EASY_BONUS_PERMILLE),
+        (ReviewGrade::Easy, _) => scale(
+            u64::from(scale(interval, u64::from(schedule.ease_permille))),
+            EASY_BONUS_PERMILLE,
+        ),
+        (ReviewGrade::Again, _) => unreachable!(),
+    };
+    schedule.ease_permille = match grade {
+        ReviewGrade::Hard => adjust_ease(schedule.ease_permille, -150),
+        ReviewGrade::Easy => adjust_ease(schedule.ease_permille, 150),
+        _ => schedule.ease_permille,
+    };
+    schedule.repetitions += 1;
+    schedule.due_at = reviewed_at + i64::from(schedule.interval_days) * DAY_MILLIS;
+    schedule
+}
+
+/// Counts a graded answer towards leech detection.
+///
+/// A correct answer resets the failure streak but keeps a card flagged as a leech until the
+/// learner clears it. A `threshold` of `0` never flags a card. Returns the new leech state and,
+/// when the answer turns the card into a leech that `action` suspends, its new status.
+pub fn track_failures(
+    previous: LeechState,
+    is_correct: bool,
+    threshold: u32,
+    action: LeechAction,
+) -> (LeechState, Option<CardStatus>) {
+    if is_correct {
+        let leech = LeechState {
+            consecutive_failures: 0,
+            ..previous
+        };
+        return (leech, None);
+    }
+    let consecutive_failures = previous.consecutive_failures.saturating_add(1);
+    let became_leech = !previous.is_leech && threshold > 0 && consecutive_failures >= threshold;
+    let leech = LeechState {
+        consecutive_failures,
+        is_leech: previous.is_leech || became_leech,
+    };
+    let status = (became_leech && action == LeechAction::Suspend).then_some(CardStatus::Suspended);
+    (leech, status)
+}
+
+/// The local day, counted from the epoch, that an instant falls on for a learner whose clock is
+/// `utc_offset_minutes` ahead of UTC.
+pub fn local_day(at: i64, utc_offset_minutes: i32) -> i64 {
+    (at + offset_millis(utc_offset_minutes)).div_euclid(DAY_MILLIS)
+}
+
+/// The instant a local day starts, at the learner's midnight.
+pub fn local_day_start(day: i64, utc_offset_minutes: i32) -> i64 {
+    day * DAY_MILLIS - offset_millis(utc_offset_minutes)
+}
+
+fn offset_millis(utc_offset_minutes: i32) -> i64 {
+    i64::from(utc_offset_minutes) * 60_000
+}
+
+fn adjust_ease(ease_permille: u32, change: i32) -> u32 {
+    ease_permille
+        .saturating_add_signed(change)
+        .max(MIN_EASE_PERMILLE)
+}
+
+fn scale(interval_days: u64, factor_permille: u64) -> u32 {
+    (interval_days * factor_permille)
+        .div_ceil(1_000)
+        .max(interval_days + 1)
+        .min(u64::from(u32::MAX)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card_id() -> CardId {
+        CardId::new("card")
+    }
+
+    #[test]
+    fn days_start_at_local_midnight() {
+        let three_hours = 3 * 60 * 60 * 1_000;
+        assert_eq!(local_day_start(1, 0), DAY_MILLIS);
+        assert_eq!(local_day_start(1, 180), DAY_MILLIS - three_hours);
+        assert_eq!(local_day(DAY_MILLIS - three_hours, 180), 1);
+        assert_eq!(local_day(DAY_MILLIS - three_hours - 1, 180), 0);
+        assert_eq!(local_day(DAY_MILLIS, -180), 0);
+    }
+
+    #[test]
+    fn new_cards_follow_the_classic_one_then_six_day_steps() {
+        let first = review_card(None, &card_id(), ReviewGrade::Good, 0);
+        assert_eq!(first.interval_days, 1);
+        assert_eq!(first.due_at, DAY_MILLIS);
+        assert_eq!(first.introduced_at, 0);
+
+        let second = review_card(Some(&first), &card_id(), ReviewGrade::Good, DAY_MILLIS);
+        assert_eq!(second.interval_days, 6);
+        assert_eq!(second.repetitions, 2);
+
+        let third = review_card(Some(&second), &card_id(), ReviewGrade::Good, 7 * DAY_MILLIS);
+        assert_eq!(third.interval_days, 15);
+        assert_eq!(third.ease_permille, INITIAL_EASE_PERMILLE);
+        assert_eq!(third.introduced_at, 0);
+    }
+
+    #[test]
+    fn failures_count_lapses_and_come_back_within_the_session() {
+        let learned = review_card(None, &card_id(), ReviewGrade::Good, 0);
+        let failed = review_card(Some(&learned), &card_id(), ReviewGrade::Again, DAY_MILLIS);
+
+        assert_eq!(failed.lapses, 1);
+        assert_eq!(failed.repetitions, 0);
+        assert_eq!(failed.interval_days, 0);
+        assert_eq!(failed.due_at, DAY_MILLIS + RELEARN_DELAY_MILLIS);
+        assert_eq!(failed.ease_permille, INITIAL_EASE_PERMILLE - 200);
+
+        let never_learned = review_card(None, &card_id(), ReviewGrade::Again, 0);
+        assert_eq!(never_learned.lapses, 0);
+    }
+
+    #[test]
+    fn ease_is_bounded_and_intervals_always_grow() {
+        let mut schedule = review_card(None, &card_id(), ReviewGrade::Good, 0);
+        for _ in 0..20 {
+            schedule = review_card(Some(&schedule), &card_id(), ReviewGrade::Again, 0);
+        }
+        assert_eq!(schedule.ease_permille, MIN_EASE_PERMILLE);
+
+        let mut previous_interval = 0;
+        for _ in 0..6 {
+            schedule = review_card(Some(&schedule), &card_id(), ReviewGrade::Hard, 0);
+            assert!(schedule.interval_days > previous_interval);
+            previous_interval = schedule.interval_days;
+        }
+        let easy = review_card(Some(&schedule), &card_id(), ReviewGrade::Easy, 0);
+        assert!(easy.interval_days > schedule.interval_days);
+        assert_eq!(easy.ease_permille, MIN_EASE_PERMILLE + 150);
+    }
+
+    #[test]
+    fn repeated_failures_flag_a_leech_and_suspend_it_when_asked() {
+        let mut state = LeechState::default();
+        for _ in 0..2 {
+            let (next, status) = track_failures(state, false, 3, LeechAction::Suspend);
+            assert_eq!(status, None);
+            state = next;
+        }
+        assert_eq!(state.consecutive_failures, 2);
+        assert!(!state.is_leech);
+
+        let (leech, status) = track_failures(state, false, 3, LeechAction::Suspend);
+        assert!(leech.is_leech);
+        assert_eq!(status, Some(CardStatus::Suspended));
+        assert_eq!(
+            track_failures(state, false, 3, LeechAction::Tag),
+            (leech, None)
+        );
+        assert_eq!(
+            track_failures(leech, false, 3, LeechAction::Suspend).1,
+            None,
+            "an existing leech is suspended only once"
+        );
+
+        let (recovered, status) = track_failures(leech, true, 3, LeechAction::Suspend);
+        assert_eq!(recovered.consecutive_failures, 0);
+        assert!(recovered.is_leech);
+        assert_eq!(status, None);
+    }
+
+    #[test]
+    fn a_zero_threshold_turns_leech_detection_off() {
+        let mut state = LeechState::default();
+        for _ in 0..50 {
+            state = track_failures(state, false, 0, LeechAction::Suspend).0;
+        }
+        assert_eq!(state.consecutive_failures, 50);
+        assert!(!state.is_leech);
+    }
+}
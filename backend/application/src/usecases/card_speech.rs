@@ -251,10 +251,10 @@ mod tests {
         input::{
             ai_settings::models::AiSettings,
             card_catalog::models::{
-                CardId, CardPage, CardSelectionQuery, ListCardsQuery, Meaning, PendingInverseCard,
-                Word,
+                CardCandidate, CardId, CardPage, CardSchedule, CardSelectionQuery, CardStatus,
+                DueCardsQuery, LeechState, ListCardsQuery, Meaning, PendingInverseCard, Word,
             },
-            language_profile::models::{LanguageProfile, ProfileId},
+            language_profile::models::{LanguageProfile, LeechAction, ProfileId},
             local_user::models::UserId,
         },
         output::{
@@ -283,6 +283,8 @@ mod tests {
                 examples: vec![],
             }],
             score: 0,
+            leech: LeechState::default(),
+            status: CardStatus::Active,
             created_at: 0,
             version: 0,
         }
@@ -373,6 +375,16 @@ mod tests {
             unimplemented!()
         }
 
+        async fn set_status(
+            &self,
+            _user_id: &UserId,
+            _profile_id: &ProfileId,
+            _card_ids: &[CardId],
+            _status: CardStatus,
+        ) -> Result<usize, CardRepositoryError> {
+            unimplemented!()
+        }
+
         async fn find(
             &self,
             _user_id: &UserId,
@@ -419,9 +431,52 @@ mod tests {
         async fn select_for_session(
             &self,
             _query: CardSelectionQuery,
+        ) -> Result<Vec<CardCandidate>, CardRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_many(
+            &self,
+            _user_id: &UserId,
+            _profile_id: &ProfileId,
+            _card_ids: &[CardId],
         ) -> Result<Vec<Card>, CardRepositoryError> {
             unimplemented!()
         }
+
+        async fn existing_ids(
+            &self,
+            _user_id: &UserId,
+            _profile_id: &ProfileId,
+            _card_ids: &[CardId],
+        ) -> Result<Vec<CardId>, CardRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_schedules(
+            &self,
+            _user_id: &UserId,
+            _profile_id: &ProfileId,
+            _card_ids: &[CardId],
+        ) -> Result<Vec<CardSchedule>, CardRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn select_due(
+            &self,
+            _query: DueCardsQuery,
+        ) -> Result<Vec<CardId>, CardRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn count_introduced_since(
+            &self,
+            _user_id: &UserId,
+            _profile_id: &ProfileId,
+            _since: i64,
+        ) -> Result<usize, CardRepositoryError> {
+            unimplemented!()
+        }
     }
 
     #[derive(Default)]
@@ -506,6 +561,8 @@ mod tests {
                 name: "Japanese".to_string(),
                 source_language: "en-US".to_string(),
                 target_language: "ja-JP".to_string(),
+                leech_threshold: 8,
+                leech_action: LeechAction::Suspend,
                 version: 0,
             })),
             Arc::new(FakeAiSettings(AiSettings {
@@ -0,0 +1,245 @@
+use std::ops::Range;
+
+use crate::ports::input::{card_catalog::models::Card, study_session::models::ClozePrompt};
+
+/// Spaced words are only shortened while this many characters of the stem remain.
+const MIN_STEM_CHARS: usize = 3;
+/// How many trailing characters of a spaced word may be dropped to find an inflected form.
+const MAX_TRIMMED_CHARS: usize = 3;
+/// How much longer than the dropped characters an inflected ending may be.
+const MAX_EXTRA_ENDING_CHARS: usize = 3;
+
+/// A usage example of a card that can be drilled with the word blanked out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClozeExample {
+    pub prompt: ClozePrompt,
+    /// The form of the word as it appears in the sentence.
+    pub answer: String,
+}
+
+/// Collects every usage example of the card that contains its word or one of its readings.
+/// Examples where neither can be located are skipped.
+pub fn cloze_examples(card: &Card) -> Vec<ClozeExample> {
+    card.meanings
+        .iter()
+        .flat_map(|meaning| &meaning.examples)
+        .filter_map(|example| {
+            let range = std::iter::once(&card.word.text)
+                .chain(&card.word.readings)
+                .find_map(|word| locate_word(&example.sentence, word))?;
+            Some(ClozeExample {
+                prompt: ClozePrompt {
+                    sentence_before: example.sentence[..range.start].to_string(),
+                    sentence_after: example.sentence[range.end..].to_string(),
+                    translation: example.translation.clone(),
+                },
+                answer: example.sentence[range].to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Finds the byte range of `word` or one of its inflected forms in `sentence`.
+///
+/// Words written with spaces must start at a word boundary; their last few characters may be
+/// replaced by a different ending, which covers most Russian declension and conjugation.
+/// Japanese and Chinese words are matched anywhere in the sentence, and a Japanese word may
+/// swap its trailing kana (okurigana) for a conjugated ending.
+pub fn locate_word(sentence: &str, word: &str) -> Option<Range<usize>> {
+    let word = word.trim().chars().collect::<Vec<_>>();
+    if word.is_empty() {
+        return None;
+    }
+    let sentence = sentence.char_indices().collect::<Vec<_>>();
+    let spaced = !word.iter().copied().any(is_unspaced_script);
+    let max_trimmed = if word.iter().any(|character| character.is_whitespace()) {
+        0
+    } else if spaced {
+        word.len()
+            .saturating_sub(MIN_STEM_CHARS)
+            .min(MAX_TRIMMED_CHARS)
+    } else {
+        let okurigana = word
+            .iter()
+            .rev()
+            .take_while(|character| is_hiragana(**character))
+            .count();
+        if okurigana == word.len() {
+            0
+        } else {
+            okurigana
+        }
+    };
+
+    (0..=max_trimmed).find_map(|trimmed| {
+        let stem = &word[..word.len() - trimmed];
+        (0..sentence.len()).find_map(|start| {
+            if spaced && start > 0 && sentence[start - 1].1.is_alphanumeric() {
+                return None;
+            }
+            let stem_end = start + stem.len();
+            let matches = sentence.get(start..stem_end).is_some_and(|candidate| {
+                candidate
+                    .iter()
+                    .zip(stem)
+                    .all(|((_, left), right)| left.to_lowercase().eq(right.to_lowercase()))
+            });
+            if !matches {
+                return None;
+            }
+            let continues = |character: char| {
+                if spaced {
+                    character.is_alphanumeric()
+                } else {
+                    trimmed > 0 && is_hiragana(character)
+                }
+            };
+            let ending = sentence[stem_end..]
+                .iter()
+                .take_while(|(_, character)| continues(*character))
+                .count();
+            if ending > trimmed + MAX_EXTRA_ENDING_CHARS {
+                return None;
+            }
+            let end = sentence
+                .get(stem_end + ending)
+                .map_or(sentence_len(&sentence), |(offset, _)| *offset);
+            Some(sentence[start].0..end)
+        })
+    })
+}
+
+fn sentence_len(sentence: &[(usize, char)]) -> usize {
+    sentence
+        .last()
+        .map_or(0, |(offset, character)| offset + character.len_utf8())
+}
+
+fn is_hiragana(character: char) -> bool {
+    ('\u{3041}'..='\u{309f}').contains(&character)
+}
+
+/// Scripts that are written without spaces between words.
+fn is_unspaced_script(character: char) -> bool {
+    is_hiragana(character)
+        || ('\u{30a0}'..='\u{30ff}').contains(&character)
+        || ('\u{3400}'..='\u{4dbf}').contains(&character)
+        || ('\u{4e00}'..='\u{9fff}').contains(&character)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::input::{
+        card_catalog::models::{
+            CardDirection, CardId, CardStatus, LeechState, Meaning, UsageExample, Word,
+        },
+        language_profile::models::ProfileId,
+    };
+
+    fn blanked(sentence: &str, word: &str) -> Option<String> {
+        locate_word(sentence, word).map(|range| sentence[range].to_string())
+    }
+
+    #[test]
+    fn spaced_words_are_matched_at_word_boundaries() {
+        assert_eq!(blanked("The Cat sleeps.", "cat"), Some("Cat".to_string()));
+        assert_eq!(blanked("Two cats sleep.", "cat"), Some("cats".to_string()));
+        assert_eq!(blanked("A category of its own.", "cat"), None);
+        assert_eq!(blanked("Concatenate them.", "cat"), None);
+        assert_eq!(
+            blanked("Give it up now.", "give up"),
+            None,
+            "phrases must appear verbatim"
+        );
+        assert_eq!(
+            blanked("Never give up.", "give up"),
+            Some("give up".to_string())
+        );
+    }
+
+    #[test]
+    fn russian_inflected_forms_are_found() {
+        assert_eq!(
+            blanked("Я читаю книгу каждый вечер.", "книга"),
+            Some("книгу".to_string())
+        );
+        assert_eq!(
+            blanked("Она читает быстро.", "читать"),
+            Some("читает".to_string())
+        );
+        assert_eq!(
+            blanked("У нас большая семья.", "большой"),
+            Some("большая".to_string())
+        );
+        assert_eq!(blanked("Вчера он шёл домой.", "идти"), None);
+    }
+
+    #[test]
+    fn japanese_words_are_found_without_spaces() {
+        assert_eq!(blanked("毎日水を飲みます。", "水"), Some("水".to_string()));
+        assert_eq!(
+            blanked("昨日すしを食べました。", "食べる"),
+            Some("食べました".to_string())
+        );
+        assert_eq!(
+            blanked("図書館で勉強しています。", "勉強する"),
+            Some("勉強しています".to_string())
+        );
+        assert_eq!(blanked("りんごがすきです。", "きれい"), None);
+    }
+
+    #[test]
+    fn byte_ranges_follow_character_boundaries() {
+        let sentence = "Ёж видит ежа.";
+
+        assert_eq!(locate_word(sentence, "ёж"), Some(0..4));
+        assert_eq!(
+            locate_word(sentence, "ежа"),
+            Some(sentence.len() - 7..sentence.len() - 1)
+        );
+    }
+
+    #[test]
+    fn examples_without_the_word_are_skipped() {
+        let example = |sentence: &str, translation: &str| UsageExample {
+            sentence: sentence.to_string(),
+            translation: translation.to_string(),
+        };
+        let card = Card {
+            id: CardId::new("card"),
+            profile_id: ProfileId::new("profile"),
+            direction: CardDirection::Straight,
+            word: Word {
+                text: "橋".to_string(),
+                readings: vec!["はし".to_string()],
+            },
+            meanings: vec![Meaning {
+                definition: "bridge".to_string(),
+                translated_definition: String::new(),
+                word_translations: vec!["bridge".to_string()],
+                examples: vec![
+                    example("川を渡る。", "Cross the river."),
+                    example("はしを渡る。", "Cross the bridge."),
+                ],
+            }],
+            score: 0,
+            leech: LeechState::default(),
+            status: CardStatus::Active,
+            created_at: 0,
+            version: 0,
+        };
+
+        assert_eq!(
+            cloze_examples(&card),
+            vec![ClozeExample {
+                prompt: ClozePrompt {
+                    sentence_before: String::new(),
+                    sentence_after: "を渡る。".to_string(),
+                    translation: "Cross the bridge.".to_string(),
+                },
+                answer: "はし".to_string(),
+            }]
+        );
+    }
+}
@@ -9,7 +9,7 @@ use crate::ports::{
         models::{
             CreateLanguageProfileCommand, DeleteLanguageProfileCommand, GetLanguageProfileQuery,
             LanguageProfile, LanguageProfileChanges, LanguageProfileError, LanguageProfileSummary,
-            ListLanguageProfilesQuery, ProfileId, UpdateLanguageProfileCommand,
+            LeechAction, ListLanguageProfilesQuery, ProfileId, UpdateLanguageProfileCommand,
         },
     },
     output::repository::language_profile::{
@@ -19,6 +19,8 @@ use crate::ports::{
 
 const MAX_PROFILE_NAME_LENGTH: usize = 50;
 const SUPPORTED_LANGUAGES: [&str; 3] = ["en-US", "ru-RU", "ja-JP"];
+pub const DEFAULT_LEECH_THRESHOLD: u32 = 8;
+const MAX_LEECH_THRESHOLD: u32 = 100;
 
 pub struct LanguageProfileService {
     repository: Arc<dyn LanguageProfileRepository>,
@@ -78,6 +80,15 @@ impl LanguageProfileService {
         if let Some(target_language) = changes.target_language {
             profile.target_language = target_language;
         }
+        if let Some(leech_threshold) = changes.leech_threshold {
+            if leech_threshold > MAX_LEECH_THRESHOLD {
+                return Err(LanguageProfileError::InvalidProfile);
+            }
+            profile.leech_threshold = leech_threshold;
+        }
+        if let Some(leech_action) = changes.leech_action {
+            profile.leech_action = leech_action;
+        }
         Self::validate_languages(&profile.source_language, &profile.target_language)?;
         Ok(profile)
     }
@@ -110,6 +121,8 @@ impl LanguageProfileUsecase for LanguageProfileService {
                 name,
                 source_language: command.source_language,
                 target_language: command.target_language,
+                leech_threshold: DEFAULT_LEECH_THRESHOLD,
+                leech_action: LeechAction::default(),
                 version: 0,
             })
             .await
@@ -0,0 +1,360 @@
+use super::answer_normalization::{fold_kana, fold_width};
+
+/// How a typed reading compares with one of the readings stored on a card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadingMatch {
+    Exact,
+    /// The spelling matches but a different syllable is stressed.
+    MisplacedStress,
+    Different,
+}
+
+const RUSSIAN_VOWELS: &str = "аеёиоуыэюя";
+const STRESS_MARKS: &[char] = &['\u{301}', '\u{b4}', '\'', '`'];
+
+const ROMAJI: &[(&str, &str)] = &[
+    ("kya", "きゃ"),
+    ("kyu", "きゅ"),
+    ("kyo", "きょ"),
+    ("sha", "しゃ"),
+    ("shu", "しゅ"),
+    ("sho", "しょ"),
+    ("she", "しぇ"),
+    ("shi", "し"),
+    ("sya", "しゃ"),
+    ("syu", "しゅ"),
+    ("syo", "しょ"),
+    ("cha", "ちゃ"),
+    ("chu", "ちゅ"),
+    ("cho", "ちょ"),
+    ("che", "ちぇ"),
+    ("chi", "ち"),
+    ("tya", "ちゃ"),
+    ("tyu", "ちゅ"),
+    ("tyo", "ちょ"),
+    ("tsu", "つ"),
+    ("nya", "にゃ"),
+    ("nyu", "にゅ"),
+    ("nyo", "にょ"),
+    ("hya", "ひゃ"),
+    ("hyu", "ひゅ"),
+    ("hyo", "ひょ"),
+    ("mya", "みゃ"),
+    ("myu", "みゅ"),
+    ("myo", "みょ"),
+    ("rya", "りゃ"),
+    ("ryu", "りゅ"),
+    ("ryo", "りょ"),
+    ("gya", "ぎゃ"),
+    ("gyu", "ぎゅ"),
+    ("gyo", "ぎょ"),
+    ("jya", "じゃ"),
+    ("jyu", "じゅ"),
+    ("jyo", "じょ"),
+    ("zya", "じゃ"),
+    ("zyu", "じゅ"),
+    ("zyo", "じょ"),
+    ("bya", "びゃ"),
+    ("byu", "びゅ"),
+    ("byo", "びょ"),
+    ("pya", "ぴゃ"),
+    ("pyu", "ぴゅ"),
+    ("pyo", "ぴょ"),
+    ("ka", "か"),
+    ("ki", "き"),
+    ("ku", "く"),
+    ("ke", "け"),
+    ("ko", "こ"),
+    ("sa", "さ"),
+    ("si", "し"),
+    ("su", "す"),
+    ("se", "せ"),
+    ("so", "そ"),
+    ("ta", "た"),
+    ("ti", "ち"),
+    ("tu", "つ"),
+    ("te", "て"),
+    ("to", "と"),
+    ("na", "な"),
+    ("ni", "に"),
+    ("nu", "ぬ"),
+    ("ne", "ね"),
+    ("no", "の"),
+    ("ha", "は"),
+    ("hi", "ひ"),
+    ("fu", "ふ"),
+    ("hu", "ふ"),
+    ("he", "へ"),
+    ("ho", "ほ"),
+    ("fa", "ふぁ"),
+    ("fi", "ふぃ"),
+    ("fe", "ふぇ"),
+    ("fo", "ふぉ"),
+    ("ma", "ま"),
+    ("mi", "み"),
+    ("mu", "む"),
+    ("me", "め"),
+    ("mo", "も"),
+    ("ya", "や"),
+    ("yu", "ゆ"),
+    ("yo", "よ"),
+    ("ra", "ら"),
+    ("ri", "り"),
+    ("ru", "る"),
+    ("re", "れ"),
+    ("ro", "ろ"),
+    ("wa", "わ"),
+    ("wo", "を"),
+    ("ga", "が"),
+    ("gi", "ぎ"),
+    ("gu", "ぐ"),
+    ("ge", "げ"),
+    ("go", "ご"),
+    ("za", "ざ"),
+    ("ji", "じ"),
+    ("zi", "じ"),
+    ("zu", "ず"),
+    ("ze", "ぜ"),
+    ("zo", "ぞ"),
+    ("ja", "じゃ"),
+    ("ju", "じゅ"),
+    ("je", "じぇ"),
+    ("jo", "じょ"),
+    ("da", "だ"),
+    ("di", "ぢ"),
+    ("du", "づ"),
+    ("de", "で"),
+    ("do", "ど"),
+    ("ba", "ば"),
+    ("bi", "び"),
+    ("bu", "ぶ"),
+    ("be", "べ"),
+    ("bo", "ぼ"),
+    ("pa", "ぱ"),
+    ("pi", "ぴ"),
+    ("pu", "ぷ"),
+    ("pe", "ぺ"),
+    ("po", "ぽ"),
+    ("a", "あ"),
+    ("i", "い"),
+    ("u", "う"),
+    ("e", "え"),
+    ("o", "お"),
+];
+
+/// Compares a typed reading with an expected one in the conventions of `language`.
+///
+/// Japanese readings ignore the difference between hiragana and katakana and between
+/// full-width and half-width forms, and accept Hepburn or Kunrei romaji. Russian readings
+/// compare the spelling and the stressed vowels separately; stress may be marked with a
+/// combining acute accent or an apostrophe after the vowel, and an expected reading without
+/// stress marks accepts any stress. Other readings are IPA, where enclosing slashes and ASCII
+/// stand-ins for stress and length marks are accepted.
+pub fn compare_reading(language: &str, expected: &str, answer: &str) -> ReadingMatch {
+    match language {
+        "ja-JP" => exact(kana_reading(expected) == kana_reading(answer)),
+        "ru-RU" => {
+            let (expected_spelling, expected_stress) = russian_reading(expected);
+            let (answer_spelling, answer_stress) = russian_reading(answer);
+            if expected_spelling != answer_spelling {
+                ReadingMatch::Different
+            } else if expected_stress.is_empty() || expected_stress == answer_stress {
+                ReadingMatch::Exact
+            } else {
+                ReadingMatch::MisplacedStress
+            }
+        }
+        _ => exact(ipa_reading(expected) == ipa_reading(answer)),
+    }
+}
+
+fn exact(matches: bool) -> ReadingMatch {
+    if matches {
+        ReadingMatch::Exact
+    } else {
+        ReadingMatch::Different
+    }
+}
+
+fn kana_reading(text: &str) -> String {
+    fold_kana(&romaji_to_hiragana(&fold_width(text).to_lowercase()))
+        .chars()
+        .filter(|character| !character.is_whitespace())
+        .collect()
+}
+
+fn romaji_to_hiragana(text: &str) -> String {
+    let characters = text.chars().collect::<Vec<_>>();
+    let mut kana = String::new();
+    let mut index = 0;
+    while index < characters.len() {
+        let character = characters[index];
+        let next = characters.get(index + 1).copied();
+        if character == 'n' && next.is_none_or(|next| !"aiueoy".contains(next)) {
+            kana.push('ん');
+            index += if next == Some('\'') { 2 } else { 1 };
+            continue;
+        }
+        if (next == Some(character) && character.is_ascii_alphabetic()
+            || character == 't' && next == Some('c'))
+            && !"aiueon".contains(character)
+        {
+            kana.push('っ');
+            index += 1;
+            continue;
+        }
+        if character == '-' {
+            kana.push('ー');
+            index += 1;
+            continue;
+        }
+        let syllable = (1..=3).rev().find_map(|length| {
+            let candidate = characters
+                .get(index..index + length)?
+                .iter()
+                .collect::<String>();
+            ROMAJI.iter().find(|(romaji, _)| *romaji == candidate)
+        });
+        match syllable {
+            Some((romaji, syllable)) => {
+                kana.push_str(syllable);
+                index += romaji.len();
+            }
+            None => {
+                kana.push(character);
+                index += 1;
+            }
+        }
+    }
+    kana
+}
+
+/// Splits a Russian reading into its spelling without stress marks (with ё written as е) and
+/// the positions of its stressed vowels.
+fn russian_reading(text: &str) -> (String, Vec<usize>) {
+    let mut spelling = String::new();
+    let mut stressed = Vec::new();
+    let mut vowels = 0;
+    for character in text.trim().to_lowercase().chars() {
+        if STRESS_MARKS.contains(&character) {
+            if vowels > 0 {
+                stressed.push(vowels - 1);
+            }
+            continue;
+        }
+        if character == 'ё' {
+            stressed.push(vowels);
+        }
+        if RUSSIAN_VOWELS.contains(character) {
+            vowels += 1;
+        }
+        spelling.push(if character == 'ё' { 'е' } else { character });
+    }
+    stressed.sort_unstable();
+    stressed.dedup();
+    (spelling, stressed)
+}
+
+fn ipa_reading(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .filter(|character| !character.is_whitespace() && !"/[]".contains(*character))
+        .map(|character| match character {
+            '\'' => 'ˈ',
+            ',' => 'ˌ',
+            ':' => 'ː',
+            'g' => 'ɡ',
+            _ => character,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn japanese_readings_fold_katakana_and_accept_romaji() {
+        for answer in ["たべる", "タベル", "ﾀﾍﾞﾙ", "taberu", " ＴＡＢＥＲＵ "] {
+            assert_eq!(
+                compare_reading("ja-JP", "たべる", answer),
+                ReadingMatch::Exact,
+                "{answer}"
+            );
+        }
+        for (expected, answer) in [
+            ("がっこう", "gakkou"),
+            ("しんぶん", "shinbun"),
+            ("こんにち", "konnichi"),
+            ("きんえん", "kin'en"),
+            ("まっちゃ", "matcha"),
+            ("コーヒー", "ko-hi-"),
+            ("じしょ", "zisyo"),
+        ] {
+            assert_eq!(
+                compare_reading("ja-JP", expected, answer),
+                ReadingMatch::Exact,
+                "{answer}"
+            );
+        }
+        assert_eq!(
+            compare_reading("ja-JP", "たべる", "tabero"),
+            ReadingMatch::Different
+        );
+    }
+
+    #[test]
+    fn russian_stress_is_checked_separately_from_spelling() {
+        for answer in ["молоко\u{301}", "молоко'", "МОЛОКО\u{301}"] {
+            assert_eq!(
+                compare_reading("ru-RU", "молоко\u{301}", answer),
+                ReadingMatch::Exact,
+                "{answer}"
+            );
+        }
+        assert_eq!(
+            compare_reading("ru-RU", "молоко\u{301}", "моло\u{301}ко"),
+            ReadingMatch::MisplacedStress
+        );
+        assert_eq!(
+            compare_reading("ru-RU", "молоко\u{301}", "молоко"),
+            ReadingMatch::MisplacedStress
+        );
+        assert_eq!(
+            compare_reading("ru-RU", "молоко\u{301}", "малако\u{301}"),
+            ReadingMatch::Different
+        );
+    }
+
+    #[test]
+    fn russian_yo_marks_its_own_stress() {
+        assert_eq!(
+            compare_reading("ru-RU", "ёлка", "ёлка"),
+            ReadingMatch::Exact
+        );
+        assert_eq!(
+            compare_reading("ru-RU", "ёлка", "е'лка"),
+            ReadingMatch::Exact
+        );
+        assert_eq!(
+            compare_reading("ru-RU", "ёлка", "елка"),
+            ReadingMatch::MisplacedStress
+        );
+        assert_eq!(compare_reading("ru-RU", "дом", "дом"), ReadingMatch::Exact);
+    }
+
+    #[test]
+    fn ipa_readings_accept_ascii_stand_ins() {
+        for answer in ["/ˈwɔːtər/", "ˈwɔːtər", "'wɔ:tər", "[ˈwɔːtər]"] {
+            assert_eq!(
+                compare_reading("en-US", "/ˈwɔːtər/", answer),
+                ReadingMatch::Exact,
+                "{answer}"
+            );
+        }
+        assert_eq!(
+            compare_reading("en-US", "/ˈwɔːtər/", "ˈwɔtər"),
+            ReadingMatch::Different
+        );
+    }
+}
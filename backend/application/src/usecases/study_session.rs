@@ -1,31 +1,49 @@
-use std::{
-    collections::HashSet,
-    sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
-};
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
+use futures_util::{StreamExt, TryStreamExt, stream};
 use uuid::Uuid;
 
-use super::pronunciation_scoring::score_pronunciation;
+use super::{
+    card_scheduling::{
+        MAX_UTC_OFFSET_MINUTES, local_day, local_day_start, review_card, track_failures,
+    },
+    pronunciation_scoring::score_pronunciation,
+    study_session_reducer::{
+        TransitionContext, accepts_recording, answer_option_seed, apply, assess, cloze_example,
+        correct_grade, correct_streak, current_card_id, hint_text, next_hint, pending_card_ids,
+        presenting_next_card, reduce, set_card_ids, shuffle, shuffle_with_seed, test_limit_reached,
+        undoable, version_seed,
+    },
+    test_card_selection::pick_test_card,
+};
 use crate::ports::{
     input::{
-        card_catalog::models::{Card, CardDirection, CardOrder, CardSelectionQuery},
+        card_catalog::models::{
+            Card, CardDirection, CardId, CardOrder, CardSchedule, CardSelectionQuery, CardStatus,
+            DueCardsQuery, LeechState, ReviewGrade,
+        },
+        card_speech::{
+            CardSpeechUsecase,
+            models::{CardSpeechCommand, CardSpeechError},
+        },
         study_session::{
             StudySessionUsecase,
             models::{
-                AnswerFeedback, ApplyStudySessionActionCommand, AssessPronunciationCommand,
-                CreateStudySessionCommand, CurrentCardView, EndStudySessionCommand,
-                GetStudySessionPreferencesQuery, PronunciationAssessmentReport,
-                PronunciationFeedback, PronunciationFeedbackKind, SessionAnswerResult,
+                AnswerFeedback, AnswerFormat, AnswerOutcome, ApplyStudySessionActionCommand,
+                AssessPronunciationCommand, CreateStudySessionCommand, CurrentCardView,
+                EndStudySessionCommand, GetStudySessionPreferencesQuery, GradedAnswer,
+                ListActiveStudySessionsQuery, PronunciationAssessmentReport, PronunciationFeedback,
+                PronunciationFeedbackKind, RemainingBudget, ResumeStudySessionQuery, RevealedHint,
                 SessionFilter, SetOutcome, StudySession, StudySessionAction, StudySessionError,
-                StudySessionMode, StudySessionPhase, StudySessionPreferences, StudySessionProgress,
-                StudySessionStatus, StudySessionSummary, StudySessionTransition, StudySessionView,
+                StudySessionEvent, StudySessionMode, StudySessionPhase, StudySessionPreferences,
+                StudySessionProgress, StudySessionStatus, StudySessionSummary,
+                StudySessionTransition, StudySessionView,
             },
         },
     },
     output::{
-        PronunciationAssessor,
+        Clock, PronunciationAssessor, RandomSource,
         pronunciation_assessor::models::{
             PronunciationAssessmentError, PronunciationAssessmentRequest,
         },
@@ -35,29 +53,48 @@ use crate::ports::{
             card::models::CardRepositoryError,
             language_profile::models::LanguageProfileRepositoryError,
             pronunciation_settings::models::PronunciationSettingsRepositoryError,
+            review_log::models::{ReviewLogEntry, ReviewOutcome},
             study_session::models::{
-                CardProgressUpdate, EndSessionRequest, StoreSessionRequest, StudySessionCommit,
-                StudySessionRepositoryError,
+                AcceptedTranslation, CardProgressUpdate, EndSessionRequest, StoreSessionRequest,
+                StudySessionCommit, StudySessionRepositoryError, UndoTransitionRequest,
             },
         },
     },
 };
 
+const MIN_ANSWER_DISTRACTORS: usize = 3;
+const MAX_ANSWER_DISTRACTORS: usize = 5;
+/// Distractor candidates are loaded this many at a time until enough options are found.
+const DISTRACTOR_BATCH_SIZE: usize = 16;
+/// At most this many cards are considered as distractors of one question.
+const DISTRACTOR_CANDIDATE_LIMIT: usize = 64;
+/// Dictation audio of this many cards is synthesized at the same time.
+const PREFETCH_CONCURRENCY: usize = 4;
+
 pub struct StudySessionService {
     cards: Arc<dyn CardRepository>,
     sessions: Arc<dyn StudySessionRepository>,
     profiles: Arc<dyn LanguageProfileRepository>,
     pronunciation_settings: Arc<dyn PronunciationSettingsRepository>,
     pronunciation_assessor: Arc<dyn PronunciationAssessor>,
+    speech: Arc<dyn CardSpeechUsecase>,
+    clock: Arc<dyn Clock>,
+    random: Arc<dyn RandomSource>,
+    session_idle_expiry: Duration,
 }
 
 impl StudySessionService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         cards: Arc<dyn CardRepository>,
         sessions: Arc<dyn StudySessionRepository>,
         profiles: Arc<dyn LanguageProfileRepository>,
         pronunciation_settings: Arc<dyn PronunciationSettingsRepository>,
         pronunciation_assessor: Arc<dyn PronunciationAssessor>,
+        speech: Arc<dyn CardSpeechUsecase>,
+        clock: Arc<dyn Clock>,
+        random: Arc<dyn RandomSource>,
+        session_idle_expiry: Duration,
     ) -> Self {
         Self {
             cards,
@@ -65,6 +102,10 @@ impl StudySessionService {
             profiles,
             pronunciation_settings,
             pronunciation_assessor,
+            speech,
+            clock,
+            random,
+            session_idle_expiry,
         }
     }
 
@@ -122,81 +163,248 @@ impl StudySessionService {
         }
     }
 
+    /// Caches the audio of dictation cards ahead of time, a few cards at a time; cards deleted
+    /// meanwhile are skipped.
+    async fn prefetch_audio(
+        &self,
+        session: &StudySession,
+        card_ids: &[CardId],
+    ) -> Result<(), StudySessionError> {
+        if session.answer_format != AnswerFormat::Dictation {
+            return Ok(());
+        }
+        stream::iter(card_ids.iter().cloned())
+            .map(|card_id| self.prefetch_card_audio(session, card_id))
+            .buffer_unordered(PREFETCH_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    async fn prefetch_card_audio(
+        &self,
+        session: &StudySession,
+        card_id: CardId,
+    ) -> Result<(), StudySessionError> {
+        match self
+            .speech
+            .get_speech(CardSpeechCommand {
+                user_id: session.owner_id.clone(),
+                profile_id: session.profile_id.clone(),
+                card_id,
+                regenerate: false,
+            })
+            .await
+        {
+            Ok(_) | Err(CardSpeechError::CardNotFound) => Ok(()),
+            Err(CardSpeechError::ProfileNotFound) => Err(StudySessionError::NotFound),
+            Err(CardSpeechError::NotConfigured | CardSpeechError::UnsupportedProvider) => {
+                Err(StudySessionError::SpeechNotConfigured)
+            }
+            Err(error) => Err(StudySessionError::Unexpected(error.to_string())),
+        }
+    }
+
     fn validate(command: &CreateStudySessionCommand) -> Result<(), StudySessionError> {
         if command
             .min_score
             .zip(command.max_score)
             .is_some_and(|(minimum, maximum)| minimum > maximum)
             || !(1..=100).contains(&command.pronunciation_score_threshold)
+            || command.utc_offset_minutes.abs() > MAX_UTC_OFFSET_MINUTES
             || match command.mode {
                 StudySessionMode::Learning => !matches!(command.cards_per_set, Some(1..=100)),
-                StudySessionMode::Test => command.cards_per_set.is_some(),
+                StudySessionMode::Test => {
+                    command.cards_per_set.is_some()
+                        || command
+                            .recent_ban_window
+                            .is_some_and(|window| window > 1000)
+                        || command
+                            .max_cards
+                            .is_some_and(|max_cards| !(1..=1000).contains(&max_cards))
+                        || command
+                            .time_limit_minutes
+                            .is_some_and(|minutes| !(1..=240).contains(&minutes))
+                        || command
+                            .correct_streak_goal
+                            .is_some_and(|goal| !(1..=1000).contains(&goal))
+                }
+                StudySessionMode::Review => {
+                    command.cards_per_set.is_some()
+                        || !matches!(command.new_cards_per_day, Some(0..=1000))
+                }
             }
+            || (command.mode != StudySessionMode::Review && command.new_cards_per_day.is_some())
+            || (command.mode != StudySessionMode::Learning && command.retry_missed_only.is_some())
+            || (command.mode != StudySessionMode::Test
+                && (command.card_order.is_some()
+                    || command.recent_ban_window.is_some()
+                    || command.max_cards.is_some()
+                    || command.time_limit_minutes.is_some()
+                    || command.correct_streak_goal.is_some()))
         {
             return Err(StudySessionError::InvalidSession);
         }
         Ok(())
     }
 
-    async fn matching_cards(&self, session: &StudySession) -> Result<Vec<Card>, StudySessionError> {
-        self.cards
+    /// The ids of the cards matching the session filter, shuffled from the session seed.
+    async fn matching_cards(
+        &self,
+        session: &StudySession,
+    ) -> Result<Vec<CardId>, StudySessionError> {
+        let mut card_ids = self
+            .cards
             .select_for_session(CardSelectionQuery {
                 user_id: session.owner_id.clone(),
                 profile_id: session.profile_id.clone(),
                 direction: session.filter.direction,
                 min_score: session.filter.min_score,
                 max_score: session.filter.max_score,
-                order: CardOrder::Random,
+                order: CardOrder::OldestFirst,
                 limit: None,
+                available_at: self.clock.now_millis(),
+                excluded_ids: Vec::new(),
             })
             .await
-            .map_err(Self::map_card_error)
+            .map_err(Self::map_card_error)?
+            .into_iter()
+            .map(|candidate| candidate.id)
+            .collect::<Vec<_>>();
+        shuffle(&mut card_ids, session.seed, session.version);
+        Ok(card_ids)
     }
 
+    /// Picks the next test card among candidates that were not tested recently, or among all
+    /// of them once every matching card was, and loads only the chosen one.
     async fn choose_test_card(
         &self,
         session: &StudySession,
     ) -> Result<Option<Card>, StudySessionError> {
-        let cards = self.matching_cards(session).await?;
-        if cards.is_empty() {
-            return Ok(None);
-        }
         let recent = self
             .sessions
-            .recent_test_cards(&session.profile_id, cards.len() / 2)
+            .recent_test_cards(&session.profile_id, session.recent_ban_window)
             .await
-            .map_err(Self::map_session_error)?
+            .map_err(Self::map_session_error)?;
+        let query = CardSelectionQuery {
+            user_id: session.owner_id.clone(),
+            profile_id: session.profile_id.clone(),
+            direction: session.filter.direction,
+            min_score: session.filter.min_score,
+            max_score: session.filter.max_score,
+            // Random picks are drawn from the session seed over a stable order.
+            order: match session.card_order {
+                CardOrder::Random => CardOrder::OldestFirst,
+                order => order,
+            },
+            // Deterministic orders are resolved by SQL; only draws need every candidate.
+            limit: match session.card_order {
+                CardOrder::Random | CardOrder::ScoreWeighted => None,
+                CardOrder::OldestFirst | CardOrder::WeakestFirst | CardOrder::LeastRecentlySeen => {
+                    Some(1)
+                }
+            },
+            available_at: self.clock.now_millis(),
+            excluded_ids: recent,
+        };
+        let mut candidates = self
+            .cards
+            .select_for_session(query.clone())
+            .await
+            .map_err(Self::map_card_error)?;
+        if candidates.is_empty() && !query.excluded_ids.is_empty() {
+            candidates = self
+                .cards
+                .select_for_session(CardSelectionQuery {
+                    excluded_ids: Vec::new(),
+                    ..query
+                })
+                .await
+                .map_err(Self::map_card_error)?;
+        }
+        let Some(candidate) = pick_test_card(
+            &candidates,
+            session.card_order,
+            version_seed(session.seed, session.version),
+        ) else {
+            return Ok(None);
+        };
+        self.cards
+            .find(&session.owner_id, &session.profile_id, &candidate.id)
+            .await
+            .map_err(Self::map_card_error)
+    }
+
+    async fn review_queue(
+        &self,
+        session: &StudySession,
+        new_cards_per_day: usize,
+        utc_offset_minutes: i32,
+    ) -> Result<Vec<CardId>, StudySessionError> {
+        let now = self.clock.now_millis();
+        let today_start = local_day_start(local_day(now, utc_offset_minutes), utc_offset_minutes);
+        let introduced_today = self
+            .cards
+            .count_introduced_since(&session.owner_id, &session.profile_id, today_start)
+            .await
+            .map_err(Self::map_card_error)?;
+        self.cards
+            .select_due(DueCardsQuery {
+                user_id: session.owner_id.clone(),
+                profile_id: session.profile_id.clone(),
+                direction: session.filter.direction,
+                min_score: session.filter.min_score,
+                max_score: session.filter.max_score,
+                due_at: now,
+                new_card_limit: new_cards_per_day.saturating_sub(introduced_today),
+            })
+            .await
+            .map_err(Self::map_card_error)
+    }
+
+    /// Drops cards deleted since the session was last used from the part of the queue that is
+    /// still ahead.
+    async fn restore(&self, session: StudySession) -> Result<StudySession, StudySessionError> {
+        let pending = pending_card_ids(&session);
+        let existing = self
+            .cards
+            .existing_ids(&session.owner_id, &session.profile_id, pending)
+            .await
+            .map_err(Self::map_card_error)?
             .into_iter()
             .collect::<HashSet<_>>();
-        Ok(cards
+        let removed = pending
             .iter()
-            .find(|card| !recent.contains(&card.id))
+            .filter(|card_id| !existing.contains(*card_id))
             .cloned()
-            .or_else(|| cards.into_iter().next()))
-    }
-
-    fn current_card_id(
-        session: &StudySession,
-    ) -> Option<&crate::ports::input::card_catalog::models::CardId> {
-        if session.status != StudySessionStatus::Active {
-            return None;
-        }
-        match (session.mode, session.phase) {
-            (StudySessionMode::Test, _) => session.card_ids.first(),
-            (StudySessionMode::Learning, StudySessionPhase::Study) => session.card_ids.get(
-                session.current_set_index * session.cards_per_set + session.current_card_index,
-            ),
-            (StudySessionMode::Learning, StudySessionPhase::Test) => {
-                session.test_order.get(session.current_card_index)
-            }
+            .collect::<Vec<_>>();
+        if removed.is_empty() {
+            return Ok(session);
         }
+        let now = self.clock.now_millis();
+        let next_test_card =
+            if session.mode == StudySessionMode::Test && !test_limit_reached(&session, now) {
+                self.choose_test_card(&session).await?.map(|card| card.id)
+            } else {
+                None
+            };
+        let expected_version = session.version;
+        let events = presenting_next_card(
+            &session,
+            vec![StudySessionEvent::CardsRemoved {
+                card_ids: removed,
+                next_test_card,
+            }],
+            now,
+        );
+        self.commit(session, expected_version, None, events, None)
+            .await
     }
 
     async fn load_current_card(
         &self,
         session: &StudySession,
     ) -> Result<Option<Card>, StudySessionError> {
-        let Some(card_id) = Self::current_card_id(session) else {
+        let Some(card_id) = current_card_id(session) else {
             return Ok(None);
         };
         self.cards
@@ -209,42 +417,110 @@ impl StudySessionService {
 
     async fn view(&self, session: &StudySession) -> Result<StudySessionView, StudySessionError> {
         let card = self.load_current_card(session).await?;
+        let options = match &card {
+            Some(card) if session.phase == StudySessionPhase::Test => {
+                self.answer_options(session, card).await?
+            }
+            _ => Vec::new(),
+        };
         let pronunciation_required_for_card = card
             .as_ref()
             .is_some_and(|card| card.direction == CardDirection::Straight);
         let current_card = card.map(|card| match session.phase {
             StudySessionPhase::Study => CurrentCardView::Study(card),
-            StudySessionPhase::Test => CurrentCardView::Test {
-                id: card.id,
-                direction: card.direction,
-                prompt: card.word.text,
-                readings: card.word.readings,
-                remaining_meanings: card
-                    .meanings
-                    .len()
-                    .saturating_sub(session.completed_meaning_indices.len()),
-                total_meanings: card.meanings.len(),
-            },
+            StudySessionPhase::Test => {
+                let hints = session
+                    .hints_used
+                    .iter()
+                    .filter_map(|&hint| {
+                        hint_text(session, &card, hint).map(|text| RevealedHint { hint, text })
+                    })
+                    .collect();
+                let hint_available = next_hint(session, &card).is_some();
+                let cloze = cloze_example(session, &card).map(|example| example.prompt);
+                let word_hidden =
+                    session.answer_format == AnswerFormat::Dictation || cloze.is_some();
+                CurrentCardView::Test {
+                    id: card.id,
+                    direction: card.direction,
+                    prompt: (!word_hidden).then_some(card.word.text),
+                    readings: if word_hidden || session.answer_format == AnswerFormat::Reading {
+                        Vec::new()
+                    } else {
+                        card.word.readings
+                    },
+                    remaining_meanings: card
+                        .meanings
+                        .len()
+                        .saturating_sub(session.completed_meaning_indices.len()),
+                    total_meanings: card.meanings.len(),
+                    options,
+                    cloze,
+                    hints,
+                    hint_available,
+                }
+            }
         });
         let total_sets = if session.mode == StudySessionMode::Learning {
             session.card_ids.len().div_ceil(session.cards_per_set)
         } else {
             0
         };
-        let (current_card_number, total_cards) = if session.mode == StudySessionMode::Learning {
-            (
-                session.current_set_index * session.cards_per_set
-                    + session.current_card_index
-                    + usize::from(current_card.is_some()),
-                session.card_ids.len(),
-            )
+        let set_cards = if session.mode == StudySessionMode::Learning {
+            set_card_ids(session).len()
+        } else {
+            0
+        };
+        let current_set_card = if session.mode == StudySessionMode::Learning {
+            session.current_card_index + usize::from(current_card.is_some())
         } else {
-            (
+            0
+        };
+        let (current_card_number, total_cards) = match session.mode {
+            StudySessionMode::Learning => {
+                let set_start = session.current_set_index * session.cards_per_set;
+                let set_len = session
+                    .card_ids
+                    .len()
+                    .saturating_sub(set_start)
+                    .min(session.cards_per_set);
+                // Cards of the set that are not retried count as done.
+                (
+                    set_start + set_len.saturating_sub(set_cards) + current_set_card,
+                    session.card_ids.len(),
+                )
+            }
+            StudySessionMode::Test | StudySessionMode::Review => (
                 session.results.len()
                     + usize::from(current_card.is_some() && !session.awaiting_continue),
-                0,
-            )
+                if session.mode == StudySessionMode::Review {
+                    session.card_ids.len()
+                } else {
+                    session.max_cards.unwrap_or_default()
+                },
+            ),
         };
+        let remaining_budget = (session.mode == StudySessionMode::Test
+            && (session.max_cards.is_some()
+                || session.ends_at.is_some()
+                || session.correct_streak_goal.is_some()))
+        .then(|| RemainingBudget {
+            cards: session
+                .max_cards
+                .map(|max_cards| max_cards.saturating_sub(session.results.len())),
+            time_millis: session
+                .ends_at
+                .map(|ends_at| ends_at.saturating_sub(self.clock.now_millis()).max(0) as u64),
+            correct_in_a_row: session
+                .correct_streak_goal
+                .map(|goal| goal.saturating_sub(correct_streak(session))),
+        });
+        let due_today = (session.mode == StudySessionMode::Review).then(|| {
+            session
+                .card_ids
+                .len()
+                .saturating_sub(session.current_card_index + usize::from(session.awaiting_continue))
+        });
         let summary = StudySessionSummary {
             correct: session
                 .results
@@ -266,6 +542,8 @@ impl StudySessionService {
             id: session.id.clone(),
             profile_id: session.profile_id.clone(),
             mode: session.mode,
+            answer_format: session.answer_format,
+            answer_strictness: session.answer_strictness,
             phase: session.phase,
             status: session.status,
             pronunciation_check_enabled: session.pronunciation_check_enabled,
@@ -280,231 +558,532 @@ impl StudySessionService {
             pronunciation_technical_failures: session.pronunciation_technical_failures,
             pronunciation_disable_required: session.pronunciation_disable_required,
             awaiting_continue: session.awaiting_continue,
+            retype_pending: session.retype_pending,
             current_card,
+            due_today,
             progress: StudySessionProgress {
                 current_card: current_card_number,
                 total_cards,
                 current_set: session.current_set_index + usize::from(total_sets > 0),
                 total_sets,
+                current_set_card,
+                set_cards,
             },
+            remaining_budget,
             summary,
             version: session.version,
         })
     }
 
-    fn similarity_score(expected: &str, actual: &str) -> f64 {
-        let expected = expected.trim().to_lowercase();
-        let actual = actual.trim().to_lowercase();
-        if expected == actual {
-            return 1.0;
-        }
-        let length = expected.chars().count().max(actual.chars().count());
-        if length == 0 {
-            0.0
-        } else {
-            1.0 - strsim::damerau_levenshtein(&expected, &actual) as f64 / length as f64
-        }
-    }
-
-    fn best_matching_translation(
+    /// Offers a translation of the first open meaning among distractors taken from other cards
+    /// of the profile in the same direction, preferring a similar score, so every option is in
+    /// the answer's language. Cards without enough distractors fall back to a typed answer.
+    async fn answer_options(
+        &self,
+        session: &StudySession,
         card: &Card,
-        completed_meaning_indices: &[usize],
-        answer: &str,
-    ) -> Option<(usize, String)> {
-        let mut best_match: Option<(f64, usize, String)> = None;
-        for (meaning_index, meaning) in card.meanings.iter().enumerate() {
-            if completed_meaning_indices.contains(&meaning_index) {
-                continue;
+    ) -> Result<Vec<String>, StudySessionError> {
+        if session.answer_format != AnswerFormat::MultipleChoice || session.awaiting_continue {
+            return Ok(Vec::new());
+        }
+        let seed = answer_option_seed(session, card);
+        let Some(correct) = card
+            .meanings
+            .iter()
+            .enumerate()
+            .find(|(index, _)| !session.completed_meaning_indices.contains(index))
+            .and_then(|(_, meaning)| {
+                meaning
+                    .word_translations
+                    .get(seed as usize % meaning.word_translations.len().max(1))
+            })
+        else {
+            return Ok(Vec::new());
+        };
+        let mut seen = card
+            .meanings
+            .iter()
+            .flat_map(|meaning| &meaning.word_translations)
+            .map(|translation| translation.trim().to_lowercase())
+            .collect::<HashSet<_>>();
+        let mut candidates = self
+            .cards
+            .select_for_session(CardSelectionQuery {
+                user_id: session.owner_id.clone(),
+                profile_id: session.profile_id.clone(),
+                direction: Some(card.direction),
+                min_score: None,
+                max_score: None,
+                order: CardOrder::OldestFirst,
+                limit: Some(DISTRACTOR_CANDIDATE_LIMIT),
+                available_at: self.clock.now_millis(),
+                excluded_ids: vec![card.id.clone()],
+            })
+            .await
+            .map_err(Self::map_card_error)?;
+        shuffle_with_seed(&mut candidates, seed);
+        candidates.sort_by_key(|candidate| candidate.score.abs_diff(card.score));
+        let mut options = vec![correct.clone()];
+        for batch in candidates.chunks(DISTRACTOR_BATCH_SIZE) {
+            if options.len() > MAX_ANSWER_DISTRACTORS {
+                break;
             }
-            for expected in &meaning.word_translations {
-                let score = Self::similarity_score(expected, answer);
-                if score >= 0.8
-                    && best_match
-                        .as_ref()
-                        .is_none_or(|(best_score, _, _)| score > *best_score)
-                {
-                    best_match = Some((score, meaning_index, expected.clone()));
+            let card_ids = batch
+                .iter()
+                .map(|candidate| candidate.id.clone())
+                .collect::<Vec<_>>();
+            let loaded = self
+                .cards
+                .find_many(&session.owner_id, &session.profile_id, &card_ids)
+                .await
+                .map_err(Self::map_card_error)?;
+            for candidate in loaded {
+                if options.len() > MAX_ANSWER_DISTRACTORS {
+                    break;
+                }
+                let Some(translation) = candidate
+                    .meanings
+                    .first()
+                    .and_then(|meaning| meaning.word_translations.first())
+                else {
+                    continue;
+                };
+                if seen.insert(translation.trim().to_lowercase()) {
+                    options.push(translation.clone());
                 }
             }
         }
-        best_match.map(|(_, meaning_index, expected)| (meaning_index, expected))
+        if options.len() <= MIN_ANSWER_DISTRACTORS {
+            return Ok(Vec::new());
+        }
+        shuffle_with_seed(&mut options, seed.rotate_left(32));
+        Ok(options)
     }
 
-    fn shuffle<T>(items: &mut [T], salt: u64) {
-        let mut state = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|duration| duration.as_nanos() as u64)
-            .unwrap_or(0)
-            ^ salt;
-        for index in (1..items.len()).rev() {
-            state ^= state << 13;
-            state ^= state >> 7;
-            state ^= state << 17;
-            items.swap(index, state as usize % (index + 1));
+    /// Loads the stored schedule of a card about to be graded. Learn mini-tests never change
+    /// schedules, so they skip the lookup.
+    async fn schedule_before_answer(
+        &self,
+        session: &StudySession,
+        card_id: &CardId,
+    ) -> Result<Option<CardSchedule>, StudySessionError> {
+        if session.mode == StudySessionMode::Learning {
+            return Ok(None);
         }
+        Ok(self
+            .cards
+            .find_schedules(
+                &session.owner_id,
+                &session.profile_id,
+                std::slice::from_ref(card_id),
+            )
+            .await
+            .map_err(Self::map_card_error)?
+            .into_iter()
+            .next())
+    }
+
+    /// Counts a graded answer towards the leech threshold of the profile and decides whether
+    /// the card is suspended as a result; like schedules, leech states are left alone by Learn
+    /// mini-tests.
+    async fn leech_after_answer(
+        &self,
+        session: &StudySession,
+        previous: LeechState,
+        is_correct: bool,
+    ) -> Result<Option<(LeechState, Option<CardStatus>)>, StudySessionError> {
+        if session.mode == StudySessionMode::Learning {
+            return Ok(None);
+        }
+        let profile = self
+            .profiles
+            .find(&session.owner_id, &session.profile_id)
+            .await
+            .map_err(Self::map_profile_error)?
+            .ok_or(StudySessionError::NotFound)?;
+        Ok(Some(track_failures(
+            previous,
+            is_correct,
+            profile.leech_threshold,
+            profile.leech_action,
+        )))
     }
 
+    /// Builds the score, schedule, leech and status update for a graded card from its schedule
+    /// before the answer; Learn mini-tests are practice right after studying and leave all of
+    /// them untouched.
+    fn card_progress(
+        &self,
+        session: &StudySession,
+        card_id: &CardId,
+        score_delta: i32,
+        grade: ReviewGrade,
+        leech: Option<(LeechState, Option<CardStatus>)>,
+    ) -> Vec<CardProgressUpdate> {
+        if session.mode == StudySessionMode::Learning {
+            return Vec::new();
+        }
+        let (leech, status) = leech.unzip();
+        vec![CardProgressUpdate {
+            card_id: card_id.clone(),
+            score_delta,
+            schedule: Some(review_card(
+                session.schedule_before_answer.as_ref(),
+                card_id,
+                grade,
+                self.clock.now_millis(),
+            )),
+            leech,
+            status: status.flatten(),
+        }]
+    }
+
+    /// Applies the events of a transition and commits them together with the card progress,
+    /// test selection and review log entries they imply, and the translation the learner kept.
     async fn commit(
         &self,
-        session: StudySession,
+        mut session: StudySession,
         expected_version: u64,
-        progress: Vec<CardProgressUpdate>,
-        selected_test_card: Option<crate::ports::input::card_catalog::models::CardId>,
+        card: Option<&Card>,
+        events: Vec<StudySessionEvent>,
+        accepted_translation: Option<AcceptedTranslation>,
     ) -> Result<StudySession, StudySessionError> {
+        let mut card_progress = Vec::new();
+        let mut review_log = Vec::new();
+        let mut selected_test_card = None;
+        for event in &events {
+            match event {
+                StudySessionEvent::AnswerGraded(graded) => {
+                    let card = card.ok_or(StudySessionError::InvalidAction)?;
+                    apply(&mut session, event);
+                    if graded.outcome == AnswerOutcome::AlmostCorrect {
+                        continue;
+                    }
+                    let is_correct = graded.outcome == AnswerOutcome::Correct;
+                    if graded.card_completed {
+                        let grade = if is_correct {
+                            correct_grade(&session, graded.response_time_millis)
+                        } else {
+                            ReviewGrade::Again
+                        };
+                        let leech = self
+                            .leech_after_answer(&session, graded.leech_before_answer, is_correct)
+                            .await?;
+                        card_progress.extend(self.card_progress(
+                            &session,
+                            &card.id,
+                            graded.score_delta,
+                            grade,
+                            leech,
+                        ));
+                    }
+                    review_log.push(self.review_log_entry(
+                        &session,
+                        card,
+                        graded.answer.clone(),
+                        graded.matched_answer.clone(),
+                        Self::review_outcome(graded),
+                        graded.score_delta,
+                        graded.response_time_millis,
+                    ));
+                }
+                StudySessionEvent::AnswerAccepted {
+                    answer,
+                    score_delta,
+                    ..
+                } => {
+                    let card = card.ok_or(StudySessionError::InvalidAction)?;
+                    let rejected_delta = session
+                        .results
+                        .last()
+                        .map_or(0, |result| result.score_delta);
+                    let previous_leech = session.leech_before_answer.unwrap_or(card.leech);
+                    let response_time_millis = session
+                        .results
+                        .last()
+                        .and_then(|result| result.response_time_millis);
+                    apply(&mut session, event);
+                    let leech = self
+                        .leech_after_answer(&session, previous_leech, true)
+                        .await?
+                        .map(|(leech, status)| {
+                            // The rejected answer may have just suspended the card as a new leech.
+                            if card.status == CardStatus::Suspended && !previous_leech.is_leech {
+                                (leech, Some(CardStatus::Active))
+                            } else {
+                                (leech, status)
+                            }
+                        });
+                    card_progress.extend(self.card_progress(
+                        &session,
+                        &card.id,
+                        score_delta - rejected_delta,
+                        correct_grade(&session, response_time_millis),
+                        leech,
+                    ));
+                    review_log.push(self.review_log_entry(
+                        &session,
+                        card,
+                        Some(answer.clone()),
+                        Some(answer.clone()),
+                        ReviewOutcome::Accepted,
+                        *score_delta,
+                        response_time_millis,
+                    ));
+                }
+                StudySessionEvent::FeedbackDismissed { next_test_card }
+                | StudySessionEvent::CardsRemoved { next_test_card, .. } => {
+                    selected_test_card = next_test_card.clone();
+                    apply(&mut session, event);
+                }
+                _ => apply(&mut session, event),
+            }
+        }
         self.sessions
             .commit_transition(StudySessionCommit {
                 session,
                 expected_version,
-                card_progress: progress,
+                card_progress,
                 selected_test_card,
+                review_log,
+                accepted_translation,
+                undoable: undoable(&events),
+                events,
             })
             .await
             .map_err(Self::map_session_error)
     }
 
-    async fn submit_answer(
+    #[allow(clippy::too_many_arguments)]
+    fn review_log_entry(
         &self,
-        mut session: StudySession,
-        expected_version: u64,
-        answer: String,
-    ) -> Result<StudySessionTransition, StudySessionError> {
-        if session.phase != StudySessionPhase::Test
-            || session.awaiting_continue
-            || answer.trim().is_empty()
-        {
-            return Err(StudySessionError::InvalidAction);
+        session: &StudySession,
+        card: &Card,
+        submitted_answer: Option<String>,
+        matched_translation: Option<String>,
+        outcome: ReviewOutcome,
+        score_delta: i32,
+        response_time_millis: Option<u64>,
+    ) -> ReviewLogEntry {
+        ReviewLogEntry {
+            session_id: session.id.clone(),
+            card_id: card.id.clone(),
+            mode: session.mode,
+            direction: card.direction,
+            submitted_answer,
+            matched_translation,
+            outcome,
+            is_correct: matches!(outcome, ReviewOutcome::Correct | ReviewOutcome::Accepted),
+            score_delta,
+            pronunciation_strict_score: session
+                .pronunciation_attempts
+                .last()
+                .map(|report| report.strict_score),
+            response_time_millis,
+            reviewed_at: self.clock.now_millis(),
         }
-        let card = self
-            .load_current_card(&session)
-            .await?
-            .ok_or(StudySessionError::InvalidAction)?;
-        if card.direction == CardDirection::Straight
-            && session.pronunciation_check_enabled
-            && !session.pronunciation_passed
-        {
-            return Err(StudySessionError::InvalidAction);
+    }
+
+    /// Only a failed pronunciation check grades a card as incorrect without an answer.
+    fn review_outcome(graded: &GradedAnswer) -> ReviewOutcome {
+        match graded.outcome {
+            AnswerOutcome::Correct => ReviewOutcome::Correct,
+            AnswerOutcome::GaveUp => ReviewOutcome::GaveUp,
+            _ if graded.answer.is_none() => ReviewOutcome::PronunciationFailed,
+            _ => ReviewOutcome::Incorrect,
         }
-        let best_match =
-            Self::best_matching_translation(&card, &session.completed_meaning_indices, &answer);
+    }
+
+    /// The languages of the card word and of its translations: the word of a straight card is
+    /// in the target language of the profile and the word of a reverse card in its source
+    /// language.
+    async fn card_languages(
+        &self,
+        session: &StudySession,
+        card: &Card,
+    ) -> Result<(String, String), StudySessionError> {
+        let profile = self
+            .profiles
+            .find(&session.owner_id, &session.profile_id)
+            .await
+            .map_err(Self::map_profile_error)?
+            .ok_or(StudySessionError::NotFound)?;
+        Ok(match card.direction {
+            CardDirection::Straight => (profile.target_language, profile.source_language),
+            CardDirection::Reverse => (profile.source_language, profile.target_language),
+        })
+    }
 
-        session.provided_answers.push(answer);
-        let matched_index = best_match.as_ref().map(|(index, _)| *index);
-        let matched = best_match.map(|(_, expected)| expected);
-        let is_correct = matched_index.is_some();
-        if let Some(index) = matched_index {
-            session.completed_meaning_indices.push(index);
+    /// Loads the current test card and whatever else the reducer needs to know about it to
+    /// take the action.
+    async fn transition_context(
+        &self,
+        session: &StudySession,
+        action: &StudySessionAction,
+    ) -> Result<TransitionContext, StudySessionError> {
+        let mut context = TransitionContext {
+            now: self.clock.now_millis(),
+            ..TransitionContext::default()
+        };
+        if session.mode == StudySessionMode::Test
+            && session.awaiting_continue
+            && *action == StudySessionAction::ContinueAfterFeedback
+            && !test_limit_reached(session, context.now)
+        {
+            context.next_test_card = self.choose_test_card(session).await?.map(|card| card.id);
         }
-        let completed =
-            !is_correct || session.completed_meaning_indices.len() == card.meanings.len();
-        let score_delta = if completed && session.mode == StudySessionMode::Test {
-            if is_correct { 1 } else { -2 }
-        } else {
-            0
+        if session.phase != StudySessionPhase::Test
+            || !matches!(
+                action,
+                StudySessionAction::SubmitWrittenAnswer { .. }
+                    | StudySessionAction::SelectAnswerOption { .. }
+                    | StudySessionAction::GiveUp
+                    | StudySessionAction::RevealHint
+                    | StudySessionAction::AcceptLastAnswer { .. }
+                    | StudySessionAction::RegisterPronunciationCaptureFailure { .. }
+            )
+        {
+            return Ok(context);
+        }
+        let Some(card) = self.load_current_card(session).await? else {
+            return Ok(context);
         };
-        let mut progress = Vec::new();
-        if completed {
-            session.awaiting_continue = true;
-            session.current_set_failed |= !is_correct;
-            session.results.push(SessionAnswerResult {
-                card_id: card.id.clone(),
-                word: card.word.text.clone(),
-                is_correct,
-                submitted_answers: session.provided_answers.clone(),
-                pronunciation_reports: session.pronunciation_attempts.clone(),
-                score_delta,
-            });
-            if score_delta != 0 {
-                progress.push(CardProgressUpdate {
-                    card_id: card.id.clone(),
-                    score_delta,
-                });
+        match action {
+            StudySessionAction::SubmitWrittenAnswer { .. }
+            | StudySessionAction::AcceptLastAnswer { .. } => {
+                (context.card_language, context.translation_language) =
+                    self.card_languages(session, &card).await?;
+            }
+            StudySessionAction::SelectAnswerOption { .. } => {
+                context.options = self.answer_options(session, &card).await?;
             }
+            _ => {}
         }
-        let remaining_meanings = card
-            .meanings
-            .len()
-            .saturating_sub(session.completed_meaning_indices.len());
-        let session = self
-            .commit(session, expected_version, progress, None)
-            .await?;
-        let view = self.view(&session).await?;
-        Ok(StudySessionTransition {
-            session: view,
-            answer_feedback: Some(AnswerFeedback {
-                is_correct,
-                matched_answer: matched,
-                card,
-                matched_meaning_index: matched_index,
-                completed_meaning_indices: session.completed_meaning_indices.clone(),
-                card_completed: completed,
-                remaining_meanings,
-                score_delta,
-            }),
-            pronunciation_feedback: None,
-            set_outcome: None,
-        })
+        if matches!(
+            action,
+            StudySessionAction::SubmitWrittenAnswer { .. }
+                | StudySessionAction::SelectAnswerOption { .. }
+                | StudySessionAction::GiveUp
+        ) {
+            context.schedule = self.schedule_before_answer(session, &card.id).await?;
+        }
+        context.card = Some(card);
+        Ok(context)
     }
 
-    async fn continue_after_feedback(
+    async fn take_action(
         &self,
-        mut session: StudySession,
+        session: StudySession,
         expected_version: u64,
+        action: StudySessionAction,
+        context: TransitionContext,
     ) -> Result<StudySessionTransition, StudySessionError> {
-        if !session.awaiting_continue {
-            return Err(StudySessionError::InvalidAction);
-        }
-        session.awaiting_continue = false;
-        session.provided_answers.clear();
-        session.completed_meaning_indices.clear();
-        session.pronunciation_attempts.clear();
-        session.pronunciation_passed = false;
-        session.pronunciation_disable_required = false;
-        let mut selected = None;
-        let mut set_outcome = None;
-
-        match session.mode {
-            StudySessionMode::Test => {
-                if let Some(card) = self.choose_test_card(&session).await? {
-                    selected = Some(card.id.clone());
-                    session.card_ids = vec![card.id];
-                } else {
-                    session.card_ids.clear();
-                    session.status = StudySessionStatus::Completed;
-                }
-            }
-            StudySessionMode::Learning => {
-                session.current_card_index += 1;
-                if session.current_card_index >= session.test_order.len() {
-                    if session.current_set_failed {
-                        set_outcome = Some(SetOutcome::Retry);
-                        session.phase = StudySessionPhase::Study;
-                        session.current_card_index = 0;
-                        session.current_set_failed = false;
-                        session.test_order.clear();
-                    } else {
-                        set_outcome = Some(SetOutcome::Passed);
-                        session.current_set_index += 1;
-                        session.current_card_index = 0;
-                        session.test_order.clear();
-                        if session.current_set_index * session.cards_per_set
-                            >= session.card_ids.len()
-                        {
-                            session.status = StudySessionStatus::Completed;
-                        } else {
-                            session.phase = StudySessionPhase::Study;
-                        }
-                    }
-                }
-            }
+        let events = reduce(&session, &action, &context)?;
+        let mut card = context.card;
+        let mut accepted_translation = None;
+        if let StudySessionAction::AcceptLastAnswer {
+            add_to_translations: true,
+        } = action
+            && let Some(StudySessionEvent::AnswerAccepted {
+                answer,
+                meaning_index: Some(index),
+                ..
+            }) = events.first()
+            && let Some(accepted_card) = card.as_mut()
+            && let Some(meaning) = accepted_card.meanings.get_mut(*index)
+            && !meaning.word_translations.contains(answer)
+        {
+            meaning.word_translations.push(answer.clone());
+            accepted_translation = Some(AcceptedTranslation {
+                card_id: accepted_card.id.clone(),
+                meaning_index: *index,
+                translation: answer.clone(),
+            });
         }
         let session = self
-            .commit(session, expected_version, Vec::new(), selected)
+            .commit(
+                session,
+                expected_version,
+                card.as_ref(),
+                events.clone(),
+                accepted_translation,
+            )
             .await?;
+        let pronunciation_feedback = match action {
+            StudySessionAction::RegisterPronunciationCaptureFailure { message } => {
+                let kind = if session.pronunciation_disable_required {
+                    PronunciationFeedbackKind::DisableRequired
+                } else {
+                    PronunciationFeedbackKind::TechnicalError
+                };
+                Some(Self::pronunciation_feedback(
+                    &session,
+                    kind,
+                    None,
+                    Some(message.chars().take(200).collect()),
+                ))
+            }
+            _ => None,
+        };
         Ok(StudySessionTransition {
             session: self.view(&session).await?,
-            answer_feedback: None,
-            pronunciation_feedback: None,
-            set_outcome,
+            answer_feedback: Self::answer_feedback(&session, card, &events),
+            pronunciation_feedback,
+            set_outcome: events.iter().find_map(|event| match event {
+                StudySessionEvent::SetPassed => Some(SetOutcome::Passed),
+                StudySessionEvent::SetRetried | StudySessionEvent::MissedCardsRetried { .. } => {
+                    Some(SetOutcome::Retry)
+                }
+                _ => None,
+            }),
+        })
+    }
+
+    fn answer_feedback(
+        session: &StudySession,
+        card: Option<Card>,
+        events: &[StudySessionEvent],
+    ) -> Option<AnswerFeedback> {
+        let card = card?;
+        events.iter().find_map(|event| match event {
+            StudySessionEvent::AnswerGraded(graded) => Some(AnswerFeedback {
+                outcome: graded.outcome,
+                is_correct: graded.outcome == AnswerOutcome::Correct,
+                matched_answer: graded.matched_answer.clone(),
+                similarity_percent: graded.similarity_percent,
+                card: card.clone(),
+                matched_meaning_index: graded.meaning_index,
+                completed_meaning_indices: session.completed_meaning_indices.clone(),
+                card_completed: graded.card_completed,
+                remaining_meanings: graded.remaining_meanings,
+                score_delta: graded.score_delta,
+                misplaced_stress: graded.misplaced_stress,
+                normalizations: graded.normalizations.clone(),
+                response_time_millis: graded.response_time_millis,
+            }),
+            StudySessionEvent::AnswerAccepted {
+                answer,
+                meaning_index,
+                score_delta,
+            } => Some(AnswerFeedback {
+                outcome: AnswerOutcome::Correct,
+                is_correct: true,
+                matched_answer: Some(answer.clone()),
+                similarity_percent: 100,
+                card: card.clone(),
+                matched_meaning_index: *meaning_index,
+                completed_meaning_indices: session.completed_meaning_indices.clone(),
+                card_completed: true,
+                remaining_meanings: 0,
+                score_delta: *score_delta,
+                misplaced_stress: false,
+                normalizations: Vec::new(),
+                response_time_millis: session
+                    .results
+                    .last()
+                    .and_then(|result| result.response_time_millis),
+            }),
+            _ => None,
         })
     }
 
@@ -524,75 +1103,17 @@ impl StudySessionService {
         }
     }
 
-    async fn record_pronunciation_technical_failure(
-        &self,
-        mut session: StudySession,
-        expected_version: u64,
-        message: String,
-    ) -> Result<StudySessionTransition, StudySessionError> {
-        if session.phase != StudySessionPhase::Test
-            || !session.pronunciation_check_enabled
-            || session.pronunciation_passed
-            || session.awaiting_continue
-        {
-            return Err(StudySessionError::InvalidAction);
-        }
-        if self
-            .load_current_card(&session)
-            .await?
-            .is_none_or(|card| card.direction != CardDirection::Straight)
-        {
-            return Err(StudySessionError::InvalidAction);
-        }
-        session.pronunciation_technical_failures = session
-            .pronunciation_technical_failures
-            .saturating_add(1)
-            .min(2);
-        session.pronunciation_disable_required = session.pronunciation_technical_failures >= 2;
-        let kind = if session.pronunciation_disable_required {
-            PronunciationFeedbackKind::DisableRequired
-        } else {
-            PronunciationFeedbackKind::TechnicalError
-        };
-        let message = message.chars().take(200).collect::<String>();
-        let session = self
-            .commit(session, expected_version, Vec::new(), None)
-            .await?;
-        Ok(StudySessionTransition {
-            pronunciation_feedback: Some(Self::pronunciation_feedback(
-                &session,
-                kind,
-                None,
-                Some(message),
-            )),
-            session: self.view(&session).await?,
-            answer_feedback: None,
-            set_outcome: None,
-        })
-    }
-
     async fn assess_recording(
         &self,
-        mut session: StudySession,
+        session: StudySession,
         expected_version: u64,
         audio: Vec<u8>,
     ) -> Result<StudySessionTransition, StudySessionError> {
-        if session.phase != StudySessionPhase::Test
-            || !session.pronunciation_check_enabled
-            || session.pronunciation_passed
-            || session.pronunciation_disable_required
-            || session.awaiting_continue
-            || session.pronunciation_attempts.len() >= 2
-        {
-            return Err(StudySessionError::InvalidAction);
-        }
         let card = self
             .load_current_card(&session)
             .await?
+            .filter(|card| accepts_recording(&session, card))
             .ok_or(StudySessionError::InvalidAction)?;
-        if card.direction != CardDirection::Straight {
-            return Err(StudySessionError::InvalidAction);
-        }
         let profile = self
             .profiles
             .find(&session.owner_id, &session.profile_id)
@@ -648,47 +1169,46 @@ impl StudySessionService {
                     PronunciationAssessmentError::Provider(message) => message,
                 };
                 return self
-                    .record_pronunciation_technical_failure(session, expected_version, message)
+                    .take_action(
+                        session,
+                        expected_version,
+                        StudySessionAction::RegisterPronunciationCaptureFailure { message },
+                        TransitionContext {
+                            card: Some(card),
+                            now: self.clock.now_millis(),
+                            ..TransitionContext::default()
+                        },
+                    )
                     .await;
             }
         };
-        session.pronunciation_technical_failures = 0;
-        session.pronunciation_disable_required = false;
         let report = score_pronunciation(&locale, session.pronunciation_score_threshold, report);
-        session.pronunciation_attempts.push(report.clone());
-        let mut progress = Vec::new();
+        let context = TransitionContext {
+            schedule: self.schedule_before_answer(&session, &card.id).await?,
+            card: Some(card),
+            now: self.clock.now_millis(),
+            ..TransitionContext::default()
+        };
+        let events = assess(&session, &context, report.clone())?;
+        let failed = events
+            .iter()
+            .any(|event| matches!(event, StudySessionEvent::AnswerGraded(_)));
+        let session = self
+            .commit(
+                session,
+                expected_version,
+                context.card.as_ref(),
+                events,
+                None,
+            )
+            .await?;
         let kind = if report.passed {
-            session.pronunciation_passed = true;
             PronunciationFeedbackKind::Passed
-        } else if session.pronunciation_attempts.len() < 2 {
-            PronunciationFeedbackKind::Retry
-        } else {
-            let score_delta = if session.mode == StudySessionMode::Test {
-                -2
-            } else {
-                0
-            };
-            session.awaiting_continue = true;
-            session.current_set_failed = true;
-            session.results.push(SessionAnswerResult {
-                card_id: card.id.clone(),
-                word: card.word.text,
-                is_correct: false,
-                submitted_answers: Vec::new(),
-                pronunciation_reports: session.pronunciation_attempts.clone(),
-                score_delta,
-            });
-            if score_delta != 0 {
-                progress.push(CardProgressUpdate {
-                    card_id: card.id,
-                    score_delta,
-                });
-            }
+        } else if failed {
             PronunciationFeedbackKind::Failed
+        } else {
+            PronunciationFeedbackKind::Retry
         };
-        let session = self
-            .commit(session, expected_version, progress, None)
-            .await?;
         Ok(StudySessionTransition {
             pronunciation_feedback: Some(Self::pronunciation_feedback(
                 &session,
@@ -753,7 +1273,11 @@ impl StudySessionUsecase for StudySessionService {
     ) -> Result<StudySessionView, StudySessionError> {
         Self::validate(&command)?;
         let pronunciation_check_enabled = command.pronunciation_check_enabled
-            && command.direction != Some(CardDirection::Reverse);
+            && command.direction != Some(CardDirection::Reverse)
+            && !matches!(
+                command.answer_format,
+                AnswerFormat::Dictation | AnswerFormat::Cloze | AnswerFormat::Reading
+            );
         if pronunciation_check_enabled
             && !self
                 .pronunciation_settings
@@ -764,6 +1288,12 @@ impl StudySessionUsecase for StudySessionService {
         {
             return Err(StudySessionError::PronunciationNotConfigured);
         }
+        let new_cards_per_day = command.new_cards_per_day;
+        let utc_offset_minutes = command.utc_offset_minutes;
+        let defaults = StudySessionPreferences::defaults(command.profile_id.clone(), command.mode);
+        let card_order = command.card_order.or(defaults.card_order);
+        let recent_ban_window = command.recent_ban_window.or(defaults.recent_ban_window);
+        let retry_missed_only = command.retry_missed_only.or(defaults.retry_missed_only);
         let mut session = StudySession {
             id: crate::ports::input::study_session::models::SessionId::new(
                 Uuid::new_v4().to_string(),
@@ -782,12 +1312,24 @@ impl StudySessionUsecase for StudySessionService {
                 min_score: command.min_score,
                 max_score: command.max_score,
             },
+            answer_format: command.answer_format,
+            answer_strictness: command.answer_strictness,
             pronunciation_check_enabled,
             pronunciation_score_threshold: command.pronunciation_score_threshold,
             cards_per_set: command.cards_per_set.unwrap_or(1),
+            retry_missed_only: retry_missed_only.unwrap_or_default(),
+            card_order: card_order.unwrap_or(CardOrder::Random),
+            recent_ban_window: recent_ban_window.unwrap_or_default(),
+            max_cards: command.max_cards,
+            ends_at: command
+                .time_limit_minutes
+                .map(|minutes| self.clock.now_millis() + i64::from(minutes) * 60_000),
+            correct_streak_goal: command.correct_streak_goal,
             card_ids: Vec::new(),
             test_order: Vec::new(),
+            seed: self.random.next_seed(),
             current_set_index: 0,
+            retry_card_ids: Vec::new(),
             current_card_index: 0,
             provided_answers: Vec::new(),
             completed_meaning_indices: Vec::new(),
@@ -796,18 +1338,18 @@ impl StudySessionUsecase for StudySessionService {
             pronunciation_technical_failures: 0,
             pronunciation_disable_required: false,
             awaiting_continue: false,
+            retype_pending: false,
+            schedule_before_answer: None,
+            leech_before_answer: None,
+            hints_used: Vec::new(),
+            card_presented_at: None,
             current_set_failed: false,
             results: Vec::new(),
             version: 0,
         };
         let selected = match session.mode {
             StudySessionMode::Learning => {
-                session.card_ids = self
-                    .matching_cards(&session)
-                    .await?
-                    .into_iter()
-                    .map(|card| card.id)
-                    .collect();
+                session.card_ids = self.matching_cards(&session).await?;
                 None
             }
             StudySessionMode::Test => {
@@ -818,10 +1360,24 @@ impl StudySessionUsecase for StudySessionService {
                 session.card_ids = vec![card.id.clone()];
                 Some(card.id)
             }
+            StudySessionMode::Review => {
+                session.card_ids = self
+                    .review_queue(
+                        &session,
+                        new_cards_per_day.unwrap_or_default(),
+                        utc_offset_minutes,
+                    )
+                    .await?;
+                None
+            }
         };
         if session.card_ids.is_empty() {
             return Err(StudySessionError::NoCardsAvailable);
         }
+        self.prefetch_audio(&session, &session.card_ids).await?;
+        if session.phase == StudySessionPhase::Test {
+            session.card_presented_at = Some(self.clock.now_millis());
+        }
         let session = self
             .sessions
             .insert(StoreSessionRequest {
@@ -833,6 +1389,15 @@ impl StudySessionUsecase for StudySessionService {
                     max_score: session.filter.max_score,
                     cards_per_set: (session.mode == StudySessionMode::Learning)
                         .then_some(session.cards_per_set),
+                    retry_missed_only,
+                    new_cards_per_day,
+                    card_order,
+                    recent_ban_window,
+                    max_cards: session.max_cards,
+                    time_limit_minutes: command.time_limit_minutes,
+                    correct_streak_goal: session.correct_streak_goal,
+                    answer_format: session.answer_format,
+                    answer_strictness: session.answer_strictness,
                     pronunciation_check_enabled: session.pronunciation_check_enabled,
                     pronunciation_score_threshold: session.pronunciation_score_threshold,
                 },
@@ -844,6 +1409,51 @@ impl StudySessionUsecase for StudySessionService {
         self.view(&session).await
     }
 
+    async fn list_active_sessions(
+        &self,
+        query: ListActiveStudySessionsQuery,
+    ) -> Result<Vec<StudySessionView>, StudySessionError> {
+        if self
+            .profiles
+            .find(&query.user_id, &query.profile_id)
+            .await
+            .map_err(Self::map_profile_error)?
+            .is_none()
+        {
+            return Err(StudySessionError::NotFound);
+        }
+        let sessions = self
+            .sessions
+            .list_active(&query.user_id, &query.profile_id)
+            .await
+            .map_err(Self::map_session_error)?;
+        let mut views = Vec::with_capacity(sessions.len());
+        for session in sessions {
+            let session = self.restore(session).await?;
+            if session.status == StudySessionStatus::Active {
+                views.push(self.view(&session).await?);
+            }
+        }
+        Ok(views)
+    }
+
+    async fn resume_session(
+        &self,
+        query: ResumeStudySessionQuery,
+    ) -> Result<StudySessionView, StudySessionError> {
+        let session = self
+            .sessions
+            .find(&query.user_id, &query.session_id)
+            .await
+            .map_err(Self::map_session_error)?
+            .ok_or(StudySessionError::NotFound)?;
+        if session.status != StudySessionStatus::Active {
+            return Err(StudySessionError::InvalidAction);
+        }
+        let session = self.restore(session).await?;
+        self.view(&session).await
+    }
+
     async fn apply_action(
         &self,
         command: ApplyStudySessionActionCommand,
@@ -863,88 +1473,28 @@ impl StudySessionUsecase for StudySessionService {
                 StudySessionError::InvalidAction
             });
         }
-        match command.action {
-            StudySessionAction::SubmitWrittenAnswer { answer } => {
-                self.submit_answer(session, command.expected_version, answer)
-                    .await
-            }
-            StudySessionAction::ContinueAfterFeedback => {
-                self.continue_after_feedback(session, command.expected_version)
-                    .await
-            }
-            StudySessionAction::RegisterPronunciationCaptureFailure { message } => {
-                self.record_pronunciation_technical_failure(
-                    session,
-                    command.expected_version,
-                    message,
-                )
-                .await
-            }
-            StudySessionAction::DisablePronunciation => {
-                if !session.pronunciation_disable_required {
-                    return Err(StudySessionError::InvalidAction);
-                }
-                let mut session = session;
-                session.pronunciation_check_enabled = false;
-                session.pronunciation_disable_required = false;
-                let session = self
-                    .commit(session, command.expected_version, Vec::new(), None)
-                    .await?;
-                Ok(StudySessionTransition {
-                    session: self.view(&session).await?,
-                    answer_feedback: None,
-                    pronunciation_feedback: None,
-                    set_outcome: None,
-                })
-            }
-            StudySessionAction::PreviousStudyCard
-            | StudySessionAction::NextStudyCard
-            | StudySessionAction::StartMiniTest => {
-                if session.mode != StudySessionMode::Learning
-                    || session.phase != StudySessionPhase::Study
-                {
-                    return Err(StudySessionError::InvalidAction);
-                }
-                let mut session = session;
-                match command.action {
-                    StudySessionAction::PreviousStudyCard => {
-                        session.current_card_index = session.current_card_index.saturating_sub(1);
-                    }
-                    StudySessionAction::NextStudyCard => {
-                        let set_start = session.current_set_index * session.cards_per_set;
-                        let set_len = session
-                            .cards_per_set
-                            .min(session.card_ids.len().saturating_sub(set_start));
-                        session.current_card_index =
-                            (session.current_card_index + 1).min(set_len.saturating_sub(1));
-                    }
-                    StudySessionAction::StartMiniTest => {
-                        let start = session.current_set_index * session.cards_per_set;
-                        let end = (start + session.cards_per_set).min(session.card_ids.len());
-                        session.test_order = session.card_ids[start..end].to_vec();
-                        Self::shuffle(&mut session.test_order, session.version);
-                        session.phase = StudySessionPhase::Test;
-                        session.current_card_index = 0;
-                        session.current_set_failed = false;
-                        session.provided_answers.clear();
-                        session.completed_meaning_indices.clear();
-                        session.pronunciation_attempts.clear();
-                        session.pronunciation_passed = false;
-                        session.pronunciation_disable_required = false;
-                    }
-                    _ => unreachable!(),
-                }
-                let session = self
-                    .commit(session, command.expected_version, Vec::new(), None)
-                    .await?;
-                Ok(StudySessionTransition {
-                    session: self.view(&session).await?,
-                    answer_feedback: None,
-                    pronunciation_feedback: None,
-                    set_outcome: None,
+        if command.action == StudySessionAction::UndoLastAction {
+            let session = self
+                .sessions
+                .undo_transition(UndoTransitionRequest {
+                    user_id: command.user_id,
+                    session_id: command.session_id,
+                    expected_version: command.expected_version,
                 })
-            }
+                .await
+                .map_err(Self::map_session_error)?
+                .ok_or(StudySessionError::InvalidAction)?;
+            let session = self.restore(session).await?;
+            return Ok(StudySessionTransition {
+                session: self.view(&session).await?,
+                answer_feedback: None,
+                pronunciation_feedback: None,
+                set_outcome: None,
+            });
         }
+        let context = self.transition_context(&session, &command.action).await?;
+        self.take_action(session, command.expected_version, command.action, context)
+            .await
     }
 
     async fn assess_pronunciation(
@@ -983,75 +1533,12 @@ impl StudySessionUsecase for StudySessionService {
     ) -> Result<StudySessionView, StudySessionError> {
         self.end(command, StudySessionStatus::Cancelled).await
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::StudySessionService;
-    use crate::ports::input::{
-        card_catalog::models::{Card, CardDirection, CardId, Meaning, Word},
-        language_profile::models::ProfileId,
-    };
-
-    fn card_with_translations(translations: &[&[&str]]) -> Card {
-        Card {
-            id: CardId::new("card"),
-            profile_id: ProfileId::new("profile"),
-            direction: CardDirection::Straight,
-            word: Word {
-                text: "word".to_string(),
-                readings: Vec::new(),
-            },
-            meanings: translations
-                .iter()
-                .map(|translations| Meaning {
-                    definition: "definition".to_string(),
-                    translated_definition: "translated definition".to_string(),
-                    word_translations: translations
-                        .iter()
-                        .map(|translation| (*translation).to_string())
-                        .collect(),
-                    examples: Vec::new(),
-                })
-                .collect(),
-            score: 0,
-            created_at: 0,
-            version: 0,
-        }
-    }
-
-    #[test]
-    fn written_answers_use_unicode_aware_similarity() {
-        assert_eq!(
-            StudySessionService::similarity_score("Привет", " привет "),
-            1.0
-        );
-        assert!(StudySessionService::similarity_score("hello", "helo") >= 0.8);
-        assert!(StudySessionService::similarity_score("hello", "goodbye") < 0.8);
-        assert!(StudySessionService::similarity_score("猫", "犬") < 0.8);
-    }
-
-    #[test]
-    fn written_answer_uses_the_best_match_across_all_meanings() {
-        let card = card_with_translations(&[&["hello"], &["helo"]]);
-
-        assert_eq!(
-            StudySessionService::best_matching_translation(&card, &[], "helo"),
-            Some((1, "helo".to_string()))
-        );
-    }
 
-    #[test]
-    fn equal_matches_prefer_the_first_uncompleted_meaning() {
-        let card = card_with_translations(&[&["same"], &["same"]]);
-
-        assert_eq!(
-            StudySessionService::best_matching_translation(&card, &[], "same"),
-            Some((0, "same".to_string()))
-        );
-        assert_eq!(
-            StudySessionService::best_matching_translation(&card, &[0], "same"),
-            Some((1, "same".to_string()))
-        );
+    async fn expire_idle_sessions(&self) -> Result<usize, StudySessionError> {
+        let idle_millis = i64::try_from(self.session_idle_expiry.as_millis()).unwrap_or(i64::MAX);
+        self.sessions
+            .expire_idle(self.clock.now_millis().saturating_sub(idle_millis))
+            .await
+            .map_err(Self::map_session_error)
     }
 }
@@ -0,0 +1,1990 @@
+use super::{
+    answer_normalization::{answer_normalizations, normalize},
+    cloze::{ClozeExample, cloze_examples},
+    reading_comparison::{ReadingMatch, compare_reading},
+};
+use crate::ports::input::{
+    card_catalog::models::{Card, CardDirection, CardId, CardSchedule, Meaning, ReviewGrade},
+    study_session::models::{
+        AnswerFormat, AnswerNormalization, AnswerOutcome, AnswerStrictness, GradedAnswer,
+        PronunciationAssessmentReport, RecordedTransition, SessionAnswerResult, SessionHint,
+        StudySession, StudySessionAction, StudySessionError, StudySessionEvent, StudySessionMode,
+        StudySessionPhase, StudySessionStatus,
+    },
+};
+
+const HINTS: [SessionHint; 4] = [
+    SessionHint::FirstLetter,
+    SessionHint::WordLength,
+    SessionHint::TranslatedDefinition,
+    SessionHint::ExampleTranslation,
+];
+/// Correct answers that take longer than this are recalled with effort; picking an option is
+/// quicker than typing.
+const SLOW_CHOICE_MILLIS: u64 = 8_000;
+const SLOW_TYPED_MILLIS: u64 = 15_000;
+
+/// The expected answer closest to a typed or selected one.
+#[derive(Debug, Clone, PartialEq)]
+struct AnswerMatch {
+    /// `None` when the match answers the whole card at once.
+    meaning_index: Option<usize>,
+    expected: String,
+    similarity: f64,
+    normalizations: Vec<AnswerNormalization>,
+}
+
+/// What an action needs to know beyond the session itself, loaded before it is reduced.
+#[derive(Debug, Clone, Default)]
+pub struct TransitionContext {
+    /// The current test card; `None` in the study phase.
+    pub card: Option<Card>,
+    pub card_language: String,
+    pub translation_language: String,
+    /// The options offered by the current multiple-choice card.
+    pub options: Vec<String>,
+    /// The stored schedule of the current card; `None` in learn sessions and for cards that
+    /// were never reviewed.
+    pub schedule: Option<CardSchedule>,
+    /// The card picked to follow the current one in a test session.
+    pub next_test_card: Option<CardId>,
+    /// When the action is taken, in milliseconds since the Unix epoch.
+    pub now: i64,
+}
+
+/// Checks an action against the session and returns the events it records without changing
+/// anything. Undo is left to the repository, which keeps the states transitions replaced.
+pub fn reduce(
+    session: &StudySession,
+    action: &StudySessionAction,
+    context: &TransitionContext,
+) -> Result<Vec<StudySessionEvent>, StudySessionError> {
+    if session.status != StudySessionStatus::Active {
+        return Err(StudySessionError::InvalidAction);
+    }
+    let event = match action {
+        StudySessionAction::PreviousStudyCard
+        | StudySessionAction::NextStudyCard
+        | StudySessionAction::StartMiniTest => {
+            if session.mode != StudySessionMode::Learning
+                || session.phase != StudySessionPhase::Study
+            {
+                return Err(StudySessionError::InvalidAction);
+            }
+            let set_cards = set_card_ids(session);
+            match action {
+                StudySessionAction::PreviousStudyCard => StudySessionEvent::StudyCardShown {
+                    index: session.current_card_index.saturating_sub(1),
+                },
+                StudySessionAction::NextStudyCard => StudySessionEvent::StudyCardShown {
+                    index: (session.current_card_index + 1).min(set_cards.len().saturating_sub(1)),
+                },
+                _ => {
+                    let mut test_order = set_cards.to_vec();
+                    shuffle(&mut test_order, session.seed, session.version);
+                    StudySessionEvent::MiniTestStarted { test_order }
+                }
+            }
+        }
+        StudySessionAction::SubmitWrittenAnswer { answer } => {
+            if answer.trim().is_empty() {
+                return Err(StudySessionError::InvalidAction);
+            }
+            written_answer(session, answerable_card(session, context)?, context, answer)
+        }
+        StudySessionAction::SelectAnswerOption { index } => {
+            let card = answerable_card(session, context)?;
+            let option = context
+                .options
+                .get(*index)
+                .ok_or(StudySessionError::InvalidAction)?;
+            let answer_match = card
+                .meanings
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !session.completed_meaning_indices.contains(index))
+                .find(|(_, meaning)| meaning.word_translations.contains(option))
+                .map(|(index, _)| AnswerMatch {
+                    meaning_index: Some(index),
+                    expected: option.clone(),
+                    similarity: 1.0,
+                    normalizations: Vec::new(),
+                });
+            let outcome = if answer_match.is_some() {
+                AnswerOutcome::Correct
+            } else {
+                AnswerOutcome::Incorrect
+            };
+            graded_answer(
+                session,
+                card,
+                context,
+                Some(option.clone()),
+                outcome,
+                answer_match,
+                false,
+            )
+        }
+        StudySessionAction::GiveUp => graded_answer(
+            session,
+            answerable_card(session, context)?,
+            context,
+            None,
+            AnswerOutcome::GaveUp,
+            None,
+            false,
+        ),
+        StudySessionAction::RevealHint => StudySessionEvent::HintRevealed {
+            hint: next_hint(session, answerable_card(session, context)?)
+                .ok_or(StudySessionError::InvalidAction)?,
+        },
+        StudySessionAction::AcceptLastAnswer {
+            add_to_translations,
+        } => {
+            if !session.awaiting_continue
+                || session.answer_format != AnswerFormat::Written
+                || session
+                    .results
+                    .last()
+                    .is_none_or(|result| result.outcome != AnswerOutcome::Incorrect)
+            {
+                return Err(StudySessionError::InvalidAction);
+            }
+            let answer = session
+                .provided_answers
+                .last()
+                .map(|answer| answer.trim().to_string())
+                .ok_or(StudySessionError::InvalidAction)?;
+            let card = context
+                .card
+                .as_ref()
+                .ok_or(StudySessionError::InvalidAction)?;
+            let (accepted, _) = similarity_thresholds(session.answer_strictness);
+            let meaning_index = best_matching_translation(
+                card,
+                &session.completed_meaning_indices,
+                &context.translation_language,
+                accepted,
+                &answer,
+            )
+            .and_then(|answer_match| answer_match.meaning_index);
+            if *add_to_translations && meaning_index.is_none() {
+                return Err(StudySessionError::InvalidAction);
+            }
+            StudySessionEvent::AnswerAccepted {
+                answer,
+                meaning_index,
+                score_delta: correct_score_delta(session),
+            }
+        }
+        StudySessionAction::ContinueAfterFeedback => {
+            if !session.awaiting_continue {
+                return Err(StudySessionError::InvalidAction);
+            }
+            let limit_reached =
+                session.mode == StudySessionMode::Test && test_limit_reached(session, context.now);
+            let mut events = vec![StudySessionEvent::FeedbackDismissed {
+                next_test_card: context.next_test_card.clone().filter(|_| !limit_reached),
+            }];
+            if session.mode == StudySessionMode::Learning
+                && session.current_card_index + 1 >= session.test_order.len()
+            {
+                events.push(if !session.current_set_failed {
+                    StudySessionEvent::SetPassed
+                } else if session.retry_missed_only {
+                    StudySessionEvent::MissedCardsRetried {
+                        card_ids: missed_card_ids(session),
+                    }
+                } else {
+                    StudySessionEvent::SetRetried
+                });
+            }
+            return Ok(presenting_next_card(session, events, context.now));
+        }
+        StudySessionAction::UndoLastAction => return Err(StudySessionError::InvalidAction),
+        StudySessionAction::RegisterPronunciationCaptureFailure { .. } => {
+            if session.phase != StudySessionPhase::Test
+                || !session.pronunciation_check_enabled
+                || session.pronunciation_passed
+                || session.awaiting_continue
+                || context
+                    .card
+                    .as_ref()
+                    .is_none_or(|card| card.direction != CardDirection::Straight)
+            {
+                return Err(StudySessionError::InvalidAction);
+            }
+            StudySessionEvent::PronunciationCaptureFailed
+        }
+        StudySessionAction::DisablePronunciation => {
+            if !session.pronunciation_disable_required {
+                return Err(StudySessionError::InvalidAction);
+            }
+            StudySessionEvent::PronunciationDisabled
+        }
+    };
+    Ok(presenting_next_card(session, vec![event], context.now))
+}
+
+/// Appends [`StudySessionEvent::CardPresented`] when the events leave a test card waiting for
+/// an answer that has not been timed yet.
+pub fn presenting_next_card(
+    session: &StudySession,
+    mut events: Vec<StudySessionEvent>,
+    now: i64,
+) -> Vec<StudySessionEvent> {
+    let mut next = session.clone();
+    for event in &events {
+        apply(&mut next, event);
+    }
+    if next.phase == StudySessionPhase::Test
+        && !next.awaiting_continue
+        && next.card_presented_at.is_none()
+        && current_card_id(&next).is_some()
+    {
+        events.push(StudySessionEvent::CardPresented { at: now });
+    }
+    events
+}
+
+/// Whether a bounded test session has reached one of its limits, counting the card under
+/// feedback; the session then completes instead of moving to another card.
+pub fn test_limit_reached(session: &StudySession, now: i64) -> bool {
+    session
+        .max_cards
+        .is_some_and(|max_cards| session.results.len() >= max_cards)
+        || session.ends_at.is_some_and(|ends_at| now >= ends_at)
+        || session
+            .correct_streak_goal
+            .is_some_and(|goal| correct_streak(session) >= goal)
+}
+
+/// The number of correct answers in a row at the end of the session results.
+pub fn correct_streak(session: &StudySession) -> usize {
+    session
+        .results
+        .iter()
+        .rev()
+        .take_while(|result| result.is_correct)
+        .count()
+}
+
+/// Whether the current card of the session is waiting for a pronunciation recording.
+pub fn accepts_recording(session: &StudySession, card: &Card) -> bool {
+    session.status == StudySessionStatus::Active
+        && session.phase == StudySessionPhase::Test
+        && session.pronunciation_check_enabled
+        && !session.pronunciation_passed
+        && !session.pronunciation_disable_required
+        && !session.awaiting_continue
+        && session.pronunciation_attempts.len() < 2
+        && card.direction == CardDirection::Straight
+}
+
+/// Records a scored pronunciation attempt; failing the second attempt fails the card.
+pub fn assess(
+    session: &StudySession,
+    context: &TransitionContext,
+    report: PronunciationAssessmentReport,
+) -> Result<Vec<StudySessionEvent>, StudySessionError> {
+    let card = context
+        .card
+        .as_ref()
+        .filter(|card| accepts_recording(session, card))
+        .ok_or(StudySessionError::InvalidAction)?;
+    let failed = !report.passed && session.pronunciation_attempts.len() + 1 >= 2;
+    let mut events = vec![StudySessionEvent::PronunciationAttempted { report }];
+    if failed {
+        events.push(StudySessionEvent::AnswerGraded(Box::new(GradedAnswer {
+            card_id: card.id.clone(),
+            word: card.word.text.clone(),
+            answer: None,
+            outcome: AnswerOutcome::Incorrect,
+            matched_answer: None,
+            similarity_percent: 0,
+            meaning_index: None,
+            card_completed: true,
+            remaining_meanings: card
+                .meanings
+                .len()
+                .saturating_sub(session.completed_meaning_indices.len()),
+            score_delta: failed_score_delta(session),
+            misplaced_stress: false,
+            normalizations: Vec::new(),
+            schedule_before_answer: context.schedule.clone(),
+            leech_before_answer: card.leech,
+            answered_at: context.now,
+            response_time_millis: response_time_millis(session, context.now),
+        })));
+    }
+    Ok(events)
+}
+
+/// Applies one recorded event to the session. `Undone` is left to [`replay`], which keeps the
+/// states that undo returns to.
+pub fn apply(session: &mut StudySession, event: &StudySessionEvent) {
+    match event {
+        StudySessionEvent::Started { session: started } => *session = (**started).clone(),
+        StudySessionEvent::StudyCardShown { index } => session.current_card_index = *index,
+        StudySessionEvent::MiniTestStarted { test_order } => {
+            clear_current_card(session);
+            session.test_order = test_order.clone();
+            session.phase = StudySessionPhase::Test;
+            session.current_card_index = 0;
+            session.current_set_failed = false;
+        }
+        StudySessionEvent::HintRevealed { hint } => session.hints_used.push(*hint),
+        StudySessionEvent::AnswerGraded(graded) => {
+            session.provided_answers.extend(graded.answer.clone());
+            if graded.outcome == AnswerOutcome::AlmostCorrect {
+                session.retype_pending = true;
+                return;
+            }
+            session.retype_pending = false;
+            session
+                .completed_meaning_indices
+                .extend(graded.meaning_index);
+            if graded.card_completed {
+                let is_correct = graded.outcome == AnswerOutcome::Correct;
+                session.awaiting_continue = true;
+                session.current_set_failed |= !is_correct;
+                session.results.push(SessionAnswerResult {
+                    card_id: graded.card_id.clone(),
+                    word: graded.word.clone(),
+                    is_correct,
+                    outcome: graded.outcome,
+                    submitted_answers: session.provided_answers.clone(),
+                    pronunciation_reports: session.pronunciation_attempts.clone(),
+                    score_delta: graded.score_delta,
+                    hints_used: session.hints_used.clone(),
+                    answered_at: Some(graded.answered_at),
+                    response_time_millis: graded.response_time_millis,
+                });
+                session.schedule_before_answer = graded.schedule_before_answer.clone();
+                session.leech_before_answer = Some(graded.leech_before_answer);
+            }
+        }
+        StudySessionEvent::AnswerAccepted {
+            meaning_index,
+            score_delta,
+            ..
+        } => {
+            session.completed_meaning_indices.extend(*meaning_index);
+            if let Some(result) = session.results.last_mut() {
+                result.is_correct = true;
+                result.outcome = AnswerOutcome::Correct;
+                result.score_delta = *score_delta;
+            }
+            if session.mode == StudySessionMode::Learning {
+                session.current_set_failed = session
+                    .results
+                    .iter()
+                    .rev()
+                    .take(session.current_card_index + 1)
+                    .any(|result| !result.is_correct);
+            }
+        }
+        StudySessionEvent::PronunciationAttempted { report } => {
+            session.pronunciation_technical_failures = 0;
+            session.pronunciation_disable_required = false;
+            session.pronunciation_attempts.push(report.clone());
+            session.pronunciation_passed |= report.passed;
+        }
+        StudySessionEvent::PronunciationCaptureFailed => {
+            session.pronunciation_technical_failures = session
+                .pronunciation_technical_failures
+                .saturating_add(1)
+                .min(2);
+            session.pronunciation_disable_required = session.pronunciation_technical_failures >= 2;
+        }
+        StudySessionEvent::PronunciationDisabled => {
+            session.pronunciation_check_enabled = false;
+            session.pronunciation_disable_required = false;
+        }
+        StudySessionEvent::FeedbackDismissed { next_test_card } => {
+            clear_current_card(session);
+            match session.mode {
+                StudySessionMode::Test => select_test_card(session, next_test_card.as_ref()),
+                StudySessionMode::Review => {
+                    session.current_card_index += 1;
+                    if session.current_card_index >= session.card_ids.len() {
+                        session.status = StudySessionStatus::Completed;
+                    }
+                }
+                StudySessionMode::Learning => session.current_card_index += 1,
+            }
+        }
+        StudySessionEvent::CardPresented { at } => session.card_presented_at = Some(*at),
+        StudySessionEvent::SetRetried => {
+            session.phase = StudySessionPhase::Study;
+            session.current_card_index = 0;
+            session.current_set_failed = false;
+            session.test_order.clear();
+        }
+        StudySessionEvent::MissedCardsRetried { card_ids } => {
+            session.phase = StudySessionPhase::Study;
+            session.current_card_index = 0;
+            session.current_set_failed = false;
+            session.test_order.clear();
+            session.retry_card_ids = card_ids.clone();
+        }
+        StudySessionEvent::SetPassed => pass_set(session),
+        StudySessionEvent::CardsRemoved {
+            card_ids,
+            next_test_card,
+        } => remove_cards(session, card_ids, next_test_card.as_ref()),
+        StudySessionEvent::Undone => {}
+        StudySessionEvent::Ended { status } => session.status = *status,
+    }
+}
+
+/// Whether a transition recording these events can be undone. Undoing the removal of deleted
+/// cards would bring them back into the session.
+pub fn undoable(events: &[StudySessionEvent]) -> bool {
+    !events.iter().any(|event| {
+        matches!(
+            event,
+            StudySessionEvent::Started { .. }
+                | StudySessionEvent::CardsRemoved { .. }
+                | StudySessionEvent::Undone
+                | StudySessionEvent::Ended { .. }
+        )
+    })
+}
+
+/// Rebuilds a session from its recorded transitions, oldest first. Returns `None` when the
+/// history does not start with the session being created or undoes more than it recorded.
+pub fn replay(transitions: &[RecordedTransition]) -> Option<StudySession> {
+    let (first, rest) = transitions.split_first()?;
+    let Some(StudySessionEvent::Started { session }) = first.events.first() else {
+        return None;
+    };
+    let mut session = (**session).clone();
+    session.version = first.version;
+    let mut undo_history = Vec::new();
+    for transition in rest {
+        if transition.events.contains(&StudySessionEvent::Undone) {
+            session = undo_history.pop()?;
+        } else {
+            let previous = session.clone();
+            for event in &transition.events {
+                apply(&mut session, event);
+            }
+            if undoable(&transition.events) {
+                undo_history.push(previous);
+            }
+        }
+        if session.status != StudySessionStatus::Active {
+            undo_history.clear();
+        }
+        session.version = transition.version;
+    }
+    Some(session)
+}
+
+/// The cards of the current learn pass: the missed cards while they are retried on their own,
+/// otherwise the whole current set.
+pub fn set_card_ids(session: &StudySession) -> &[CardId] {
+    if !session.retry_card_ids.is_empty() {
+        return &session.retry_card_ids;
+    }
+    let set_start = (session.current_set_index * session.cards_per_set).min(session.card_ids.len());
+    let set_end = (set_start + session.cards_per_set).min(session.card_ids.len());
+    &session.card_ids[set_start..set_end]
+}
+
+/// The cards of the current pass that the finished mini-test got wrong, in pass order.
+fn missed_card_ids(session: &StudySession) -> Vec<CardId> {
+    let missed = session
+        .results
+        .iter()
+        .rev()
+        .take(session.test_order.len())
+        .filter(|result| !result.is_correct)
+        .map(|result| &result.card_id)
+        .collect::<Vec<_>>();
+    set_card_ids(session)
+        .iter()
+        .filter(|card_id| missed.contains(card_id))
+        .cloned()
+        .collect()
+}
+
+fn pass_set(session: &mut StudySession) {
+    session.current_set_index += 1;
+    session.current_card_index = 0;
+    session.test_order.clear();
+    session.retry_card_ids.clear();
+    if session.current_set_index * session.cards_per_set >= session.card_ids.len() {
+        session.status = StudySessionStatus::Completed;
+    } else {
+        session.phase = StudySessionPhase::Study;
+    }
+}
+
+/// The part of the queue that is still ahead of the learner.
+pub fn pending_card_ids(session: &StudySession) -> &[CardId] {
+    let pending_from = match session.mode {
+        StudySessionMode::Test => 0,
+        StudySessionMode::Review => session.current_card_index,
+        StudySessionMode::Learning => session.current_set_index * session.cards_per_set,
+    }
+    .min(session.card_ids.len());
+    &session.card_ids[pending_from..]
+}
+
+pub fn current_card_id(session: &StudySession) -> Option<&CardId> {
+    if session.status != StudySessionStatus::Active {
+        return None;
+    }
+    match (session.mode, session.phase) {
+        (StudySessionMode::Test, _) => session.card_ids.first(),
+        (StudySessionMode::Review, _) => session.card_ids.get(session.current_card_index),
+        (StudySessionMode::Learning, StudySessionPhase::Study) => {
+            set_card_ids(session).get(session.current_card_index)
+        }
+        (StudySessionMode::Learning, StudySessionPhase::Test) => {
+            session.test_order.get(session.current_card_index)
+        }
+    }
+}
+
+/// Drops deleted cards from the part of the queue that is still ahead. A Learn set that loses
+/// a card mid-test is studied again from its first card, and a retry that loses all of its
+/// missed cards passes the set.
+fn remove_cards(session: &mut StudySession, removed: &[CardId], next_test_card: Option<&CardId>) {
+    let pending_from = session.card_ids.len() - pending_card_ids(session).len();
+    let previous_card = current_card_id(session).cloned();
+    let previous_phase = session.phase;
+    let skipped_before_current = set_card_ids(session)
+        .iter()
+        .take(session.current_card_index)
+        .filter(|card_id| removed.contains(*card_id))
+        .count();
+    let mut position = 0;
+    session.card_ids.retain(|card_id| {
+        position += 1;
+        position <= pending_from || !removed.contains(card_id)
+    });
+    match session.mode {
+        StudySessionMode::Test => select_test_card(session, next_test_card),
+        StudySessionMode::Review => {
+            if session.current_card_index >= session.card_ids.len() {
+                session.status = StudySessionStatus::Completed;
+            }
+        }
+        StudySessionMode::Learning => {
+            let retrying = !session.retry_card_ids.is_empty();
+            session
+                .retry_card_ids
+                .retain(|card_id| !removed.contains(card_id));
+            let remaining_in_set = set_card_ids(session).len();
+            if retrying && session.retry_card_ids.is_empty() {
+                pass_set(session);
+            } else if session.phase == StudySessionPhase::Test {
+                session.phase = StudySessionPhase::Study;
+                session.current_card_index = 0;
+                session.current_set_failed = false;
+                session.test_order.clear();
+            } else {
+                session.current_card_index = (session.current_card_index - skipped_before_current)
+                    .min(remaining_in_set.saturating_sub(1));
+            }
+            if remaining_in_set == 0 {
+                session.status = StudySessionStatus::Completed;
+            }
+        }
+    }
+    if current_card_id(session) != previous_card.as_ref() || session.phase != previous_phase {
+        clear_current_card(session);
+    }
+}
+
+/// Makes the picked card the only one of a test session, or completes it when none is left.
+fn select_test_card(session: &mut StudySession, card_id: Option<&CardId>) {
+    match card_id {
+        Some(card_id) => session.card_ids = vec![card_id.clone()],
+        None => {
+            session.card_ids.clear();
+            session.status = StudySessionStatus::Completed;
+        }
+    }
+}
+
+fn clear_current_card(session: &mut StudySession) {
+    session.awaiting_continue = false;
+    session.retype_pending = false;
+    session.schedule_before_answer = None;
+    session.leech_before_answer = None;
+    session.provided_answers.clear();
+    session.hints_used.clear();
+    session.card_presented_at = None;
+    session.completed_meaning_indices.clear();
+    session.pronunciation_attempts.clear();
+    session.pronunciation_passed = false;
+    session.pronunciation_disable_required = false;
+}
+
+/// The card under test once it is ready to take an answer.
+fn answerable_card<'a>(
+    session: &StudySession,
+    context: &'a TransitionContext,
+) -> Result<&'a Card, StudySessionError> {
+    if session.phase != StudySessionPhase::Test || session.awaiting_continue {
+        return Err(StudySessionError::InvalidAction);
+    }
+    let card = context
+        .card
+        .as_ref()
+        .ok_or(StudySessionError::InvalidAction)?;
+    if card.direction == CardDirection::Straight
+        && session.pronunciation_check_enabled
+        && !session.pronunciation_passed
+    {
+        return Err(StudySessionError::InvalidAction);
+    }
+    Ok(card)
+}
+
+fn written_answer(
+    session: &StudySession,
+    card: &Card,
+    context: &TransitionContext,
+    answer: &str,
+) -> StudySessionEvent {
+    let cloze = cloze_example(session, card);
+    let (accepted, _) = similarity_thresholds(session.answer_strictness);
+    let mut misplaced_stress = false;
+    let (outcome, answer_match) = if session.answer_format == AnswerFormat::Reading
+        && !card.word.readings.is_empty()
+    {
+        let mut exact = None;
+        for reading in &card.word.readings {
+            match compare_reading(&context.card_language, reading, answer) {
+                ReadingMatch::Exact => {
+                    exact = Some(reading);
+                    break;
+                }
+                ReadingMatch::MisplacedStress => misplaced_stress = true,
+                ReadingMatch::Different => {}
+            }
+        }
+        match exact {
+            Some(reading) => (
+                AnswerOutcome::Correct,
+                Some(AnswerMatch {
+                    meaning_index: None,
+                    expected: reading.clone(),
+                    similarity: 1.0,
+                    normalizations: Vec::new(),
+                }),
+            ),
+            None => {
+                let closest = card
+                    .word
+                    .readings
+                    .iter()
+                    .map(|reading| AnswerMatch {
+                        meaning_index: None,
+                        expected: reading.clone(),
+                        similarity: similarity_score(reading, answer),
+                        normalizations: Vec::new(),
+                    })
+                    .max_by(|left, right| left.similarity.total_cmp(&right.similarity));
+                (AnswerOutcome::Incorrect, closest)
+            }
+        }
+    } else {
+        let answer_match = if session.answer_format == AnswerFormat::Dictation || cloze.is_some() {
+            best_matching_spelling(
+                card,
+                cloze.as_ref().map(|example| &example.answer),
+                &context.card_language,
+                accepted,
+                answer,
+            )
+        } else {
+            best_matching_translation(
+                card,
+                &session.completed_meaning_indices,
+                &context.translation_language,
+                accepted,
+                answer,
+            )
+        };
+        (
+            answer_outcome(
+                session.answer_strictness,
+                session.retype_pending,
+                answer_match.as_ref(),
+            ),
+            answer_match,
+        )
+    };
+    graded_answer(
+        session,
+        card,
+        context,
+        Some(answer.to_string()),
+        outcome,
+        answer_match,
+        misplaced_stress,
+    )
+}
+
+/// Grades an answer, or giving up when there is none; a match without a meaning index answers
+/// the whole card at once. An almost correct answer only reveals the expected spelling and
+/// keeps the card open.
+fn graded_answer(
+    session: &StudySession,
+    card: &Card,
+    context: &TransitionContext,
+    answer: Option<String>,
+    outcome: AnswerOutcome,
+    answer_match: Option<AnswerMatch>,
+    misplaced_stress: bool,
+) -> StudySessionEvent {
+    let similarity_percent = answer_match.as_ref().map_or(0, |answer_match| {
+        similarity_percent(answer_match.similarity)
+    });
+    let completed_before = session.completed_meaning_indices.len();
+    let mut graded = GradedAnswer {
+        card_id: card.id.clone(),
+        word: card.word.text.clone(),
+        answer,
+        outcome,
+        matched_answer: None,
+        similarity_percent,
+        meaning_index: None,
+        card_completed: false,
+        remaining_meanings: card.meanings.len().saturating_sub(completed_before),
+        score_delta: 0,
+        misplaced_stress,
+        normalizations: Vec::new(),
+        schedule_before_answer: None,
+        leech_before_answer: card.leech,
+        answered_at: context.now,
+        response_time_millis: response_time_millis(session, context.now),
+    };
+    if outcome == AnswerOutcome::AlmostCorrect {
+        graded.matched_answer = answer_match.map(|answer_match| answer_match.expected);
+        return StudySessionEvent::AnswerGraded(Box::new(graded));
+    }
+    let is_correct = outcome == AnswerOutcome::Correct;
+    if let Some(answer_match) = answer_match.filter(|_| is_correct) {
+        graded.meaning_index = answer_match.meaning_index;
+        graded.matched_answer = Some(answer_match.expected);
+        graded.normalizations = answer_match.normalizations;
+    }
+    let whole_card = is_correct && graded.meaning_index.is_none();
+    let completed_meanings = completed_before + usize::from(graded.meaning_index.is_some());
+    graded.card_completed = !is_correct || whole_card || completed_meanings == card.meanings.len();
+    graded.remaining_meanings = if whole_card {
+        0
+    } else {
+        card.meanings.len().saturating_sub(completed_meanings)
+    };
+    if graded.card_completed {
+        graded.score_delta = if is_correct {
+            correct_score_delta(session)
+        } else {
+            failed_score_delta(session)
+        };
+        graded.schedule_before_answer = context.schedule.clone();
+    }
+    StudySessionEvent::AnswerGraded(Box::new(graded))
+}
+
+fn similarity_score(expected: &str, actual: &str) -> f64 {
+    let expected = expected.trim().to_lowercase();
+    let actual = actual.trim().to_lowercase();
+    if expected == actual {
+        return 1.0;
+    }
+    let length = expected.chars().count().max(actual.chars().count());
+    if length == 0 {
+        0.0
+    } else {
+        1.0 - strsim::damerau_levenshtein(&expected, &actual) as f64 / length as f64
+    }
+}
+
+fn similarity_percent(similarity: f64) -> u8 {
+    (similarity * 100.0 + 1e-9).floor().clamp(0.0, 100.0) as u8
+}
+
+/// The similarity, in percent, an answer needs to be accepted and to count as almost correct.
+fn similarity_thresholds(strictness: AnswerStrictness) -> (u8, u8) {
+    match strictness {
+        AnswerStrictness::Exact => (100, 80),
+        AnswerStrictness::Tolerant => (80, 60),
+        AnswerStrictness::Lenient => (65, 50),
+    }
+}
+
+/// Grades a typed answer by its closest match; an almost correct answer is only offered one
+/// retype.
+fn answer_outcome(
+    strictness: AnswerStrictness,
+    retype_pending: bool,
+    answer_match: Option<&AnswerMatch>,
+) -> AnswerOutcome {
+    let (accepted, almost) = similarity_thresholds(strictness);
+    let similarity = answer_match.map_or(0, |answer_match| {
+        similarity_percent(answer_match.similarity)
+    });
+    if similarity >= accepted {
+        AnswerOutcome::Correct
+    } else if similarity >= almost && !retype_pending {
+        AnswerOutcome::AlmostCorrect
+    } else {
+        AnswerOutcome::Incorrect
+    }
+}
+
+/// Scores an answer against the expected text after each normalization of the answer language
+/// in turn. A normalization never lowers the score, and only the ones applied while the answer
+/// was still below the `accepted` similarity are reported.
+fn normalized_similarity(
+    language: &str,
+    accepted: u8,
+    expected: &str,
+    answer: &str,
+) -> (f64, Vec<AnswerNormalization>) {
+    let mut expected = expected.trim().to_lowercase();
+    let mut answer = answer.trim().to_lowercase();
+    let mut score = similarity_score(&expected, &answer);
+    let mut applied = Vec::new();
+    for &normalization in answer_normalizations(language) {
+        let normalized_expected = normalize(normalization, &expected);
+        let normalized_answer = normalize(normalization, &answer);
+        if normalized_expected.trim().is_empty()
+            || normalized_answer.trim().is_empty()
+            || (normalized_expected == expected && normalized_answer == answer)
+        {
+            continue;
+        }
+        if similarity_percent(score) < accepted {
+            applied.push(normalization);
+        }
+        score = score.max(similarity_score(&normalized_expected, &normalized_answer));
+        expected = normalized_expected;
+        answer = normalized_answer;
+    }
+    if similarity_percent(score) < accepted {
+        applied.clear();
+    }
+    (score, applied)
+}
+
+/// Finds the translation of an open meaning closest to the answer.
+fn best_matching_translation(
+    card: &Card,
+    completed_meaning_indices: &[usize],
+    language: &str,
+    accepted: u8,
+    answer: &str,
+) -> Option<AnswerMatch> {
+    let mut best_match: Option<AnswerMatch> = None;
+    for (meaning_index, meaning) in card.meanings.iter().enumerate() {
+        if completed_meaning_indices.contains(&meaning_index) {
+            continue;
+        }
+        for expected in &meaning.word_translations {
+            let (similarity, normalizations) =
+                normalized_similarity(language, accepted, expected, answer);
+            if best_match
+                .as_ref()
+                .is_none_or(|best_match| similarity > best_match.similarity)
+            {
+                best_match = Some(AnswerMatch {
+                    meaning_index: Some(meaning_index),
+                    expected: expected.clone(),
+                    similarity,
+                    normalizations,
+                });
+            }
+        }
+    }
+    best_match
+}
+
+/// Finds the spelling closest to a dictated or cloze answer among the written word, its
+/// readings and, in cloze drills, the inflected form blanked out of the example.
+fn best_matching_spelling(
+    card: &Card,
+    inflected_form: Option<&String>,
+    language: &str,
+    accepted: u8,
+    answer: &str,
+) -> Option<AnswerMatch> {
+    std::iter::once(&card.word.text)
+        .chain(&card.word.readings)
+        .chain(inflected_form)
+        .map(|expected| {
+            let (similarity, normalizations) =
+                normalized_similarity(language, accepted, expected, answer);
+            AnswerMatch {
+                meaning_index: None,
+                expected: expected.clone(),
+                similarity,
+                normalizations,
+            }
+        })
+        .max_by(|left, right| left.similarity.total_cmp(&right.similarity))
+}
+
+/// Shuffles items from the seed and version of a session, so that replaying the same actions
+/// on a session with the same seed yields the same order.
+pub fn shuffle<T>(items: &mut [T], seed: u64, version: u64) {
+    shuffle_with_seed(items, version_seed(seed, version));
+}
+
+/// Derives the random state of one session version from the session seed.
+pub fn version_seed(seed: u64, version: u64) -> u64 {
+    seed.wrapping_add(version.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+}
+
+pub fn shuffle_with_seed<T>(items: &mut [T], mut state: u64) {
+    for index in (1..items.len()).rev() {
+        items.swap(index, next_random(&mut state) as usize % (index + 1));
+    }
+}
+
+/// Advances a xorshift state and returns it; a zero state stays zero.
+pub fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// Seeds the options of a multiple-choice card so that every view of the same question offers
+/// them in the same order.
+pub fn answer_option_seed(session: &StudySession, card: &Card) -> u64 {
+    format!(
+        "{}:{}:{}",
+        session.id.as_str(),
+        card.id.as_str(),
+        session.completed_meaning_indices.len()
+    )
+    .bytes()
+    .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Picks the usage example drilled for the current card of a cloze session. Cards without an
+/// example that contains the word fall back to a typed translation.
+pub fn cloze_example(session: &StudySession, card: &Card) -> Option<ClozeExample> {
+    if session.answer_format != AnswerFormat::Cloze {
+        return None;
+    }
+    let mut examples = cloze_examples(card);
+    if examples.is_empty() {
+        return None;
+    }
+    let index = answer_option_seed(session, card) as usize % examples.len();
+    Some(examples.swap_remove(index))
+}
+
+/// The first meaning of the card that is still to be answered.
+fn open_meaning<'a>(session: &StudySession, card: &'a Card) -> Option<&'a Meaning> {
+    card.meanings
+        .iter()
+        .enumerate()
+        .find(|(index, _)| !session.completed_meaning_indices.contains(index))
+        .map(|(_, meaning)| meaning)
+}
+
+/// The answer that letter and length hints describe: the word, its reading or a translation of
+/// the first open meaning, depending on what the card asks for.
+fn hinted_answer(session: &StudySession, card: &Card) -> Option<String> {
+    if session.answer_format == AnswerFormat::Reading
+        && let Some(reading) = card.word.readings.first()
+    {
+        return Some(reading.clone());
+    }
+    if session.answer_format == AnswerFormat::Dictation {
+        return Some(card.word.text.clone());
+    }
+    if let Some(example) = cloze_example(session, card) {
+        return Some(example.answer);
+    }
+    open_meaning(session, card)?
+        .word_translations
+        .first()
+        .cloned()
+}
+
+/// What a hint reveals about the current card; `None` when the card has nothing to show for
+/// it.
+pub fn hint_text(session: &StudySession, card: &Card, hint: SessionHint) -> Option<String> {
+    let text = match hint {
+        SessionHint::FirstLetter => hinted_answer(session, card)?
+            .trim()
+            .chars()
+            .next()?
+            .to_string(),
+        SessionHint::WordLength => {
+            let length = hinted_answer(session, card)?.trim().chars().count();
+            if length == 0 {
+                return None;
+            }
+            length.to_string()
+        }
+        SessionHint::TranslatedDefinition => open_meaning(session, card)?
+            .translated_definition
+            .trim()
+            .to_string(),
+        SessionHint::ExampleTranslation => open_meaning(session, card)?
+            .examples
+            .iter()
+            .map(|example| example.translation.trim())
+            .find(|translation| !translation.is_empty())?
+            .to_string(),
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// The next hint not yet revealed for the current card that has something to show.
+pub fn next_hint(session: &StudySession, card: &Card) -> Option<SessionHint> {
+    HINTS.into_iter().find(|hint| {
+        !session.hints_used.contains(hint) && hint_text(session, card, *hint).is_some()
+    })
+}
+
+/// The score change for a correct answer: each revealed hint takes one point off the reward,
+/// which never drops below zero, so a hinted answer is never penalised.
+fn correct_score_delta(session: &StudySession) -> i32 {
+    if session.mode == StudySessionMode::Learning {
+        0
+    } else {
+        (1 - session.hints_used.len() as i32).max(0)
+    }
+}
+
+/// Learn mini-tests are practice right after studying and never cost score.
+fn failed_score_delta(session: &StudySession) -> i32 {
+    if session.mode == StudySessionMode::Learning {
+        0
+    } else {
+        -2
+    }
+}
+
+fn response_time_millis(session: &StudySession, now: i64) -> Option<u64> {
+    session
+        .card_presented_at
+        .map(|presented_at| u64::try_from(now - presented_at).unwrap_or(0))
+}
+
+/// Answers that needed hints or came slowly are scheduled as hard recalls. Cards that went
+/// through a pronunciation check first are not timed against the limit, since recording
+/// takes its own time.
+pub fn correct_grade(session: &StudySession, response_time_millis: Option<u64>) -> ReviewGrade {
+    let slow_after = match session.answer_format {
+        AnswerFormat::MultipleChoice => SLOW_CHOICE_MILLIS,
+        _ => SLOW_TYPED_MILLIS,
+    };
+    let slow = session.pronunciation_attempts.is_empty()
+        && response_time_millis.is_some_and(|millis| millis > slow_after);
+    if session.hints_used.is_empty() && !slow {
+        ReviewGrade::Good
+    } else {
+        ReviewGrade::Hard
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::ports::input::{
+        card_catalog::models::{CardOrder, CardStatus, LeechState, Word},
+        language_profile::models::ProfileId,
+        local_user::models::UserId,
+        study_session::models::{SessionFilter, SessionId},
+    };
+
+    const TOLERANT: u8 = 80;
+    const UNDO_HISTORY_LIMIT: usize = 10;
+
+    /// The closest match when the tolerant threshold accepts it.
+    fn accepted(
+        answer_match: Option<AnswerMatch>,
+    ) -> Option<(Option<usize>, String, Vec<AnswerNormalization>)> {
+        answer_match
+            .filter(|answer_match| similarity_percent(answer_match.similarity) >= TOLERANT)
+            .map(|answer_match| {
+                (
+                    answer_match.meaning_index,
+                    answer_match.expected,
+                    answer_match.normalizations,
+                )
+            })
+    }
+
+    fn card_with_translations(translations: &[&[&str]]) -> Card {
+        Card {
+            id: CardId::new("card"),
+            profile_id: ProfileId::new("profile"),
+            direction: CardDirection::Straight,
+            word: Word {
+                text: "word".to_string(),
+                readings: Vec::new(),
+            },
+            meanings: translations
+                .iter()
+                .map(|translations| Meaning {
+                    definition: "definition".to_string(),
+                    translated_definition: "translated definition".to_string(),
+                    word_translations: translations
+                        .iter()
+                        .map(|translation| (*translation).to_string())
+                        .collect(),
+                    examples: Vec::new(),
+                })
+                .collect(),
+            score: 0,
+            leech: LeechState::default(),
+            status: CardStatus::Active,
+            created_at: 0,
+            version: 0,
+        }
+    }
+
+    #[test]
+    fn written_answers_use_unicode_aware_similarity() {
+        assert_eq!(similarity_score("Привет", " привет "), 1.0);
+        assert!(similarity_score("hello", "helo") >= 0.8);
+        assert!(similarity_score("hello", "goodbye") < 0.8);
+        assert!(similarity_score("猫", "犬") < 0.8);
+    }
+
+    #[test]
+    fn written_answer_uses_the_best_match_across_all_meanings() {
+        let card = card_with_translations(&[&["hello"], &["helo"]]);
+
+        assert_eq!(
+            accepted(best_matching_translation(
+                &card,
+                &[],
+                "en-US",
+                TOLERANT,
+                "helo"
+            )),
+            Some((Some(1), "helo".to_string(), Vec::new()))
+        );
+    }
+
+    #[test]
+    fn equal_matches_prefer_the_first_uncompleted_meaning() {
+        let card = card_with_translations(&[&["same"], &["same"]]);
+
+        assert_eq!(
+            accepted(best_matching_translation(
+                &card,
+                &[],
+                "en-US",
+                TOLERANT,
+                "same"
+            )),
+            Some((Some(0), "same".to_string(), Vec::new()))
+        );
+        assert_eq!(
+            accepted(best_matching_translation(
+                &card,
+                &[0],
+                "en-US",
+                TOLERANT,
+                "same"
+            )),
+            Some((Some(1), "same".to_string(), Vec::new()))
+        );
+    }
+
+    #[test]
+    fn dictation_accepts_the_word_or_any_of_its_readings() {
+        let mut card = card_with_translations(&[&["bridge"]]);
+        card.word.text = "橋".to_string();
+        card.word.readings = vec!["はし".to_string(), "hashi".to_string()];
+        let spelling = |answer| {
+            accepted(best_matching_spelling(
+                &card, None, "ja-JP", TOLERANT, answer,
+            ))
+        };
+
+        assert_eq!(spelling("橋"), Some((None, "橋".to_string(), Vec::new())));
+        assert_eq!(
+            spelling(" Hashi "),
+            Some((None, "hashi".to_string(), Vec::new()))
+        );
+        assert_eq!(spelling("bridge"), None);
+    }
+
+    #[test]
+    fn answers_report_the_normalizations_that_made_them_match() {
+        let card = card_with_translations(&[&["ёжик"], &["(to) run"]]);
+        let translation = |language, answer| {
+            accepted(best_matching_translation(
+                &card,
+                &[],
+                language,
+                TOLERANT,
+                answer,
+            ))
+        };
+
+        assert_eq!(
+            translation("ru-RU", "ежик"),
+            Some((
+                Some(0),
+                "ёжик".to_string(),
+                vec![AnswerNormalization::FoldYo]
+            ))
+        );
+        assert_eq!(
+            translation("ru-RU", "ёжик"),
+            Some((Some(0), "ёжик".to_string(), Vec::new()))
+        );
+        assert_eq!(
+            translation("en-US", "to run"),
+            Some((
+                Some(1),
+                "(to) run".to_string(),
+                vec![
+                    AnswerNormalization::IgnoreOptionalParts,
+                    AnswerNormalization::IgnoreLeadingArticle,
+                ]
+            ))
+        );
+        assert_eq!(
+            normalized_similarity("ja-JP", TOLERANT, "がっこう", "ｶﾞｯｺｳ"),
+            (
+                1.0,
+                vec![
+                    AnswerNormalization::FoldWidth,
+                    AnswerNormalization::FoldKana
+                ]
+            )
+        );
+        assert_eq!(
+            normalized_similarity("en-US", TOLERANT, "the end", "the ends").1,
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn strictness_decides_between_correct_almost_correct_and_incorrect() {
+        let outcome = |strictness, retype_pending, expected: &str, answer: &str| {
+            let answer_match = AnswerMatch {
+                meaning_index: Some(0),
+                expected: expected.to_string(),
+                similarity: similarity_score(expected, answer),
+                normalizations: Vec::new(),
+            };
+            answer_outcome(strictness, retype_pending, Some(&answer_match))
+        };
+
+        assert_eq!(
+            outcome(AnswerStrictness::Exact, false, "house", "House"),
+            AnswerOutcome::Correct
+        );
+        assert_eq!(
+            outcome(AnswerStrictness::Exact, false, "house", "hous"),
+            AnswerOutcome::AlmostCorrect
+        );
+        assert_eq!(
+            outcome(AnswerStrictness::Tolerant, false, "house", "hous"),
+            AnswerOutcome::Correct
+        );
+        assert_eq!(
+            outcome(AnswerStrictness::Tolerant, false, "house", "huse"),
+            AnswerOutcome::Correct
+        );
+        assert_eq!(
+            outcome(AnswerStrictness::Tolerant, false, "house", "hosue"),
+            AnswerOutcome::Correct
+        );
+        assert_eq!(
+            outcome(AnswerStrictness::Tolerant, false, "house", "hoss"),
+            AnswerOutcome::AlmostCorrect
+        );
+        assert_eq!(
+            outcome(AnswerStrictness::Tolerant, true, "house", "hoss"),
+            AnswerOutcome::Incorrect
+        );
+        assert_eq!(
+            outcome(AnswerStrictness::Lenient, false, "house", "hoss"),
+            AnswerOutcome::AlmostCorrect
+        );
+        assert_eq!(
+            outcome(AnswerStrictness::Lenient, false, "house", "garden"),
+            AnswerOutcome::Incorrect
+        );
+        assert_eq!(
+            answer_outcome(AnswerStrictness::Lenient, false, None),
+            AnswerOutcome::Incorrect
+        );
+    }
+
+    #[test]
+    fn session_order_is_replayed_from_its_seed() {
+        let shuffled = |seed, version| {
+            let mut items = (0..20).collect::<Vec<_>>();
+            shuffle(&mut items, seed, version);
+            items
+        };
+
+        assert_eq!(shuffled(7, 3), shuffled(7, 3));
+        assert_ne!(shuffled(7, 3), shuffled(8, 3));
+        assert_ne!(shuffled(7, 3), shuffled(7, 4));
+        assert_ne!(shuffled(7, 3), (0..20).collect::<Vec<_>>());
+    }
+
+    #[derive(Debug, Clone)]
+    enum Step {
+        Act(StudySessionAction),
+        /// Types the first translation of a meaning of the current card (or its word in
+        /// dictation): as is, with its last letter dropped, or replaced by something else.
+        Answer {
+            meaning: usize,
+            variant: usize,
+        },
+        Assess {
+            passed: bool,
+        },
+        DeleteCard(usize),
+    }
+
+    /// Drives the reducer the way the service and repository do, over a fixed set of cards.
+    struct Simulation {
+        cards: Vec<Card>,
+        deleted: Vec<CardId>,
+        session: StudySession,
+        transitions: Vec<RecordedTransition>,
+        undo_history: Vec<StudySession>,
+    }
+
+    impl Simulation {
+        fn new(
+            mode: StudySessionMode,
+            answer_format: AnswerFormat,
+            pronunciation_check_enabled: bool,
+            cards_per_set: usize,
+            retry_missed_only: bool,
+            meanings: &[usize],
+            seed: u64,
+        ) -> Self {
+            let cards = meanings
+                .iter()
+                .enumerate()
+                .map(|(index, &meanings)| Card {
+                    id: CardId::new(format!("card-{index}")),
+                    direction: if index % 2 == 0 {
+                        CardDirection::Straight
+                    } else {
+                        CardDirection::Reverse
+                    },
+                    word: Word {
+                        text: format!("word{index}"),
+                        readings: Vec::new(),
+                    },
+                    meanings: (0..meanings)
+                        .map(|meaning| Meaning {
+                            definition: String::new(),
+                            translated_definition: format!("definition {index} {meaning}"),
+                            word_translations: vec![format!("meaning{index}{meaning}")],
+                            examples: Vec::new(),
+                        })
+                        .collect(),
+                    ..card_with_translations(&[])
+                })
+                .collect::<Vec<_>>();
+            let session = StudySession {
+                id: SessionId::new("session"),
+                owner_id: UserId::new("user"),
+                profile_id: ProfileId::new("profile"),
+                mode,
+                phase: if mode == StudySessionMode::Learning {
+                    StudySessionPhase::Study
+                } else {
+                    StudySessionPhase::Test
+                },
+                status: StudySessionStatus::Active,
+                filter: SessionFilter {
+                    direction: None,
+                    min_score: None,
+                    max_score: None,
+                },
+                answer_format,
+                answer_strictness: AnswerStrictness::Tolerant,
+                pronunciation_check_enabled,
+                pronunciation_score_threshold: 75,
+                cards_per_set,
+                retry_missed_only,
+                card_order: CardOrder::Random,
+                recent_ban_window: 0,
+                max_cards: None,
+                ends_at: None,
+                correct_streak_goal: None,
+                card_ids: if mode == StudySessionMode::Test {
+                    vec![cards[0].id.clone()]
+                } else {
+                    cards.iter().map(|card| card.id.clone()).collect()
+                },
+                test_order: Vec::new(),
+                seed,
+                current_set_index: 0,
+                retry_card_ids: Vec::new(),
+                current_card_index: 0,
+                provided_answers: Vec::new(),
+                completed_meaning_indices: Vec::new(),
+                pronunciation_attempts: Vec::new(),
+                pronunciation_passed: false,
+                pronunciation_technical_failures: 0,
+                pronunciation_disable_required: false,
+                awaiting_continue: false,
+                retype_pending: false,
+                schedule_before_answer: None,
+                leech_before_answer: None,
+                hints_used: Vec::new(),
+                card_presented_at: (mode != StudySessionMode::Learning).then_some(0),
+                current_set_failed: false,
+                results: Vec::new(),
+                version: 0,
+            };
+            Self {
+                cards,
+                deleted: Vec::new(),
+                transitions: vec![RecordedTransition {
+                    version: 0,
+                    events: vec![StudySessionEvent::Started {
+                        session: Box::new(session.clone()),
+                    }],
+                }],
+                session,
+                undo_history: Vec::new(),
+            }
+        }
+
+        fn with_limits(
+            mut self,
+            max_cards: Option<usize>,
+            ends_at: Option<i64>,
+            correct_streak_goal: Option<usize>,
+        ) -> Self {
+            self.session.max_cards = max_cards;
+            self.session.ends_at = ends_at;
+            self.session.correct_streak_goal = correct_streak_goal;
+            self.transitions[0].events = vec![StudySessionEvent::Started {
+                session: Box::new(self.session.clone()),
+            }];
+            self
+        }
+
+        fn card(&self, card_id: &CardId) -> &Card {
+            self.cards.iter().find(|card| &card.id == card_id).unwrap()
+        }
+
+        /// A card left to test next; `None` once in a while to end a test session.
+        fn next_test_card(&self) -> Option<CardId> {
+            let available = self
+                .cards
+                .iter()
+                .filter(|card| !self.deleted.contains(&card.id))
+                .collect::<Vec<_>>();
+            available
+                .get(self.session.version as usize % (available.len() + 1))
+                .map(|card| card.id.clone())
+        }
+
+        fn context(&self) -> TransitionContext {
+            let card = current_card_id(&self.session)
+                .filter(|_| self.session.phase == StudySessionPhase::Test)
+                .map(|card_id| self.card(card_id).clone());
+            let options = card
+                .as_ref()
+                .filter(|_| {
+                    self.session.answer_format == AnswerFormat::MultipleChoice
+                        && !self.session.awaiting_continue
+                })
+                .and_then(|card| open_meaning(&self.session, card))
+                .map(|meaning| {
+                    let mut options = vec!["other one".to_string(), "other two".to_string()];
+                    options.extend(meaning.word_translations.first().cloned());
+                    options.push("other three".to_string());
+                    options
+                })
+                .unwrap_or_default();
+            TransitionContext {
+                card,
+                card_language: "en-US".to_string(),
+                translation_language: "en-US".to_string(),
+                options,
+                schedule: None,
+                next_test_card: (self.session.mode == StudySessionMode::Test)
+                    .then(|| self.next_test_card())
+                    .flatten(),
+                now: self.session.version as i64 * 1_000,
+            }
+        }
+
+        fn commit(&mut self, events: Vec<StudySessionEvent>) {
+            let previous = self.session.clone();
+            for event in &events {
+                apply(&mut self.session, event);
+            }
+            if self.session.status != StudySessionStatus::Active {
+                self.undo_history.clear();
+            } else if undoable(&events) {
+                self.undo_history.push(previous);
+                if self.undo_history.len() > UNDO_HISTORY_LIMIT {
+                    self.undo_history.remove(0);
+                }
+            }
+            self.session.version += 1;
+            self.transitions.push(RecordedTransition {
+                version: self.session.version,
+                events,
+            });
+        }
+
+        fn step(&mut self, step: Step) {
+            if self.session.status != StudySessionStatus::Active {
+                return;
+            }
+            let context = self.context();
+            let events = match step {
+                Step::Act(StudySessionAction::UndoLastAction) => {
+                    let Some(previous) = self.undo_history.pop() else {
+                        return;
+                    };
+                    let version = self.session.version + 1;
+                    self.session = StudySession {
+                        version,
+                        ..previous
+                    };
+                    self.transitions.push(RecordedTransition {
+                        version,
+                        events: vec![StudySessionEvent::Undone],
+                    });
+                    return;
+                }
+                Step::Act(action) => reduce(&self.session, &action, &context),
+                Step::Answer { meaning, variant } => {
+                    let Some(card) = &context.card else {
+                        return;
+                    };
+                    let expected = if self.session.answer_format == AnswerFormat::Dictation {
+                        card.word.text.clone()
+                    } else {
+                        card.meanings[meaning % card.meanings.len()].word_translations[0].clone()
+                    };
+                    let answer = match variant {
+                        0 => expected,
+                        1 => expected[..expected.len() - 3].to_string(),
+                        _ => "something else".to_string(),
+                    };
+                    reduce(
+                        &self.session,
+                        &StudySessionAction::SubmitWrittenAnswer { answer },
+                        &context,
+                    )
+                }
+                Step::Assess { passed } => assess(
+                    &self.session,
+                    &context,
+                    PronunciationAssessmentReport {
+                        strict_score: if passed { 90 } else { 40 },
+                        weakest_phoneme_score: None,
+                        weakest_word_score: None,
+                        pronunciation_score: None,
+                        fluency_score: None,
+                        completeness_score: None,
+                        prosody_score: None,
+                        recognized_text: None,
+                        issues: Vec::new(),
+                        scoring_version: 3,
+                        passed,
+                    },
+                ),
+                Step::DeleteCard(index) => {
+                    let card_id = self.cards[index % self.cards.len()].id.clone();
+                    if !self.deleted.contains(&card_id) {
+                        self.deleted.push(card_id);
+                    }
+                    let removed = pending_card_ids(&self.session)
+                        .iter()
+                        .filter(|card_id| self.deleted.contains(card_id))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    if removed.is_empty() {
+                        return;
+                    }
+                    let limit_reached = self.session.mode == StudySessionMode::Test
+                        && test_limit_reached(&self.session, context.now);
+                    Ok(presenting_next_card(
+                        &self.session,
+                        vec![StudySessionEvent::CardsRemoved {
+                            card_ids: removed,
+                            next_test_card: context
+                                .next_test_card
+                                .clone()
+                                .filter(|_| !limit_reached),
+                        }],
+                        context.now,
+                    ))
+                }
+            };
+            if let Ok(events) = events {
+                self.commit(events);
+            }
+        }
+
+        fn check_invariants(&self) -> Result<(), TestCaseError> {
+            let session = &self.session;
+            if session.status == StudySessionStatus::Active {
+                let card = current_card_id(session);
+                prop_assert!(card.is_some(), "an active session has a current card");
+                let meanings = self.card(card.unwrap()).meanings.len();
+                prop_assert!(session.completed_meaning_indices.len() <= meanings);
+                prop_assert!(
+                    session
+                        .completed_meaning_indices
+                        .iter()
+                        .all(|&index| index < meanings)
+                );
+            }
+            let mut completed = session.completed_meaning_indices.clone();
+            completed.sort_unstable();
+            completed.dedup();
+            prop_assert_eq!(completed.len(), session.completed_meaning_indices.len());
+            prop_assert!(!session.awaiting_continue || session.phase == StudySessionPhase::Test);
+            prop_assert_eq!(
+                session.card_presented_at.is_some(),
+                session.status == StudySessionStatus::Active
+                    && session.phase == StudySessionPhase::Test
+            );
+            prop_assert!(!(session.retype_pending && session.awaiting_continue));
+            prop_assert!(session.retry_card_ids.is_empty() || session.retry_missed_only);
+            prop_assert!(
+                session
+                    .max_cards
+                    .is_none_or(|max_cards| session.results.len() <= max_cards)
+            );
+            // The earliest instant leaves the time limit out.
+            prop_assert!(
+                session.status != StudySessionStatus::Active
+                    || session.awaiting_continue
+                    || !test_limit_reached(session, i64::MIN)
+            );
+            let set_start = session.current_set_index * session.cards_per_set;
+            let retried_outside_set = session.retry_card_ids.iter().any(|card_id| {
+                !session.card_ids[set_start.min(session.card_ids.len())..]
+                    .iter()
+                    .take(session.cards_per_set)
+                    .any(|set_card_id| set_card_id == card_id)
+            });
+            prop_assert!(!retried_outside_set);
+            prop_assert!(session.hints_used.len() <= HINTS.len());
+            let repeated_hint = session
+                .hints_used
+                .iter()
+                .enumerate()
+                .any(|(index, hint)| session.hints_used[..index].contains(hint));
+            prop_assert!(!repeated_hint);
+            prop_assert!(session.pronunciation_attempts.len() <= 2);
+            prop_assert!(session.pronunciation_technical_failures <= 2);
+            prop_assert!(
+                session
+                    .results
+                    .iter()
+                    .all(|result| (-2..=1).contains(&result.score_delta))
+            );
+            prop_assert!(
+                session
+                    .results
+                    .iter()
+                    .all(|result| !result.is_correct || (0..=1).contains(&result.score_delta))
+            );
+            prop_assert!(
+                session
+                    .results
+                    .iter()
+                    .all(|result| result.is_correct == (result.outcome == AnswerOutcome::Correct))
+            );
+            prop_assert_eq!(replay(&self.transitions), Some(session.clone()));
+            Ok(())
+        }
+    }
+
+    fn step() -> impl Strategy<Value = Step> {
+        prop_oneof![
+            1 => Just(Step::Act(StudySessionAction::PreviousStudyCard)),
+            2 => Just(Step::Act(StudySessionAction::NextStudyCard)),
+            2 => Just(Step::Act(StudySessionAction::StartMiniTest)),
+            6 => (0..3usize, 0..3usize)
+                .prop_map(|(meaning, variant)| Step::Answer { meaning, variant }),
+            2 => (0..4usize).prop_map(|index| Step::Act(
+                StudySessionAction::SelectAnswerOption { index }
+            )),
+            5 => Just(Step::Act(StudySessionAction::ContinueAfterFeedback)),
+            1 => Just(Step::Act(StudySessionAction::GiveUp)),
+            1 => Just(Step::Act(StudySessionAction::RevealHint)),
+            1 => any::<bool>().prop_map(|add_to_translations| Step::Act(
+                StudySessionAction::AcceptLastAnswer { add_to_translations }
+            )),
+            2 => Just(Step::Act(StudySessionAction::UndoLastAction)),
+            1 => Just(Step::Act(
+                StudySessionAction::RegisterPronunciationCaptureFailure {
+                    message: String::new()
+                }
+            )),
+            1 => Just(Step::Act(StudySessionAction::DisablePronunciation)),
+            2 => any::<bool>().prop_map(|passed| Step::Assess { passed }),
+            1 => (0..6usize).prop_map(Step::DeleteCard),
+        ]
+    }
+
+    fn mode() -> impl Strategy<Value = StudySessionMode> {
+        prop_oneof![
+            Just(StudySessionMode::Learning),
+            Just(StudySessionMode::Test),
+            Just(StudySessionMode::Review),
+        ]
+    }
+
+    fn answer_format() -> impl Strategy<Value = AnswerFormat> {
+        prop_oneof![
+            Just(AnswerFormat::Written),
+            Just(AnswerFormat::MultipleChoice),
+            Just(AnswerFormat::Dictation),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn sessions_keep_their_invariants_and_replay_from_their_events(
+            mode in mode(),
+            answer_format in answer_format(),
+            pronunciation_check_enabled in any::<bool>(),
+            cards_per_set in 1..4usize,
+            retry_missed_only in any::<bool>(),
+            max_cards in prop::option::of(1..5usize),
+            ends_at in prop::option::of(0..60_000i64),
+            correct_streak_goal in prop::option::of(1..4usize),
+            meanings in prop::collection::vec(1..4usize, 1..6),
+            seed in any::<u64>(),
+            steps in prop::collection::vec(step(), 0..60),
+        ) {
+            let mut simulation = Simulation::new(
+                mode,
+                answer_format,
+                pronunciation_check_enabled,
+                cards_per_set,
+                retry_missed_only,
+                &meanings,
+                seed,
+            );
+            if mode == StudySessionMode::Test {
+                simulation = simulation.with_limits(max_cards, ends_at, correct_streak_goal);
+            }
+            simulation.check_invariants()?;
+            for step in steps {
+                simulation.step(step);
+                simulation.check_invariants()?;
+            }
+        }
+    }
+
+    #[test]
+    fn answers_are_timed_from_the_presentation_of_their_card() {
+        let mut simulation = Simulation::new(
+            StudySessionMode::Review,
+            AnswerFormat::Written,
+            false,
+            1,
+            false,
+            &[1, 1],
+            0,
+        );
+        simulation.step(Step::Act(StudySessionAction::RevealHint));
+        simulation.step(Step::Answer {
+            meaning: 0,
+            variant: 0,
+        });
+
+        let result = &simulation.session.results[0];
+        assert_eq!(result.answered_at, Some(1_000));
+        assert_eq!(result.response_time_millis, Some(1_000));
+
+        simulation.step(Step::Act(StudySessionAction::ContinueAfterFeedback));
+
+        assert_eq!(simulation.session.card_presented_at, Some(2_000));
+        assert_eq!(replay(&simulation.transitions), Some(simulation.session));
+    }
+
+    #[test]
+    fn failed_sets_can_retry_only_their_missed_cards() {
+        let mut simulation = Simulation::new(
+            StudySessionMode::Learning,
+            AnswerFormat::Written,
+            false,
+            3,
+            true,
+            &[1, 1, 1],
+            0,
+        );
+        simulation.step(Step::Act(StudySessionAction::StartMiniTest));
+        let test_order = simulation.session.test_order.clone();
+        for variant in [0, 2, 0] {
+            simulation.step(Step::Answer {
+                meaning: 0,
+                variant,
+            });
+            simulation.step(Step::Act(StudySessionAction::ContinueAfterFeedback));
+        }
+
+        assert_eq!(simulation.session.phase, StudySessionPhase::Study);
+        assert_eq!(
+            simulation.session.retry_card_ids,
+            vec![test_order[1].clone()]
+        );
+        assert_eq!(current_card_id(&simulation.session), Some(&test_order[1]));
+        assert_eq!(
+            simulation.transitions.last().unwrap().events.last(),
+            Some(&StudySessionEvent::MissedCardsRetried {
+                card_ids: vec![test_order[1].clone()],
+            })
+        );
+
+        simulation.step(Step::Act(StudySessionAction::NextStudyCard));
+        assert_eq!(simulation.session.current_card_index, 0);
+        simulation.step(Step::Act(StudySessionAction::StartMiniTest));
+        assert_eq!(simulation.session.test_order, vec![test_order[1].clone()]);
+        simulation.step(Step::Answer {
+            meaning: 0,
+            variant: 0,
+        });
+        simulation.step(Step::Act(StudySessionAction::ContinueAfterFeedback));
+
+        assert_eq!(simulation.session.status, StudySessionStatus::Completed);
+        assert!(simulation.session.retry_card_ids.is_empty());
+        assert_eq!(replay(&simulation.transitions), Some(simulation.session));
+    }
+
+    #[test]
+    fn bounded_test_sessions_complete_at_their_first_limit() {
+        let run = |limits: (Option<usize>, Option<i64>, Option<usize>), variants: &[usize]| {
+            let (max_cards, ends_at, correct_streak_goal) = limits;
+            let mut simulation = Simulation::new(
+                StudySessionMode::Test,
+                AnswerFormat::Written,
+                false,
+                1,
+                false,
+                &[1; 7],
+                0,
+            )
+            .with_limits(max_cards, ends_at, correct_streak_goal);
+            for &variant in variants {
+                simulation.step(Step::Answer {
+                    meaning: 0,
+                    variant,
+                });
+                simulation.step(Step::Act(StudySessionAction::ContinueAfterFeedback));
+            }
+            assert_eq!(
+                replay(&simulation.transitions),
+                Some(simulation.session.clone())
+            );
+            (simulation.session.status, simulation.session.results.len())
+        };
+
+        assert_eq!(
+            run((Some(2), None, None), &[0]),
+            (StudySessionStatus::Active, 1)
+        );
+        assert_eq!(
+            run((Some(2), None, None), &[2, 0, 0]),
+            (StudySessionStatus::Completed, 2)
+        );
+        assert_eq!(
+            run((None, None, Some(2)), &[0, 2, 0]),
+            (StudySessionStatus::Active, 3)
+        );
+        assert_eq!(
+            run((None, None, Some(2)), &[0, 2, 0, 0, 0]),
+            (StudySessionStatus::Completed, 4)
+        );
+        assert_eq!(
+            run((None, Some(2_500), None), &[0, 0, 0]),
+            (StudySessionStatus::Completed, 2)
+        );
+    }
+
+    #[test]
+    fn revealed_hints_reduce_the_credit_but_never_below_zero() {
+        let session = Simulation::new(
+            StudySessionMode::Test,
+            AnswerFormat::Written,
+            false,
+            1,
+            false,
+            &[1],
+            0,
+        )
+        .session;
+        let credit = |hints: usize| {
+            correct_score_delta(&StudySession {
+                hints_used: HINTS[..hints].to_vec(),
+                ..session.clone()
+            })
+        };
+
+        assert_eq!(
+            (0..=HINTS.len()).map(credit).collect::<Vec<_>>(),
+            [1, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn slow_or_hinted_correct_answers_are_graded_hard() {
+        let session = Simulation::new(
+            StudySessionMode::Review,
+            AnswerFormat::Written,
+            false,
+            1,
+            false,
+            &[1],
+            0,
+        )
+        .session;
+        let choice = StudySession {
+            answer_format: AnswerFormat::MultipleChoice,
+            ..session.clone()
+        };
+        let hinted = StudySession {
+            hints_used: vec![SessionHint::FirstLetter],
+            ..session.clone()
+        };
+
+        assert_eq!(correct_grade(&session, None), ReviewGrade::Good);
+        assert_eq!(
+            correct_grade(&session, Some(SLOW_TYPED_MILLIS)),
+            ReviewGrade::Good
+        );
+        assert_eq!(
+            correct_grade(&session, Some(SLOW_TYPED_MILLIS + 1)),
+            ReviewGrade::Hard
+        );
+        assert_eq!(
+            correct_grade(&choice, Some(SLOW_CHOICE_MILLIS + 1)),
+            ReviewGrade::Hard
+        );
+        assert_eq!(correct_grade(&hinted, Some(0)), ReviewGrade::Hard);
+    }
+}
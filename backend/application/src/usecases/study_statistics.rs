@@ -0,0 +1,208 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::card_scheduling::{MAX_UTC_OFFSET_MINUTES, local_day, local_day_start};
+use crate::ports::{
+    input::study_statistics::{
+        StudyStatisticsUsecase,
+        models::{
+            AccuracyPoint, DailyReviewCount, HardCard, HardestCardsQuery, ProfileStatisticsQuery,
+            ScoreBucket, StatisticsPeriodQuery, StudyStatisticsError, StudyStreak,
+            StudyStreakQuery,
+        },
+    },
+    output::{
+        Clock,
+        repository::study_statistics::{
+            StudyStatisticsRepository,
+            models::{DailyAnswerCounts, StudyStatisticsRepositoryError},
+        },
+    },
+};
+
+const MAX_HARDEST_CARDS: usize = 100;
+
+pub struct StudyStatisticsService {
+    repository: Arc<dyn StudyStatisticsRepository>,
+    clock: Arc<dyn Clock>,
+}
+
+impl StudyStatisticsService {
+    pub fn new(repository: Arc<dyn StudyStatisticsRepository>, clock: Arc<dyn Clock>) -> Self {
+        Self { repository, clock }
+    }
+
+    fn map_repository_error(error: StudyStatisticsRepositoryError) -> StudyStatisticsError {
+        match error {
+            StudyStatisticsRepositoryError::NotFound => StudyStatisticsError::NotFound,
+            StudyStatisticsRepositoryError::Unavailable => StudyStatisticsError::Unexpected(
+                "study statistics repository is unavailable".to_string(),
+            ),
+            StudyStatisticsRepositoryError::Unexpected(message) => {
+                StudyStatisticsError::Unexpected(message)
+            }
+        }
+    }
+
+    fn validate_offset(utc_offset_minutes: i32) -> Result<(), StudyStatisticsError> {
+        if utc_offset_minutes.abs() > MAX_UTC_OFFSET_MINUTES {
+            return Err(StudyStatisticsError::InvalidQuery);
+        }
+        Ok(())
+    }
+
+    async fn daily_answers(
+        &self,
+        query: StatisticsPeriodQuery,
+    ) -> Result<Vec<DailyAnswerCounts>, StudyStatisticsError> {
+        Self::validate_offset(query.utc_offset_minutes)?;
+        self.repository
+            .daily_answers(
+                &query.user_id,
+                &query.profile_id,
+                query.since,
+                query.utc_offset_minutes,
+            )
+            .await
+            .map_err(Self::map_repository_error)
+    }
+
+    /// Computes streaks from ascending, distinct local days with answers.
+    fn streak_from_days(days: &[i64], today: i64) -> StudyStreak {
+        let mut longest_days = 0;
+        let mut run = 0;
+        let mut previous = None;
+        for &day in days {
+            run = if previous == Some(day - 1) {
+                run + 1
+            } else {
+                1
+            };
+            longest_days = longest_days.max(run);
+            previous = Some(day);
+        }
+        let studied_today = previous == Some(today);
+        let current_days = if studied_today || previous == Some(today - 1) {
+            run
+        } else {
+            0
+        };
+        StudyStreak {
+            current_days,
+            longest_days,
+            studied_today,
+        }
+    }
+}
+
+#[async_trait]
+impl StudyStatisticsUsecase for StudyStatisticsService {
+    async fn accuracy_over_time(
+        &self,
+        query: StatisticsPeriodQuery,
+    ) -> Result<Vec<AccuracyPoint>, StudyStatisticsError> {
+        let utc_offset_minutes = query.utc_offset_minutes;
+        Ok(self
+            .daily_answers(query)
+            .await?
+            .into_iter()
+            .map(|counts| AccuracyPoint {
+                day_start: local_day_start(counts.day, utc_offset_minutes),
+                answers: counts.answers,
+                correct: counts.correct,
+                average_response_millis: counts.average_response_millis,
+            })
+            .collect())
+    }
+
+    async fn cards_reviewed_per_day(
+        &self,
+        query: StatisticsPeriodQuery,
+    ) -> Result<Vec<DailyReviewCount>, StudyStatisticsError> {
+        let utc_offset_minutes = query.utc_offset_minutes;
+        Ok(self
+            .daily_answers(query)
+            .await?
+            .into_iter()
+            .map(|counts| DailyReviewCount {
+                day_start: local_day_start(counts.day, utc_offset_minutes),
+                cards: counts.distinct_cards,
+            })
+            .collect())
+    }
+
+    async fn score_distribution(
+        &self,
+        query: ProfileStatisticsQuery,
+    ) -> Result<Vec<ScoreBucket>, StudyStatisticsError> {
+        self.repository
+            .score_distribution(&query.user_id, &query.profile_id)
+            .await
+            .map_err(Self::map_repository_error)
+    }
+
+    async fn streak(&self, query: StudyStreakQuery) -> Result<StudyStreak, StudyStatisticsError> {
+        let today = local_day(self.clock.now_millis(), query.utc_offset_minutes);
+        let days = self
+            .daily_answers(StatisticsPeriodQuery {
+                user_id: query.user_id,
+                profile_id: query.profile_id,
+                since: 0,
+                utc_offset_minutes: query.utc_offset_minutes,
+            })
+            .await?
+            .into_iter()
+            .map(|counts| counts.day)
+            .collect::<Vec<_>>();
+        Ok(Self::streak_from_days(&days, today))
+    }
+
+    async fn hardest_cards(
+        &self,
+        query: HardestCardsQuery,
+    ) -> Result<Vec<HardCard>, StudyStatisticsError> {
+        if !(1..=MAX_HARDEST_CARDS).contains(&query.limit) {
+            return Err(StudyStatisticsError::InvalidQuery);
+        }
+        self.repository
+            .hardest_cards(&query.user_id, &query.profile_id, query.limit)
+            .await
+            .map_err(Self::map_repository_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streak_continues_until_a_full_day_is_missed() {
+        let days = [1, 2, 3, 7, 8];
+
+        assert_eq!(
+            StudyStatisticsService::streak_from_days(&days, 8),
+            StudyStreak {
+                current_days: 2,
+                longest_days: 3,
+                studied_today: true,
+            }
+        );
+        assert_eq!(
+            StudyStatisticsService::streak_from_days(&days, 9).current_days,
+            2
+        );
+        assert_eq!(
+            StudyStatisticsService::streak_from_days(&days, 10).current_days,
+            0
+        );
+        assert_eq!(
+            StudyStatisticsService::streak_from_days(&[], 10),
+            StudyStreak {
+                current_days: 0,
+                longest_days: 0,
+                studied_today: false,
+            }
+        );
+    }
+}
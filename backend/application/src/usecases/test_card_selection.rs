@@ -0,0 +1,92 @@
+use super::study_session_reducer::next_random;
+use crate::ports::input::card_catalog::models::{CardCandidate, CardOrder};
+
+/// Cards at or above this score get the smallest weight in a score-weighted draw; each point
+/// below it makes a card likelier, up to `MAX_SELECTION_WEIGHT`.
+const LIGHTEST_SCORE: i64 = 30;
+const MAX_SELECTION_WEIGHT: i64 = 60;
+
+/// Picks the next card of a test session from candidates listed in the repository order for
+/// `order`; random draws are made from `seed`.
+pub fn pick_test_card(
+    candidates: &[CardCandidate],
+    order: CardOrder,
+    seed: u64,
+) -> Option<&CardCandidate> {
+    let mut state = seed;
+    match order {
+        CardOrder::OldestFirst | CardOrder::WeakestFirst | CardOrder::LeastRecentlySeen => {
+            candidates.first()
+        }
+        CardOrder::Random => {
+            candidates.get(next_random(&mut state) as usize % candidates.len().max(1))
+        }
+        CardOrder::ScoreWeighted => {
+            let total = candidates
+                .iter()
+                .map(|candidate| selection_weight(candidate.score))
+                .sum::<u64>();
+            let mut draw = next_random(&mut state) % total.max(1);
+            candidates.iter().find(|candidate| {
+                let weight = selection_weight(candidate.score);
+                if draw < weight {
+                    return true;
+                }
+                draw -= weight;
+                false
+            })
+        }
+    }
+}
+
+fn selection_weight(score: i32) -> u64 {
+    (LIGHTEST_SCORE + 1 - i64::from(score)).clamp(1, MAX_SELECTION_WEIGHT) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::input::card_catalog::models::{CardDirection, CardId};
+
+    fn candidate(id: &str, score: i32) -> CardCandidate {
+        CardCandidate {
+            id: CardId::new(id),
+            direction: CardDirection::Straight,
+            score,
+            last_seen_at: None,
+        }
+    }
+
+    #[test]
+    fn ordered_picks_take_the_first_candidate() {
+        let candidates = [candidate("weak", -10), candidate("middle", 0)];
+        let pick =
+            |order| pick_test_card(&candidates, order, 7).map(|candidate| candidate.id.as_str());
+
+        assert_eq!(pick(CardOrder::WeakestFirst), Some("weak"));
+        assert_eq!(pick(CardOrder::LeastRecentlySeen), Some("weak"));
+        assert!(pick(CardOrder::Random).is_some());
+        assert_eq!(pick_test_card(&[], CardOrder::Random, 7), None);
+        assert_eq!(pick_test_card(&[], CardOrder::ScoreWeighted, 7), None);
+    }
+
+    #[test]
+    fn score_weighted_picks_favour_weak_cards_and_follow_the_seed() {
+        let candidates = [candidate("weak", -10), candidate("strong", 30)];
+        let pick = |seed| {
+            pick_test_card(&candidates, CardOrder::ScoreWeighted, seed)
+                .map(|candidate| candidate.id.as_str().to_string())
+        };
+
+        let weak_picks = (1..=1_000)
+            .filter(|seed| pick(seed * 0x9e37_79b9) == Some("weak".to_string()))
+            .count();
+
+        assert!(weak_picks > 900, "weak card picked {weak_picks} times");
+        assert!(weak_picks < 1_000, "strong card never picked");
+        assert_eq!(pick(42), pick(42));
+        assert_eq!(selection_weight(-100), 60);
+        assert_eq!(selection_weight(0), 31);
+        assert_eq!(selection_weight(45), 1);
+    }
+}
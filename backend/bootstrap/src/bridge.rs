@@ -6,20 +6,25 @@ use adapters::output::persistence::{
     SqliteLanguageProfileRepositoryInitError, SqlitePronunciationSettingsRepository,
     SqlitePronunciationSettingsRepositoryInitError, SqliteSpeechAudioRepository,
     SqliteSpeechAudioRepositoryInitError, SqliteStudySessionRepository,
-    SqliteStudySessionRepositoryInitError, SqliteUserRepository, SqliteUserRepositoryInitError,
+    SqliteStudySessionRepositoryInitError, SqliteStudyStatisticsRepository,
+    SqliteStudyStatisticsRepositoryInitError, SqliteUserRepository, SqliteUserRepositoryInitError,
+};
+use adapters::output::{
+    AiSpeechSynthesizer, AzurePronunciationAssessor, GenAiCardNormalizer, SystemClock,
+    SystemRandomSource,
 };
-use adapters::output::{AiSpeechSynthesizer, AzurePronunciationAssessor, GenAiCardNormalizer};
 use application::{
     ports::input::{
         ai_settings::AiSettingsUsecase, card_catalog::CardCatalogUsecase,
         card_normalization::CardNormalizationUsecase, card_speech::CardSpeechUsecase,
         language_profile::LanguageProfileUsecase, local_user::LocalUserUsecase,
         pronunciation_settings::PronunciationSettingsUsecase, study_session::StudySessionUsecase,
+        study_statistics::StudyStatisticsUsecase,
     },
     usecases::{
         AiSettingsService, CardCatalogService, CardNormalizationService, CardSpeechService,
         LanguageProfileService, LocalUserService, PronunciationSettingsService,
-        StudySessionService,
+        StudySessionService, StudyStatisticsService,
     },
 };
 use thiserror::Error;
@@ -36,6 +41,8 @@ pub enum BootstrapError {
     CardRepository(#[from] SqliteCardRepositoryInitError),
     #[error("failed to initialize the study session repository: {0}")]
     StudySessionRepository(#[from] SqliteStudySessionRepositoryInitError),
+    #[error("failed to initialize the study statistics repository: {0}")]
+    StudyStatisticsRepository(#[from] SqliteStudyStatisticsRepositoryInitError),
     #[error("failed to initialize the speech audio repository: {0}")]
     SpeechAudioRepository(#[from] SqliteSpeechAudioRepositoryInitError),
     #[error("failed to initialize the pronunciation settings repository: {0}")]
@@ -55,6 +62,7 @@ pub struct BootstrapBridge {
     ai_settings: Arc<dyn AiSettingsUsecase>,
     pronunciation_settings: Arc<dyn PronunciationSettingsUsecase>,
     study_sessions: Arc<dyn StudySessionUsecase>,
+    study_statistics: Arc<dyn StudyStatisticsUsecase>,
 }
 
 impl BootstrapBridge {
@@ -65,6 +73,8 @@ impl BootstrapBridge {
         let card_repository = Arc::new(SqliteCardRepository::new(&config.database_path)?);
         let study_session_repository =
             Arc::new(SqliteStudySessionRepository::new(&config.database_path)?);
+        let study_statistics_repository =
+            Arc::new(SqliteStudyStatisticsRepository::new(&config.database_path)?);
         let speech_audio_repository =
             Arc::new(SqliteSpeechAudioRepository::new(&config.database_path)?);
         let pronunciation_settings_repository = Arc::new(
@@ -72,13 +82,27 @@ impl BootstrapBridge {
         );
         let ai_settings_repository =
             Arc::new(SqliteAiSettingsRepository::new(&config.database_path)?);
+        let clock = Arc::new(SystemClock) as Arc<dyn application::ports::output::Clock>;
         let local_users = Arc::new(LocalUserService::new(user_repository));
         let language_profiles = Arc::new(LanguageProfileService::new(Arc::clone(
             &language_profile_repository,
         )
             as Arc<dyn application::ports::output::repository::LanguageProfileRepository>));
-        let cards = Arc::new(CardCatalogService::new(Arc::clone(&card_repository)
-            as Arc<dyn application::ports::output::repository::CardRepository>));
+        let cards = Arc::new(CardCatalogService::new(
+            Arc::clone(&card_repository)
+                as Arc<dyn application::ports::output::repository::CardRepository>,
+            Arc::clone(&clock),
+        ));
+        let card_speech = Arc::new(CardSpeechService::new(
+            Arc::clone(&language_profile_repository)
+                as Arc<dyn application::ports::output::repository::LanguageProfileRepository>,
+            Arc::clone(&ai_settings_repository)
+                as Arc<dyn application::ports::output::repository::AiSettingsRepository>,
+            Arc::clone(&card_repository)
+                as Arc<dyn application::ports::output::repository::CardRepository>,
+            speech_audio_repository,
+            Arc::new(AiSpeechSynthesizer::default()),
+        ));
         let study_sessions = Arc::new(StudySessionService::new(
             Arc::clone(&card_repository)
                 as Arc<dyn application::ports::output::repository::CardRepository>,
@@ -88,6 +112,14 @@ impl BootstrapBridge {
             Arc::clone(&pronunciation_settings_repository)
                 as Arc<dyn application::ports::output::repository::PronunciationSettingsRepository>,
             Arc::new(AzurePronunciationAssessor::default()),
+            Arc::clone(&card_speech) as Arc<dyn CardSpeechUsecase>,
+            Arc::clone(&clock),
+            Arc::new(SystemRandomSource::default()),
+            config.session_idle_expiry,
+        ));
+        let study_statistics = Arc::new(StudyStatisticsService::new(
+            study_statistics_repository,
+            clock,
         ));
         let pronunciation_settings = Arc::new(PronunciationSettingsService::new(
             pronunciation_settings_repository,
@@ -101,13 +133,6 @@ impl BootstrapBridge {
                 as Arc<dyn application::ports::output::repository::AiSettingsRepository>,
             Arc::new(GenAiCardNormalizer),
         ));
-        let card_speech = Arc::new(CardSpeechService::new(
-            language_profile_repository,
-            ai_settings_repository,
-            card_repository,
-            speech_audio_repository,
-            Arc::new(AiSpeechSynthesizer::default()),
-        ));
 
         Ok(Self {
             local_users,
@@ -118,6 +143,7 @@ impl BootstrapBridge {
             ai_settings,
             pronunciation_settings,
             study_sessions,
+            study_statistics,
         })
     }
 
@@ -152,6 +178,10 @@ impl BootstrapBridge {
     pub fn study_sessions(&self) -> Arc<dyn StudySessionUsecase> {
         Arc::clone(&self.study_sessions)
     }
+
+    pub fn study_statistics(&self) -> Arc<dyn StudyStatisticsUsecase> {
+        Arc::clone(&self.study_statistics)
+    }
 }
 
 #[cfg(test)]
@@ -1,22 +1,37 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
+
+/// Unfinished study sessions left untouched for this long are discarded.
+const DEFAULT_SESSION_IDLE_EXPIRY: Duration = Duration::from_secs(7 * 24 * 60 * 60);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BootstrapConfig {
     pub database_path: PathBuf,
+    pub session_idle_expiry: Duration,
 }
 
 impl BootstrapConfig {
     pub fn new(database_path: impl Into<PathBuf>) -> Self {
         Self {
             database_path: database_path.into(),
+            session_idle_expiry: DEFAULT_SESSION_IDLE_EXPIRY,
         }
     }
 
     pub fn from_env() -> Self {
-        std::env::var_os("LH_DATABASE_PATH")
+        let mut config = std::env::var_os("LH_DATABASE_PATH")
             .map(PathBuf::from)
             .map(Self::new)
-            .unwrap_or_default()
+            .unwrap_or_default();
+        // Values that do not parse or overflow keep the default expiry.
+        if let Some(expiry) = std::env::var("LH_SESSION_IDLE_EXPIRY_HOURS")
+            .ok()
+            .and_then(|hours| hours.parse::<u64>().ok())
+            .and_then(|hours| hours.checked_mul(60 * 60))
+            .map(Duration::from_secs)
+        {
+            config.session_idle_expiry = expiry;
+        }
+        config
     }
 }
 
@@ -2,4 +2,5 @@ pub mod cards;
 pub mod profiles;
 pub mod sessions;
 pub mod speech;
+pub mod statistics;
 pub mod users;
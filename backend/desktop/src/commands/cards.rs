@@ -2,10 +2,11 @@ use application::ports::input::{
     card_catalog::{
         CardCatalogUsecase,
         models::{
-            Card, CardChanges, CardDirection, CardId, CardListCursor, CardSortField,
-            CreateCardsCommand, DeleteCardsCommand, GetCardQuery, ListCardsQuery, Meaning, NewCard,
-            PendingInverseCard, PrepareInverseCardsQuery, SaveInverseCardsCommand, SortDirection,
-            UpdateCardCommand, UsageExample, Word,
+            Card, CardChanges, CardDirection, CardId, CardListCursor, CardSortField, CardStatus,
+            CreateCardsCommand, DeleteCardsCommand, GetCardQuery, LeechState, ListCardsQuery,
+            Meaning, NewCard, PendingInverseCard, PrepareInverseCardsQuery,
+            SaveInverseCardsCommand, SetCardStatusCommand, SortDirection, UpdateCardCommand,
+            UsageExample, Word,
         },
     },
     card_normalization::models::{CardNormalizationCommand, NormalizedCard},
@@ -60,6 +61,14 @@ pub struct CardDto {
     readings: Vec<String>,
     meanings: Vec<MeaningDto>,
     score: i32,
+    #[serde(default)]
+    consecutive_failures: u32,
+    #[serde(default)]
+    is_leech: bool,
+    #[serde(default)]
+    suspended: bool,
+    #[serde(default)]
+    buried_until: Option<i64>,
     created_at: i64,
     version: u64,
 }
@@ -71,6 +80,9 @@ pub struct CardSummaryDto {
     word: String,
     direction: String,
     score: i32,
+    is_leech: bool,
+    suspended: bool,
+    buried_until: Option<i64>,
     created_at: i64,
 }
 
@@ -90,6 +102,7 @@ pub struct ListCardsDto {
     direction: Option<String>,
     min_score: Option<i32>,
     max_score: Option<i32>,
+    leech: Option<bool>,
     sort_field: String,
     sort_direction: String,
     cursor: Option<String>,
@@ -114,6 +127,8 @@ pub struct UpdateCardDto {
     word: String,
     readings: Vec<String>,
     meanings: Vec<MeaningDto>,
+    #[serde(default)]
+    clear_leech: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -124,6 +139,23 @@ pub struct DeleteCardsDto {
     card_ids: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardStatusDto {
+    username: String,
+    profile_id: String,
+    card_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuryCardsDto {
+    username: String,
+    profile_id: String,
+    card_ids: Vec<String>,
+    until: i64,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PrepareInverseCardsDto {
@@ -164,6 +196,15 @@ fn direction_name(direction: CardDirection) -> &'static str {
     }
 }
 
+/// Splits a status into the `suspended` and `buriedUntil` fields of the DTOs.
+fn status_fields(status: CardStatus) -> (bool, Option<i64>) {
+    match status {
+        CardStatus::Active => (false, None),
+        CardStatus::Suspended => (true, None),
+        CardStatus::Buried { until } => (false, Some(until)),
+    }
+}
+
 fn map_meaning(meaning: MeaningDto) -> Meaning {
     Meaning {
         definition: meaning.definition,
@@ -226,6 +267,7 @@ pub async fn normalize_card(
 
 impl From<Card> for CardDto {
     fn from(card: Card) -> Self {
+        let (suspended, buried_until) = status_fields(card.status);
         Self {
             id: card.id.into_inner(),
             profile_id: card.profile_id.into_inner(),
@@ -234,6 +276,10 @@ impl From<Card> for CardDto {
             readings: card.word.readings,
             meanings: card.meanings.into_iter().map(map_meaning_dto).collect(),
             score: card.score,
+            consecutive_failures: card.leech.consecutive_failures,
+            is_leech: card.leech.is_leech,
+            suspended,
+            buried_until,
             created_at: card.created_at,
             version: card.version,
         }
@@ -251,6 +297,15 @@ fn map_card(dto: CardDto) -> Result<Card, CommandError> {
         },
         meanings: dto.meanings.into_iter().map(map_meaning).collect(),
         score: dto.score,
+        leech: LeechState {
+            consecutive_failures: dto.consecutive_failures,
+            is_leech: dto.is_leech,
+        },
+        status: match (dto.suspended, dto.buried_until) {
+            (true, _) => CardStatus::Suspended,
+            (false, Some(until)) => CardStatus::Buried { until },
+            (false, None) => CardStatus::Active,
+        },
         created_at: dto.created_at,
         version: dto.version,
     })
@@ -295,6 +350,7 @@ async fn browse_cards(
             direction,
             min_score: query.min_score,
             max_score: query.max_score,
+            leech: query.leech,
             sort_field,
             sort_direction,
             cursor: query.cursor.map(CardListCursor::new),
@@ -305,12 +361,18 @@ async fn browse_cards(
             items: page
                 .items
                 .into_iter()
-                .map(|card| CardSummaryDto {
-                    id: card.id.into_inner(),
-                    word: card.word,
-                    direction: direction_name(card.direction).to_string(),
-                    score: card.score,
-                    created_at: card.created_at,
+                .map(|card| {
+                    let (suspended, buried_until) = status_fields(card.status);
+                    CardSummaryDto {
+                        id: card.id.into_inner(),
+                        word: card.word,
+                        direction: direction_name(card.direction).to_string(),
+                        score: card.score,
+                        is_leech: card.leech.is_leech,
+                        suspended,
+                        buried_until,
+                        created_at: card.created_at,
+                    }
                 })
                 .collect(),
             next_cursor: page.next_cursor.map(|cursor| cursor.as_str().to_string()),
@@ -406,6 +468,7 @@ async fn change_card(
                     readings: command.readings,
                 }),
                 meanings: Some(command.meanings.into_iter().map(map_meaning).collect()),
+                clear_leech: command.clear_leech,
             },
         })
         .await
@@ -444,6 +507,72 @@ pub async fn delete_cards(
     remove_cards(state.cards().as_ref(), command).await
 }
 
+async fn change_status(
+    usecase: &dyn CardCatalogUsecase,
+    username: String,
+    profile_id: String,
+    card_ids: Vec<String>,
+    status: CardStatus,
+) -> Result<usize, CommandError> {
+    usecase
+        .set_card_status(SetCardStatusCommand {
+            user_id: UserId::new(username),
+            profile_id: ProfileId::new(profile_id),
+            card_ids: card_ids.into_iter().map(CardId::new).collect(),
+            status,
+        })
+        .await
+        .map(|result| result.updated_count)
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn suspend_cards(
+    state: State<'_, DesktopState>,
+    command: CardStatusDto,
+) -> Result<usize, CommandError> {
+    change_status(
+        state.cards().as_ref(),
+        command.username,
+        command.profile_id,
+        command.card_ids,
+        CardStatus::Suspended,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn unsuspend_cards(
+    state: State<'_, DesktopState>,
+    command: CardStatusDto,
+) -> Result<usize, CommandError> {
+    change_status(
+        state.cards().as_ref(),
+        command.username,
+        command.profile_id,
+        command.card_ids,
+        CardStatus::Active,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn bury_cards(
+    state: State<'_, DesktopState>,
+    command: BuryCardsDto,
+) -> Result<usize, CommandError> {
+    change_status(
+        state.cards().as_ref(),
+        command.username,
+        command.profile_id,
+        command.card_ids,
+        CardStatus::Buried {
+            until: command.until,
+        },
+    )
+    .await
+}
+
 async fn prepare_inverses(
     usecase: &dyn CardCatalogUsecase,
     query: PrepareInverseCardsDto,
@@ -587,6 +716,7 @@ mod tests {
                 direction: None,
                 min_score: None,
                 max_score: None,
+                leech: None,
                 sort_field: "createdAt".to_string(),
                 sort_direction: "descending".to_string(),
                 cursor: None,
@@ -633,6 +763,7 @@ mod tests {
                 word: "updated".to_string(),
                 readings: vec![],
                 meanings: vec![meaning("updated definition")],
+                clear_leech: false,
             },
         )
         .await
@@ -650,6 +781,42 @@ mod tests {
         .await
         .unwrap();
         assert_eq!(loaded.word, "updated");
+        let card_ids = created
+            .iter()
+            .map(|card| card.id.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            change_status(
+                reopened.cards().as_ref(),
+                "alice".to_string(),
+                profile.id.as_str().to_string(),
+                card_ids.clone(),
+                CardStatus::Suspended,
+            )
+            .await
+            .unwrap(),
+            2
+        );
+        let error = change_status(
+            reopened.cards().as_ref(),
+            "alice".to_string(),
+            profile.id.as_str().to_string(),
+            card_ids,
+            CardStatus::Buried { until: 0 },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.code(), "invalid_card");
+        let suspended = load_card(
+            reopened.cards().as_ref(),
+            "alice".to_string(),
+            profile.id.as_str().to_string(),
+            created[1].id.clone(),
+        )
+        .await
+        .unwrap();
+        assert!(suspended.suspended);
+        assert_eq!(suspended.buried_until, None);
         assert_eq!(
             remove_cards(
                 reopened.cards().as_ref(),
@@ -3,8 +3,9 @@ use application::ports::input::{
     language_profile::{
         LanguageProfileUsecase,
         models::{
-            CreateLanguageProfileCommand, DeleteLanguageProfileCommand, LanguageProfile,
-            LanguageProfileSummary, ListLanguageProfilesQuery, ProfileId,
+            CreateLanguageProfileCommand, DeleteLanguageProfileCommand, GetLanguageProfileQuery,
+            LanguageProfile, LanguageProfileChanges, LanguageProfileError, LanguageProfileSummary,
+            LeechAction, ListLanguageProfilesQuery, ProfileId, UpdateLanguageProfileCommand,
         },
     },
     local_user::models::UserId,
@@ -48,6 +49,38 @@ impl From<LanguageProfileSummary> for LanguageProfileDto {
     }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveLeechSettingsDto {
+    username: String,
+    profile_id: String,
+    version: u64,
+    leech_threshold: u32,
+    leech_action: String,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LeechSettingsDto {
+    version: u64,
+    leech_threshold: u32,
+    leech_action: String,
+}
+
+impl From<LanguageProfile> for LeechSettingsDto {
+    fn from(profile: LanguageProfile) -> Self {
+        Self {
+            version: profile.version,
+            leech_threshold: profile.leech_threshold,
+            leech_action: match profile.leech_action {
+                LeechAction::Suspend => "suspend",
+                LeechAction::Tag => "tag",
+            }
+            .to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SaveAiSettingsDto {
@@ -154,6 +187,46 @@ async fn remove_profile(
         .map_err(Into::into)
 }
 
+async fn load_leech_settings(
+    usecase: &dyn LanguageProfileUsecase,
+    username: String,
+    profile_id: String,
+) -> Result<LeechSettingsDto, CommandError> {
+    usecase
+        .get_profile(GetLanguageProfileQuery {
+            user_id: UserId::new(username),
+            profile_id: ProfileId::new(profile_id),
+        })
+        .await
+        .map(Into::into)
+        .map_err(CommandError::from)
+}
+
+async fn change_leech_settings(
+    usecase: &dyn LanguageProfileUsecase,
+    settings: SaveLeechSettingsDto,
+) -> Result<LeechSettingsDto, CommandError> {
+    let leech_action = match settings.leech_action.as_str() {
+        "suspend" => LeechAction::Suspend,
+        "tag" => LeechAction::Tag,
+        _ => return Err(LanguageProfileError::InvalidProfile.into()),
+    };
+    usecase
+        .update_profile(UpdateLanguageProfileCommand {
+            user_id: UserId::new(settings.username),
+            profile_id: ProfileId::new(settings.profile_id),
+            expected_version: settings.version,
+            changes: LanguageProfileChanges {
+                leech_threshold: Some(settings.leech_threshold),
+                leech_action: Some(leech_action),
+                ..LanguageProfileChanges::default()
+            },
+        })
+        .await
+        .map(Into::into)
+        .map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub async fn list_language_profiles(
     state: State<'_, DesktopState>,
@@ -223,6 +296,23 @@ pub async fn delete_language_profile(
     remove_profile(state.language_profiles().as_ref(), username, profile_id).await
 }
 
+#[tauri::command]
+pub async fn get_leech_settings(
+    state: State<'_, DesktopState>,
+    username: String,
+    profile_id: String,
+) -> Result<LeechSettingsDto, CommandError> {
+    load_leech_settings(state.language_profiles().as_ref(), username, profile_id).await
+}
+
+#[tauri::command]
+pub async fn save_leech_settings(
+    state: State<'_, DesktopState>,
+    settings: SaveLeechSettingsDto,
+) -> Result<LeechSettingsDto, CommandError> {
+    change_leech_settings(state.language_profiles().as_ref(), settings).await
+}
+
 #[tauri::command]
 pub async fn get_pronunciation_settings(
     state: State<'_, DesktopState>,
@@ -335,4 +425,66 @@ mod tests {
                 .is_empty()
         );
     }
+
+    #[tokio::test]
+    async fn leech_settings_default_to_suspension_and_can_be_changed() {
+        let directory = TempDir::new().unwrap();
+        let bridge =
+            BootstrapBridge::create(BootstrapConfig::new(directory.path().join("users.db")))
+                .unwrap();
+        bridge
+            .local_users()
+            .create_user(CreateLocalUserCommand {
+                username: "alice".to_string(),
+            })
+            .await
+            .unwrap();
+        let profile = add_profile(
+            bridge.language_profiles().as_ref(),
+            "alice".to_string(),
+            "Japanese".to_string(),
+            "en-US".to_string(),
+            "ja-JP".to_string(),
+        )
+        .await
+        .unwrap();
+        let profiles = bridge.language_profiles();
+        let settings =
+            load_leech_settings(profiles.as_ref(), "alice".to_string(), profile.id.clone())
+                .await
+                .unwrap();
+        assert_eq!(settings.leech_threshold, 8);
+        assert_eq!(settings.leech_action, "suspend");
+
+        let save = |leech_action: &str| SaveLeechSettingsDto {
+            username: "alice".to_string(),
+            profile_id: profile.id.clone(),
+            version: settings.version,
+            leech_threshold: 4,
+            leech_action: leech_action.to_string(),
+        };
+        assert!(
+            change_leech_settings(profiles.as_ref(), save("ignore"))
+                .await
+                .is_err()
+        );
+        let saved = change_leech_settings(profiles.as_ref(), save("tag"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            saved,
+            LeechSettingsDto {
+                version: settings.version + 1,
+                leech_threshold: 4,
+                leech_action: "tag".to_string(),
+            }
+        );
+        assert_eq!(
+            load_leech_settings(profiles.as_ref(), "alice".to_string(), profile.id)
+                .await
+                .unwrap(),
+            saved
+        );
+    }
 }
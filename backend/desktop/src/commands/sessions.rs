@@ -1,15 +1,18 @@
 use application::ports::input::{
-    card_catalog::models::CardDirection,
+    card_catalog::models::{CardDirection, CardOrder},
     language_profile::models::ProfileId,
     local_user::models::UserId,
     study_session::{
         StudySessionUsecase,
         models::{
-            ApplyStudySessionActionCommand, AssessPronunciationCommand, CreateStudySessionCommand,
-            CurrentCardView, EndStudySessionCommand, GetStudySessionPreferencesQuery,
+            AnswerFormat, AnswerNormalization, AnswerOutcome, AnswerStrictness,
+            ApplyStudySessionActionCommand, AssessPronunciationCommand, ClozePrompt,
+            CreateStudySessionCommand, CurrentCardView, EndStudySessionCommand,
+            GetStudySessionPreferencesQuery, ListActiveStudySessionsQuery,
             PronunciationAssessmentIssue, PronunciationAssessmentReport, PronunciationFeedbackKind,
-            SessionId, SetOutcome, StudySessionAction, StudySessionMode, StudySessionPhase,
-            StudySessionPreferences, StudySessionStatus, StudySessionTransition, StudySessionView,
+            ResumeStudySessionQuery, RevealedHint, SessionHint, SessionId, SetOutcome,
+            StudySessionAction, StudySessionMode, StudySessionPhase, StudySessionPreferences,
+            StudySessionStatus, StudySessionTransition, StudySessionView,
         },
     },
 };
@@ -29,8 +32,19 @@ pub struct CreateStudySessionDto {
     min_score: Option<i32>,
     max_score: Option<i32>,
     cards_per_set: Option<usize>,
+    retry_missed_only: Option<bool>,
+    new_cards_per_day: Option<usize>,
+    card_order: Option<String>,
+    recent_ban_window: Option<usize>,
+    max_cards: Option<usize>,
+    time_limit_minutes: Option<u32>,
+    correct_streak_goal: Option<usize>,
+    answer_format: Option<String>,
+    answer_strictness: Option<String>,
     pronunciation_check_enabled: bool,
     pronunciation_score_threshold: u8,
+    #[serde(default)]
+    utc_offset_minutes: i32,
 }
 
 #[derive(Debug, Serialize)]
@@ -40,6 +54,15 @@ pub struct StudySessionPreferencesDto {
     min_score: Option<i32>,
     max_score: Option<i32>,
     cards_per_set: Option<usize>,
+    retry_missed_only: Option<bool>,
+    new_cards_per_day: Option<usize>,
+    card_order: Option<String>,
+    recent_ban_window: Option<usize>,
+    max_cards: Option<usize>,
+    time_limit_minutes: Option<u32>,
+    correct_streak_goal: Option<usize>,
+    answer_format: String,
+    answer_strictness: String,
     pronunciation_check_enabled: bool,
     pronunciation_score_threshold: u8,
 }
@@ -51,6 +74,15 @@ impl From<StudySessionPreferences> for StudySessionPreferencesDto {
             min_score: preferences.min_score,
             max_score: preferences.max_score,
             cards_per_set: preferences.cards_per_set,
+            retry_missed_only: preferences.retry_missed_only,
+            new_cards_per_day: preferences.new_cards_per_day,
+            card_order: preferences.card_order.map(card_order_name),
+            recent_ban_window: preferences.recent_ban_window,
+            max_cards: preferences.max_cards,
+            time_limit_minutes: preferences.time_limit_minutes,
+            correct_streak_goal: preferences.correct_streak_goal,
+            answer_format: answer_format_name(preferences.answer_format),
+            answer_strictness: answer_strictness_name(preferences.answer_strictness),
             pronunciation_check_enabled: preferences.pronunciation_check_enabled,
             pronunciation_score_threshold: preferences.pronunciation_score_threshold,
         }
@@ -65,6 +97,8 @@ pub struct ApplyStudySessionActionDto {
     expected_version: u64,
     action: String,
     answer: Option<String>,
+    option_index: Option<usize>,
+    add_to_translations: Option<bool>,
     message: Option<String>,
 }
 
@@ -96,6 +130,58 @@ pub struct SessionCurrentCardDto {
     readings: Vec<String>,
     remaining_meanings: Option<usize>,
     total_meanings: Option<usize>,
+    options: Vec<String>,
+    cloze: Option<ClozePromptDto>,
+    hints: Vec<HintDto>,
+    hint_available: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HintDto {
+    kind: String,
+    text: String,
+}
+
+impl From<RevealedHint> for HintDto {
+    fn from(hint: RevealedHint) -> Self {
+        Self {
+            kind: match hint.hint {
+                SessionHint::FirstLetter => "firstLetter",
+                SessionHint::WordLength => "wordLength",
+                SessionHint::TranslatedDefinition => "translatedDefinition",
+                SessionHint::ExampleTranslation => "exampleTranslation",
+            }
+            .to_string(),
+            text: hint.text,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClozePromptDto {
+    sentence_before: String,
+    sentence_after: String,
+    translation: String,
+}
+
+impl From<ClozePrompt> for ClozePromptDto {
+    fn from(prompt: ClozePrompt) -> Self {
+        Self {
+            sentence_before: prompt.sentence_before,
+            sentence_after: prompt.sentence_after,
+            translation: prompt.translation,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemainingBudgetDto {
+    cards: Option<usize>,
+    time_millis: Option<u64>,
+    correct_in_a_row: Option<usize>,
 }
 
 #[derive(Debug, Serialize)]
@@ -112,6 +198,8 @@ pub struct StudySessionDto {
     id: String,
     profile_id: String,
     mode: String,
+    answer_format: String,
+    answer_strictness: String,
     phase: String,
     status: String,
     pronunciation_check_enabled: bool,
@@ -121,11 +209,16 @@ pub struct StudySessionDto {
     pronunciation_technical_failures: u8,
     pronunciation_disable_required: bool,
     awaiting_continue: bool,
+    retype_pending: bool,
     current_card: Option<SessionCurrentCardDto>,
+    due_today: Option<usize>,
     current_card_number: usize,
     total_cards: usize,
     current_set: usize,
     total_sets: usize,
+    current_set_card: usize,
+    set_cards: usize,
+    remaining_budget: Option<RemainingBudgetDto>,
     summary: SessionSummaryDto,
     version: u64,
 }
@@ -133,14 +226,19 @@ pub struct StudySessionDto {
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AnswerFeedbackDto {
+    outcome: String,
     is_correct: bool,
     matched_answer: Option<String>,
+    similarity_percent: u8,
     card: CardDto,
     matched_meaning_index: Option<usize>,
     completed_meaning_indices: Vec<usize>,
     card_completed: bool,
     remaining_meanings: usize,
     score_delta: i32,
+    misplaced_stress: bool,
+    normalizations: Vec<String>,
+    response_time_millis: Option<u64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -257,10 +355,96 @@ fn direction_name(direction: CardDirection) -> String {
     .to_string()
 }
 
+fn answer_format_name(format: AnswerFormat) -> String {
+    match format {
+        AnswerFormat::Written => "written",
+        AnswerFormat::MultipleChoice => "multipleChoice",
+        AnswerFormat::Dictation => "dictation",
+        AnswerFormat::Cloze => "cloze",
+        AnswerFormat::Reading => "reading",
+    }
+    .to_string()
+}
+
+fn answer_strictness_name(strictness: AnswerStrictness) -> String {
+    match strictness {
+        AnswerStrictness::Exact => "exact",
+        AnswerStrictness::Tolerant => "tolerant",
+        AnswerStrictness::Lenient => "lenient",
+    }
+    .to_string()
+}
+
+fn card_order_name(order: CardOrder) -> String {
+    match order {
+        CardOrder::OldestFirst => "oldestFirst",
+        CardOrder::Random => "random",
+        CardOrder::WeakestFirst => "weakestFirst",
+        CardOrder::ScoreWeighted => "scoreWeighted",
+        CardOrder::LeastRecentlySeen => "leastRecentlySeen",
+    }
+    .to_string()
+}
+
+fn normalization_name(normalization: AnswerNormalization) -> String {
+    match normalization {
+        AnswerNormalization::FoldYo => "foldYo",
+        AnswerNormalization::StripStressMarks => "stripStressMarks",
+        AnswerNormalization::FoldWidth => "foldWidth",
+        AnswerNormalization::FoldKana => "foldKana",
+        AnswerNormalization::IgnoreOptionalParts => "ignoreOptionalParts",
+        AnswerNormalization::IgnoreLeadingArticle => "ignoreLeadingArticle",
+    }
+    .to_string()
+}
+
+fn parse_answer_format(value: Option<String>) -> Result<AnswerFormat, CommandError> {
+    match value.as_deref() {
+        None | Some("written") => Ok(AnswerFormat::Written),
+        Some("multipleChoice") => Ok(AnswerFormat::MultipleChoice),
+        Some("dictation") => Ok(AnswerFormat::Dictation),
+        Some("cloze") => Ok(AnswerFormat::Cloze),
+        Some("reading") => Ok(AnswerFormat::Reading),
+        Some(_) => Err(
+            application::ports::input::study_session::models::StudySessionError::InvalidSession
+                .into(),
+        ),
+    }
+}
+
+fn parse_answer_strictness(value: Option<String>) -> Result<AnswerStrictness, CommandError> {
+    match value.as_deref() {
+        None | Some("tolerant") => Ok(AnswerStrictness::Tolerant),
+        Some("exact") => Ok(AnswerStrictness::Exact),
+        Some("lenient") => Ok(AnswerStrictness::Lenient),
+        Some(_) => Err(
+            application::ports::input::study_session::models::StudySessionError::InvalidSession
+                .into(),
+        ),
+    }
+}
+
+fn parse_card_order(value: Option<String>) -> Result<Option<CardOrder>, CommandError> {
+    value
+        .map(|value| match value.as_str() {
+            "oldestFirst" => Ok(CardOrder::OldestFirst),
+            "random" => Ok(CardOrder::Random),
+            "weakestFirst" => Ok(CardOrder::WeakestFirst),
+            "scoreWeighted" => Ok(CardOrder::ScoreWeighted),
+            "leastRecentlySeen" => Ok(CardOrder::LeastRecentlySeen),
+            _ => Err(
+                application::ports::input::study_session::models::StudySessionError::InvalidSession
+                    .into(),
+            ),
+        })
+        .transpose()
+}
+
 fn parse_mode(value: &str) -> Result<StudySessionMode, CommandError> {
     match value {
         "learning" => Ok(StudySessionMode::Learning),
         "test" => Ok(StudySessionMode::Test),
+        "review" => Ok(StudySessionMode::Review),
         _ => Err(
             application::ports::input::study_session::models::StudySessionError::InvalidSession
                 .into(),
@@ -280,6 +464,10 @@ impl From<StudySessionView> for StudySessionDto {
                 readings: Vec::new(),
                 remaining_meanings: None,
                 total_meanings: None,
+                options: Vec::new(),
+                cloze: None,
+                hints: Vec::new(),
+                hint_available: false,
             },
             CurrentCardView::Test {
                 id,
@@ -288,15 +476,23 @@ impl From<StudySessionView> for StudySessionDto {
                 readings,
                 remaining_meanings,
                 total_meanings,
+                options,
+                cloze,
+                hints,
+                hint_available,
             } => SessionCurrentCardDto {
                 kind: "test".to_string(),
                 card: None,
                 id: Some(id.into_inner()),
                 direction: Some(direction_name(direction)),
-                prompt: Some(prompt),
+                prompt,
                 readings,
                 remaining_meanings: Some(remaining_meanings),
                 total_meanings: Some(total_meanings),
+                options,
+                cloze: cloze.map(Into::into),
+                hints: hints.into_iter().map(Into::into).collect(),
+                hint_available,
             },
         });
         Self {
@@ -305,8 +501,11 @@ impl From<StudySessionView> for StudySessionDto {
             mode: match view.mode {
                 StudySessionMode::Learning => "learning",
                 StudySessionMode::Test => "test",
+                StudySessionMode::Review => "review",
             }
             .to_string(),
+            answer_format: answer_format_name(view.answer_format),
+            answer_strictness: answer_strictness_name(view.answer_strictness),
             phase: match view.phase {
                 StudySessionPhase::Study => "study",
                 StudySessionPhase::Test => "test",
@@ -325,11 +524,20 @@ impl From<StudySessionView> for StudySessionDto {
             pronunciation_technical_failures: view.pronunciation_technical_failures,
             pronunciation_disable_required: view.pronunciation_disable_required,
             awaiting_continue: view.awaiting_continue,
+            retype_pending: view.retype_pending,
             current_card,
+            due_today: view.due_today,
             current_card_number: view.progress.current_card,
             total_cards: view.progress.total_cards,
             current_set: view.progress.current_set,
             total_sets: view.progress.total_sets,
+            current_set_card: view.progress.current_set_card,
+            set_cards: view.progress.set_cards,
+            remaining_budget: view.remaining_budget.map(|budget| RemainingBudgetDto {
+                cards: budget.cards,
+                time_millis: budget.time_millis,
+                correct_in_a_row: budget.correct_in_a_row,
+            }),
             summary: SessionSummaryDto {
                 correct: view.summary.correct,
                 incorrect: view.summary.incorrect,
@@ -347,14 +555,29 @@ impl From<StudySessionTransition> for StudySessionTransitionDto {
             answer_feedback: transition
                 .answer_feedback
                 .map(|feedback| AnswerFeedbackDto {
+                    outcome: match feedback.outcome {
+                        AnswerOutcome::Correct => "correct",
+                        AnswerOutcome::AlmostCorrect => "almostCorrect",
+                        AnswerOutcome::Incorrect => "incorrect",
+                        AnswerOutcome::GaveUp => "gaveUp",
+                    }
+                    .to_string(),
                     is_correct: feedback.is_correct,
                     matched_answer: feedback.matched_answer,
+                    similarity_percent: feedback.similarity_percent,
                     card: feedback.card.into(),
                     matched_meaning_index: feedback.matched_meaning_index,
                     completed_meaning_indices: feedback.completed_meaning_indices,
                     card_completed: feedback.card_completed,
                     remaining_meanings: feedback.remaining_meanings,
                     score_delta: feedback.score_delta,
+                    misplaced_stress: feedback.misplaced_stress,
+                    normalizations: feedback
+                        .normalizations
+                        .into_iter()
+                        .map(normalization_name)
+                        .collect(),
+                    response_time_millis: feedback.response_time_millis,
                 }),
             pronunciation_feedback: transition.pronunciation_feedback.map(|feedback| {
                 PronunciationFeedbackDto {
@@ -398,8 +621,18 @@ async fn create(
             min_score: command.min_score,
             max_score: command.max_score,
             cards_per_set: command.cards_per_set,
+            retry_missed_only: command.retry_missed_only,
+            new_cards_per_day: command.new_cards_per_day,
+            card_order: parse_card_order(command.card_order)?,
+            recent_ban_window: command.recent_ban_window,
+            max_cards: command.max_cards,
+            time_limit_minutes: command.time_limit_minutes,
+            correct_streak_goal: command.correct_streak_goal,
+            answer_format: parse_answer_format(command.answer_format)?,
+            answer_strictness: parse_answer_strictness(command.answer_strictness)?,
             pronunciation_check_enabled: command.pronunciation_check_enabled,
             pronunciation_score_threshold: command.pronunciation_score_threshold,
+            utc_offset_minutes: command.utc_offset_minutes,
         })
         .await
         .map(Into::into)
@@ -433,6 +666,40 @@ pub async fn create_study_session(
     create(state.study_sessions().as_ref(), command).await
 }
 
+#[tauri::command]
+pub async fn list_active_study_sessions(
+    state: State<'_, DesktopState>,
+    username: String,
+    profile_id: String,
+) -> Result<Vec<StudySessionDto>, CommandError> {
+    state
+        .study_sessions()
+        .list_active_sessions(ListActiveStudySessionsQuery {
+            user_id: UserId::new(username),
+            profile_id: ProfileId::new(profile_id),
+        })
+        .await
+        .map(|sessions| sessions.into_iter().map(Into::into).collect())
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn resume_study_session(
+    state: State<'_, DesktopState>,
+    username: String,
+    session_id: String,
+) -> Result<StudySessionDto, CommandError> {
+    state
+        .study_sessions()
+        .resume_session(ResumeStudySessionQuery {
+            user_id: UserId::new(username),
+            session_id: SessionId::new(session_id),
+        })
+        .await
+        .map(Into::into)
+        .map_err(Into::into)
+}
+
 #[tauri::command]
 pub async fn apply_study_session_action(
     state: State<'_, DesktopState>,
@@ -443,6 +710,12 @@ pub async fn apply_study_session_action(
         "nextStudyCard" => StudySessionAction::NextStudyCard,
         "startMiniTest" => StudySessionAction::StartMiniTest,
         "continueAfterFeedback" => StudySessionAction::ContinueAfterFeedback,
+        "giveUp" => StudySessionAction::GiveUp,
+        "revealHint" => StudySessionAction::RevealHint,
+        "undoLastAction" => StudySessionAction::UndoLastAction,
+        "acceptLastAnswer" => StudySessionAction::AcceptLastAnswer {
+            add_to_translations: command.add_to_translations.unwrap_or(false),
+        },
         "registerPronunciationCaptureFailure" => {
             StudySessionAction::RegisterPronunciationCaptureFailure {
                 message: command.message.unwrap_or_else(|| {
@@ -456,6 +729,11 @@ pub async fn apply_study_session_action(
                 application::ports::input::study_session::models::StudySessionError::InvalidSession,
             )?,
         },
+        "selectAnswerOption" => StudySessionAction::SelectAnswerOption {
+            index: command.option_index.ok_or(
+                application::ports::input::study_session::models::StudySessionError::InvalidSession,
+            )?,
+        },
         _ => {
             return Err(
                 application::ports::input::study_session::models::StudySessionError::InvalidSession
@@ -532,9 +810,13 @@ pub async fn cancel_study_session(
 mod tests {
     use application::ports::input::{
         card_catalog::models::{
-            CardDirection, CreateCardsCommand, GetCardQuery, Meaning, NewCard, UsageExample, Word,
+            CardDirection, CardStatus, CreateCardsCommand, DeleteCardsCommand, GetCardQuery,
+            Meaning, NewCard, SetCardStatusCommand, UsageExample, Word,
+        },
+        language_profile::models::{
+            CreateLanguageProfileCommand, LanguageProfileChanges, LeechAction,
+            UpdateLanguageProfileCommand,
         },
-        language_profile::models::CreateLanguageProfileCommand,
         local_user::models::CreateLocalUserCommand,
         pronunciation_settings::models::SavePronunciationSettingsCommand,
         study_session::models::{
@@ -638,8 +920,18 @@ mod tests {
                 min_score: Some(-3),
                 max_score: Some(7),
                 cards_per_set: None,
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
                 pronunciation_check_enabled: false,
                 pronunciation_score_threshold: 82,
+                utc_offset_minutes: 0,
             })
             .await
             .unwrap();
@@ -697,8 +989,18 @@ mod tests {
                 min_score: None,
                 max_score: None,
                 cards_per_set: None,
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
                 pronunciation_check_enabled: false,
                 pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
             })
             .await
             .unwrap();
@@ -710,175 +1012,1866 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn learning_retries_a_failed_set_without_changing_score() {
+    async fn review_session_ends_with_its_due_queue_and_caps_new_cards_per_day() {
         let directory = TempDir::new().unwrap();
-        let path = directory.path().join("learning.db");
+        let path = directory.path().join("review.db");
         let (bridge, profile_id) = populated_bridge(&path).await;
+        let command = CreateStudySessionCommand {
+            user_id: UserId::new("alice"),
+            profile_id: ProfileId::new(&profile_id),
+            mode: StudySessionMode::Review,
+            direction: None,
+            min_score: None,
+            max_score: None,
+            cards_per_set: None,
+            retry_missed_only: None,
+            new_cards_per_day: Some(1),
+            card_order: None,
+            recent_ban_window: None,
+            max_cards: None,
+            time_limit_minutes: None,
+            correct_streak_goal: None,
+            answer_format: AnswerFormat::Written,
+            answer_strictness: AnswerStrictness::Tolerant,
+            pronunciation_check_enabled: false,
+            pronunciation_score_threshold: 75,
+            utc_offset_minutes: 0,
+        };
         let session = bridge
             .study_sessions()
-            .create_session(CreateStudySessionCommand {
-                user_id: UserId::new("alice"),
-                profile_id: ProfileId::new(profile_id),
-                mode: StudySessionMode::Learning,
-                direction: None,
-                min_score: None,
-                max_score: None,
-                cards_per_set: Some(1),
-                pronunciation_check_enabled: false,
-                pronunciation_score_threshold: 75,
-            })
+            .create_session(command.clone())
             .await
             .unwrap();
-        let testing = bridge
+        assert_eq!(session.due_today, Some(1));
+        assert_eq!(session.progress.total_cards, 1);
+        let prompt = match session.current_card.unwrap() {
+            CurrentCardView::Test { prompt, .. } => prompt.unwrap(),
+            _ => panic!("expected a test card"),
+        };
+        let answered = bridge
             .study_sessions()
             .apply_action(ApplyStudySessionActionCommand {
                 user_id: UserId::new("alice"),
                 session_id: session.id.clone(),
                 expected_version: session.version,
-                action: StudySessionAction::StartMiniTest,
-            })
-            .await
-            .unwrap()
-            .session;
-        let failed = bridge
-            .study_sessions()
-            .apply_action(ApplyStudySessionActionCommand {
-                user_id: UserId::new("alice"),
-                session_id: session.id.clone(),
-                expected_version: testing.version,
                 action: StudySessionAction::SubmitWrittenAnswer {
-                    answer: "wrong".to_string(),
+                    answer: format!("{prompt}-translation"),
                 },
             })
             .await
-            .unwrap()
-            .session;
-        let retry = bridge
+            .unwrap();
+        assert_eq!(answered.answer_feedback.unwrap().score_delta, 1);
+        assert_eq!(answered.session.due_today, Some(0));
+        let finished = bridge
             .study_sessions()
             .apply_action(ApplyStudySessionActionCommand {
                 user_id: UserId::new("alice"),
                 session_id: session.id,
-                expected_version: failed.version,
+                expected_version: answered.session.version,
                 action: StudySessionAction::ContinueAfterFeedback,
             })
             .await
-            .unwrap();
-        assert_eq!(retry.set_outcome, Some(SetOutcome::Retry));
-        assert_eq!(retry.session.phase, StudySessionPhase::Study);
-        assert_eq!(retry.session.summary.score_delta, 0);
-    }
+            .unwrap()
+            .session;
+        assert_eq!(finished.status, StudySessionStatus::Completed);
+        assert!(finished.current_card.is_none());
 
-    #[tokio::test]
-    async fn reverse_cards_skip_pronunciation_even_when_any_direction_is_enabled() {
-        let directory = TempDir::new().unwrap();
-        let path = directory.path().join("reverse-pronunciation.db");
-        let (bridge, _) = populated_bridge(&path).await;
-        bridge
-            .pronunciation_settings()
-            .save_settings(SavePronunciationSettingsCommand {
-                user_id: UserId::new("alice"),
-                expected_version: 0,
-                endpoint: Some("https://example.cognitiveservices.azure.com".to_string()),
-                subscription_key: Some("secret".to_string()),
-            })
-            .await
-            .unwrap();
-        let profile = bridge
-            .language_profiles()
-            .create_profile(CreateLanguageProfileCommand {
+        assert_eq!(
+            bridge
+                .study_sessions()
+                .create_session(command.clone())
+                .await,
+            Err(StudySessionError::NoCardsAvailable)
+        );
+        assert_eq!(
+            bridge
+                .study_sessions()
+                .create_session(CreateStudySessionCommand {
+                    utc_offset_minutes: 15 * 60,
+                    ..command.clone()
+                })
+                .await,
+            Err(StudySessionError::InvalidSession)
+        );
+        let preferences = bridge
+            .study_sessions()
+            .get_preferences(GetStudySessionPreferencesQuery {
                 user_id: UserId::new("alice"),
-                name: "Reverse only".to_string(),
-                source_language: "en-US".to_string(),
-                target_language: "ja-JP".to_string(),
+                profile_id: ProfileId::new(&profile_id),
+                mode: StudySessionMode::Review,
             })
             .await
             .unwrap();
-        bridge
-            .cards()
-            .create_cards(CreateCardsCommand {
-                user_id: UserId::new("alice"),
-                profile_id: profile.id.clone(),
-                cards: vec![NewCard {
-                    direction: CardDirection::Reverse,
-                    word: Word {
-                        text: "слово".to_string(),
-                        readings: Vec::new(),
-                    },
-                    meanings: vec![Meaning {
-                        definition: "definition".to_string(),
-                        translated_definition: "определение".to_string(),
-                        word_translations: vec!["answer".to_string()],
-                        examples: Vec::new(),
-                    }],
-                }],
+        assert_eq!(preferences.new_cards_per_day, Some(1));
+        let raised = bridge
+            .study_sessions()
+            .create_session(CreateStudySessionCommand {
+                new_cards_per_day: Some(2),
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
+                ..command
             })
             .await
             .unwrap();
+        assert_eq!(raised.due_today, Some(1));
+    }
 
+    #[tokio::test]
+    async fn accepting_a_rejected_answer_restores_the_score_and_learns_the_synonym() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("accept.db");
+        let (bridge, profile_id) = populated_bridge(&path).await;
         let session = bridge
             .study_sessions()
             .create_session(CreateStudySessionCommand {
                 user_id: UserId::new("alice"),
-                profile_id: profile.id.clone(),
+                profile_id: ProfileId::new(&profile_id),
                 mode: StudySessionMode::Test,
                 direction: None,
                 min_score: None,
                 max_score: None,
                 cards_per_set: None,
-                pronunciation_check_enabled: true,
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
+                pronunciation_check_enabled: false,
                 pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
             })
             .await
             .unwrap();
-        assert!(session.pronunciation_check_enabled);
-        assert!(!session.pronunciation_required);
-
-        let assessment_error = bridge
+        let card_id = match session.current_card.clone().unwrap() {
+            CurrentCardView::Test { id, .. } => id,
+            _ => panic!("expected a test card"),
+        };
+        let action = |session: &StudySessionView, action| ApplyStudySessionActionCommand {
+            user_id: UserId::new("alice"),
+            session_id: session.id.clone(),
+            expected_version: session.version,
+            action,
+        };
+        let rejected = bridge
             .study_sessions()
-            .assess_pronunciation(AssessPronunciationCommand {
-                user_id: UserId::new("alice"),
-                session_id: session.id.clone(),
-                expected_version: session.version,
-                audio: vec![0; 44],
-            })
+            .apply_action(action(
+                &session,
+                StudySessionAction::SubmitWrittenAnswer {
+                    answer: " greeting ".to_string(),
+                },
+            ))
             .await
-            .unwrap_err();
-        assert_eq!(assessment_error, StudySessionError::InvalidAction);
+            .unwrap();
+        assert!(!rejected.answer_feedback.unwrap().is_correct);
 
-        let answered = bridge
+        let accepted = bridge
             .study_sessions()
-            .apply_action(ApplyStudySessionActionCommand {
-                user_id: UserId::new("alice"),
-                session_id: session.id,
-                expected_version: session.version,
-                action: StudySessionAction::SubmitWrittenAnswer {
-                    answer: "answer".to_string(),
+            .apply_action(action(
+                &rejected.session,
+                StudySessionAction::AcceptLastAnswer {
+                    add_to_translations: true,
                 },
-            })
+            ))
             .await
             .unwrap();
-        let feedback = answered.answer_feedback.unwrap();
-        assert!(feedback.is_correct);
+        let feedback = accepted.answer_feedback.unwrap();
+        assert_eq!(feedback.outcome, AnswerOutcome::Correct);
+        assert_eq!(feedback.score_delta, 1);
         assert_eq!(feedback.matched_meaning_index, Some(0));
-        assert_eq!(feedback.completed_meaning_indices, vec![0]);
+        assert_eq!(accepted.session.summary.correct, 1);
+        assert_eq!(accepted.session.summary.incorrect, 0);
+        assert_eq!(accepted.session.summary.score_delta, 1);
+        let card = bridge
+            .cards()
+            .get_card(GetCardQuery {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new(&profile_id),
+                card_id,
+            })
+            .await
+            .unwrap();
+        assert_eq!(card.score, 1);
+        assert_eq!(card.meanings[0].word_translations.len(), 2);
+        assert_eq!(card.meanings[0].word_translations[1], "greeting");
 
-        let reverse_only = bridge
+        assert_eq!(
+            bridge
+                .study_sessions()
+                .apply_action(action(
+                    &accepted.session,
+                    StudySessionAction::AcceptLastAnswer {
+                        add_to_translations: false,
+                    },
+                ))
+                .await
+                .unwrap_err(),
+            StudySessionError::InvalidAction
+        );
+    }
+
+    #[tokio::test]
+    async fn undo_takes_back_a_mistyped_answer_and_its_score() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("undo.db");
+        let (bridge, profile_id) = populated_bridge(&path).await;
+        let session = bridge
             .study_sessions()
             .create_session(CreateStudySessionCommand {
                 user_id: UserId::new("alice"),
-                profile_id: profile.id,
+                profile_id: ProfileId::new(&profile_id),
                 mode: StudySessionMode::Test,
-                direction: Some(CardDirection::Reverse),
+                direction: None,
                 min_score: None,
                 max_score: None,
                 cards_per_set: None,
-                pronunciation_check_enabled: true,
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
+                pronunciation_check_enabled: false,
                 pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
             })
             .await
             .unwrap();
-        assert!(!reverse_only.pronunciation_check_enabled);
-        assert!(!reverse_only.pronunciation_required);
+        let card_id = match session.current_card.clone().unwrap() {
+            CurrentCardView::Test { id, .. } => id,
+            _ => panic!("expected a test card"),
+        };
+        let card_score = || async {
+            bridge
+                .cards()
+                .get_card(GetCardQuery {
+                    user_id: UserId::new("alice"),
+                    profile_id: ProfileId::new(&profile_id),
+                    card_id: card_id.clone(),
+                })
+                .await
+                .unwrap()
+                .score
+        };
+        let action = |session: &StudySessionView, action| ApplyStudySessionActionCommand {
+            user_id: UserId::new("alice"),
+            session_id: session.id.clone(),
+            expected_version: session.version,
+            action,
+        };
+        let answer = |answer: &str| StudySessionAction::SubmitWrittenAnswer {
+            answer: answer.to_string(),
+        };
+
+        assert_eq!(
+            bridge
+                .study_sessions()
+                .apply_action(action(&session, StudySessionAction::UndoLastAction))
+                .await
+                .unwrap_err(),
+            StudySessionError::InvalidAction
+        );
+        let rejected = bridge
+            .study_sessions()
+            .apply_action(action(&session, answer("h")))
+            .await
+            .unwrap();
+        assert_eq!(card_score().await, -2);
+        let continued = bridge
+            .study_sessions()
+            .apply_action(action(
+                &rejected.session,
+                StudySessionAction::ContinueAfterFeedback,
+            ))
+            .await
+            .unwrap();
+
+        let back_to_feedback = bridge
+            .study_sessions()
+            .apply_action(action(
+                &continued.session,
+                StudySessionAction::UndoLastAction,
+            ))
+            .await
+            .unwrap();
+        assert!(back_to_feedback.session.awaiting_continue);
+        assert_eq!(
+            back_to_feedback.session.version,
+            continued.session.version + 1
+        );
+        let undone = bridge
+            .study_sessions()
+            .apply_action(action(
+                &back_to_feedback.session,
+                StudySessionAction::UndoLastAction,
+            ))
+            .await
+            .unwrap();
+        assert!(!undone.session.awaiting_continue);
+        assert_eq!(undone.session.current_card, session.current_card);
+        assert_eq!(undone.session.summary.incorrect, 0);
+        assert_eq!(card_score().await, 0);
+
+        let word = match undone.session.current_card.clone().unwrap() {
+            CurrentCardView::Test { prompt, .. } => prompt.unwrap(),
+            _ => panic!("expected a test card"),
+        };
+        let retried = bridge
+            .study_sessions()
+            .apply_action(action(
+                &undone.session,
+                answer(&format!("{word}-translation")),
+            ))
+            .await
+            .unwrap();
+        assert!(retried.answer_feedback.unwrap().is_correct);
+        assert_eq!(card_score().await, 1);
+    }
+
+    #[tokio::test]
+    async fn learning_retries_a_failed_set_without_changing_score() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("learning.db");
+        let (bridge, profile_id) = populated_bridge(&path).await;
+        let session = bridge
+            .study_sessions()
+            .create_session(CreateStudySessionCommand {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new(profile_id),
+                mode: StudySessionMode::Learning,
+                direction: None,
+                min_score: None,
+                max_score: None,
+                cards_per_set: Some(1),
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
+                pronunciation_check_enabled: false,
+                pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
+            })
+            .await
+            .unwrap();
+        let testing = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id.clone(),
+                expected_version: session.version,
+                action: StudySessionAction::StartMiniTest,
+            })
+            .await
+            .unwrap()
+            .session;
+        let failed = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id.clone(),
+                expected_version: testing.version,
+                action: StudySessionAction::SubmitWrittenAnswer {
+                    answer: "wrong".to_string(),
+                },
+            })
+            .await
+            .unwrap()
+            .session;
+        let retry = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id,
+                expected_version: failed.version,
+                action: StudySessionAction::ContinueAfterFeedback,
+            })
+            .await
+            .unwrap();
+        assert_eq!(retry.set_outcome, Some(SetOutcome::Retry));
+        assert_eq!(retry.session.phase, StudySessionPhase::Study);
+        assert_eq!(retry.session.summary.score_delta, 0);
+    }
+
+    #[tokio::test]
+    async fn learning_can_retry_only_the_missed_cards_of_a_set() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("retry.db");
+        let (bridge, profile_id) = populated_bridge(&path).await;
+        let usecase = bridge.study_sessions();
+        let apply = |session: &StudySessionView, action| {
+            usecase.apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id.clone(),
+                expected_version: session.version,
+                action,
+            })
+        };
+        let session = usecase
+            .create_session(CreateStudySessionCommand {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new(profile_id),
+                mode: StudySessionMode::Learning,
+                direction: None,
+                min_score: None,
+                max_score: None,
+                cards_per_set: Some(2),
+                retry_missed_only: Some(true),
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
+                pronunciation_check_enabled: false,
+                pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
+            })
+            .await
+            .unwrap();
+        let mut transition = apply(&session, StudySessionAction::StartMiniTest)
+            .await
+            .unwrap();
+        assert_eq!(transition.session.progress.current_set_card, 1);
+        assert_eq!(transition.session.progress.set_cards, 2);
+        let mut missed = None;
+        for answer_correctly in [true, false] {
+            let Some(CurrentCardView::Test { id, prompt, .. }) =
+                transition.session.current_card.clone()
+            else {
+                panic!("expected a test card");
+            };
+            let answer = if answer_correctly {
+                format!("{}-translation", prompt.unwrap())
+            } else {
+                missed = Some(id);
+                "wrong".to_string()
+            };
+            let answered = apply(
+                &transition.session,
+                StudySessionAction::SubmitWrittenAnswer { answer },
+            )
+            .await
+            .unwrap();
+            transition = apply(&answered.session, StudySessionAction::ContinueAfterFeedback)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(transition.set_outcome, Some(SetOutcome::Retry));
+        let retry = transition.session;
+        assert_eq!(retry.phase, StudySessionPhase::Study);
+        assert_eq!(retry.progress.current_card, 2);
+        assert_eq!(retry.progress.current_set_card, 1);
+        assert_eq!(retry.progress.set_cards, 1);
+        let Some(CurrentCardView::Study(card)) = &retry.current_card else {
+            panic!("expected a study card");
+        };
+        assert_eq!(Some(&card.id), missed.as_ref());
+
+        let testing = apply(&retry, StudySessionAction::StartMiniTest)
+            .await
+            .unwrap()
+            .session;
+        assert_eq!(testing.progress.set_cards, 1);
+        let answered = apply(
+            &testing,
+            StudySessionAction::SubmitWrittenAnswer {
+                answer: format!("{}-translation", card.word.text),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(answered.answer_feedback.unwrap().is_correct);
+        let passed = apply(&answered.session, StudySessionAction::ContinueAfterFeedback)
+            .await
+            .unwrap();
+        assert_eq!(passed.set_outcome, Some(SetOutcome::Passed));
+        assert_eq!(passed.session.status, StudySessionStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn reverse_cards_skip_pronunciation_even_when_any_direction_is_enabled() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("reverse-pronunciation.db");
+        let (bridge, _) = populated_bridge(&path).await;
+        bridge
+            .pronunciation_settings()
+            .save_settings(SavePronunciationSettingsCommand {
+                user_id: UserId::new("alice"),
+                expected_version: 0,
+                endpoint: Some("https://example.cognitiveservices.azure.com".to_string()),
+                subscription_key: Some("secret".to_string()),
+            })
+            .await
+            .unwrap();
+        let profile = bridge
+            .language_profiles()
+            .create_profile(CreateLanguageProfileCommand {
+                user_id: UserId::new("alice"),
+                name: "Reverse only".to_string(),
+                source_language: "en-US".to_string(),
+                target_language: "ja-JP".to_string(),
+            })
+            .await
+            .unwrap();
+        bridge
+            .cards()
+            .create_cards(CreateCardsCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id.clone(),
+                cards: vec![NewCard {
+                    direction: CardDirection::Reverse,
+                    word: Word {
+                        text: "слово".to_string(),
+                        readings: Vec::new(),
+                    },
+                    meanings: vec![Meaning {
+                        definition: "definition".to_string(),
+                        translated_definition: "определение".to_string(),
+                        word_translations: vec!["answer".to_string()],
+                        examples: Vec::new(),
+                    }],
+                }],
+            })
+            .await
+            .unwrap();
+
+        let session = bridge
+            .study_sessions()
+            .create_session(CreateStudySessionCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id.clone(),
+                mode: StudySessionMode::Test,
+                direction: None,
+                min_score: None,
+                max_score: None,
+                cards_per_set: None,
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
+                pronunciation_check_enabled: true,
+                pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
+            })
+            .await
+            .unwrap();
+        assert!(session.pronunciation_check_enabled);
+        assert!(!session.pronunciation_required);
+
+        let assessment_error = bridge
+            .study_sessions()
+            .assess_pronunciation(AssessPronunciationCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id.clone(),
+                expected_version: session.version,
+                audio: vec![0; 44],
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(assessment_error, StudySessionError::InvalidAction);
+
+        let answered = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id,
+                expected_version: session.version,
+                action: StudySessionAction::SubmitWrittenAnswer {
+                    answer: "answer".to_string(),
+                },
+            })
+            .await
+            .unwrap();
+        let feedback = answered.answer_feedback.unwrap();
+        assert!(feedback.is_correct);
+        assert_eq!(feedback.matched_meaning_index, Some(0));
+        assert_eq!(feedback.completed_meaning_indices, vec![0]);
+
+        let reverse_only = bridge
+            .study_sessions()
+            .create_session(CreateStudySessionCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id,
+                mode: StudySessionMode::Test,
+                direction: Some(CardDirection::Reverse),
+                min_score: None,
+                max_score: None,
+                cards_per_set: None,
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
+                pronunciation_check_enabled: true,
+                pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
+            })
+            .await
+            .unwrap();
+        assert!(!reverse_only.pronunciation_check_enabled);
+        assert!(!reverse_only.pronunciation_required);
+    }
+
+    #[tokio::test]
+    async fn active_sessions_survive_a_restart_and_skip_deleted_cards() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("resume.db");
+        let (bridge, profile_id) = populated_bridge(&path).await;
+        let session = bridge
+            .study_sessions()
+            .create_session(CreateStudySessionCommand {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new(&profile_id),
+                mode: StudySessionMode::Learning,
+                direction: None,
+                min_score: None,
+                max_score: None,
+                cards_per_set: Some(5),
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
+                pronunciation_check_enabled: false,
+                pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
+            })
+            .await
+            .unwrap();
+        let Some(CurrentCardView::Study(first)) = session.current_card else {
+            panic!("expected a study card");
+        };
+        drop(bridge);
+
+        let reopened = BootstrapBridge::create(BootstrapConfig::new(&path)).unwrap();
+        reopened
+            .cards()
+            .delete_cards(DeleteCardsCommand {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new(&profile_id),
+                card_ids: vec![first.id.clone()],
+            })
+            .await
+            .unwrap();
+        let active = reopened
+            .study_sessions()
+            .list_active_sessions(ListActiveStudySessionsQuery {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new(&profile_id),
+            })
+            .await
+            .unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, session.id);
+        assert_eq!(active[0].progress.total_cards, 1);
+        let Some(CurrentCardView::Study(remaining)) = active[0].current_card.clone() else {
+            panic!("expected a study card");
+        };
+        assert_ne!(remaining.id, first.id);
+        let resumed = reopened
+            .study_sessions()
+            .resume_session(ResumeStudySessionQuery {
+                user_id: UserId::new("alice"),
+                session_id: session.id.clone(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(resumed.version, active[0].version);
+        drop(reopened);
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let expiring = BootstrapBridge::create(BootstrapConfig {
+            session_idle_expiry: std::time::Duration::ZERO,
+            ..BootstrapConfig::new(&path)
+        })
+        .unwrap();
+        assert_eq!(
+            expiring
+                .study_sessions()
+                .list_active_sessions(ListActiveStudySessionsQuery {
+                    user_id: UserId::new("alice"),
+                    profile_id: ProfileId::new(&profile_id),
+                })
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            expiring
+                .study_sessions()
+                .expire_idle_sessions()
+                .await
+                .unwrap(),
+            1
+        );
+        assert!(
+            expiring
+                .study_sessions()
+                .list_active_sessions(ListActiveStudySessionsQuery {
+                    user_id: UserId::new("alice"),
+                    profile_id: ProfileId::new(&profile_id),
+                })
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            expiring
+                .study_sessions()
+                .resume_session(ResumeStudySessionQuery {
+                    user_id: UserId::new("alice"),
+                    session_id: session.id,
+                })
+                .await
+                .unwrap_err(),
+            StudySessionError::NotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn multiple_choice_offers_stable_options_with_one_correct_translation() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("choice.db");
+        let (bridge, profile_id) = populated_bridge(&path).await;
+        bridge
+            .cards()
+            .create_cards(CreateCardsCommand {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new(&profile_id),
+                cards: ["one", "two", "three", "four", "five", "reverse"]
+                    .into_iter()
+                    .map(|word| NewCard {
+                        direction: if word == "reverse" {
+                            CardDirection::Reverse
+                        } else {
+                            CardDirection::Straight
+                        },
+                        word: Word {
+                            text: word.to_string(),
+                            readings: Vec::new(),
+                        },
+                        meanings: vec![Meaning {
+                            definition: "definition".to_string(),
+                            translated_definition: String::new(),
+                            word_translations: vec![format!("{word}-translation")],
+                            examples: Vec::new(),
+                        }],
+                    })
+                    .collect(),
+            })
+            .await
+            .unwrap();
+        let session = bridge
+            .study_sessions()
+            .create_session(CreateStudySessionCommand {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new(&profile_id),
+                mode: StudySessionMode::Test,
+                direction: Some(CardDirection::Straight),
+                min_score: None,
+                max_score: None,
+                cards_per_set: None,
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::MultipleChoice,
+                answer_strictness: AnswerStrictness::Tolerant,
+                pronunciation_check_enabled: false,
+                pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
+            })
+            .await
+            .unwrap();
+        let Some(CurrentCardView::Test {
+            prompt, options, ..
+        }) = session.current_card.clone()
+        else {
+            panic!("expected a test card");
+        };
+        assert_eq!(options.len(), 6);
+        assert!(!options.contains(&"reverse-translation".to_string()));
+        let correct = format!("{}-translation", prompt.unwrap());
+        let correct_index = options
+            .iter()
+            .position(|option| *option == correct)
+            .unwrap();
+        let resumed = bridge
+            .study_sessions()
+            .resume_session(ResumeStudySessionQuery {
+                user_id: UserId::new("alice"),
+                session_id: session.id.clone(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(resumed.current_card, session.current_card);
+
+        let transition = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id.clone(),
+                expected_version: session.version,
+                action: StudySessionAction::SelectAnswerOption {
+                    index: correct_index,
+                },
+            })
+            .await
+            .unwrap();
+        let feedback = transition.answer_feedback.unwrap();
+        assert!(feedback.is_correct);
+        assert_eq!(feedback.matched_answer, Some(correct));
+        let next = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id.clone(),
+                expected_version: transition.session.version,
+                action: StudySessionAction::ContinueAfterFeedback,
+            })
+            .await
+            .unwrap()
+            .session;
+        let Some(CurrentCardView::Test {
+            prompt, options, ..
+        }) = next.current_card
+        else {
+            panic!("expected a test card");
+        };
+        let wrong_index = options
+            .iter()
+            .position(|option| *option != format!("{}-translation", prompt.as_deref().unwrap()))
+            .unwrap();
+        let transition = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id.clone(),
+                expected_version: next.version,
+                action: StudySessionAction::SelectAnswerOption { index: wrong_index },
+            })
+            .await
+            .unwrap();
+        assert!(!transition.answer_feedback.unwrap().is_correct);
+        assert_eq!(
+            bridge
+                .study_sessions()
+                .apply_action(ApplyStudySessionActionCommand {
+                    user_id: UserId::new("alice"),
+                    session_id: session.id,
+                    expected_version: transition.session.version,
+                    action: StudySessionAction::SelectAnswerOption { index: 0 },
+                })
+                .await
+                .unwrap_err(),
+            StudySessionError::InvalidAction
+        );
+    }
+
+    #[tokio::test]
+    async fn dictation_needs_speech_generation_to_prefetch_the_queue_audio() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("dictation.db");
+        let (bridge, profile_id) = populated_bridge(&path).await;
+        let command = CreateStudySessionCommand {
+            user_id: UserId::new("alice"),
+            profile_id: ProfileId::new(&profile_id),
+            mode: StudySessionMode::Learning,
+            direction: None,
+            min_score: None,
+            max_score: None,
+            cards_per_set: Some(5),
+            retry_missed_only: None,
+            new_cards_per_day: None,
+            card_order: None,
+            recent_ban_window: None,
+            max_cards: None,
+            time_limit_minutes: None,
+            correct_streak_goal: None,
+            answer_format: AnswerFormat::Dictation,
+            answer_strictness: AnswerStrictness::Tolerant,
+            pronunciation_check_enabled: false,
+            pronunciation_score_threshold: 75,
+            utc_offset_minutes: 0,
+        };
+
+        assert_eq!(
+            bridge
+                .study_sessions()
+                .create_session(command)
+                .await
+                .unwrap_err(),
+            StudySessionError::SpeechNotConfigured
+        );
+        assert!(
+            bridge
+                .study_sessions()
+                .list_active_sessions(ListActiveStudySessionsQuery {
+                    user_id: UserId::new("alice"),
+                    profile_id: ProfileId::new(&profile_id),
+                })
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn cloze_blanks_the_inflected_word_and_falls_back_without_examples() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("cloze.db");
+        let (bridge, _) = populated_bridge(&path).await;
+        let mut profile_ids = Vec::new();
+        for (word, sentence) in [("книга", "Я читаю книгу."), ("окно", "Открой дверь.")]
+        {
+            let profile = bridge
+                .language_profiles()
+                .create_profile(CreateLanguageProfileCommand {
+                    user_id: UserId::new("alice"),
+                    name: word.to_string(),
+                    source_language: "en-US".to_string(),
+                    target_language: "ru-RU".to_string(),
+                })
+                .await
+                .unwrap();
+            bridge
+                .cards()
+                .create_cards(CreateCardsCommand {
+                    user_id: UserId::new("alice"),
+                    profile_id: profile.id.clone(),
+                    cards: vec![NewCard {
+                        direction: CardDirection::Straight,
+                        word: Word {
+                            text: word.to_string(),
+                            readings: Vec::new(),
+                        },
+                        meanings: vec![Meaning {
+                            definition: "definition".to_string(),
+                            translated_definition: String::new(),
+                            word_translations: vec![format!("{word}-translation")],
+                            examples: vec![UsageExample {
+                                sentence: sentence.to_string(),
+                                translation: "translated example".to_string(),
+                            }],
+                        }],
+                    }],
+                })
+                .await
+                .unwrap();
+            profile_ids.push(profile.id);
+        }
+        let command = |profile_id: &ProfileId| CreateStudySessionCommand {
+            user_id: UserId::new("alice"),
+            profile_id: profile_id.clone(),
+            mode: StudySessionMode::Test,
+            direction: None,
+            min_score: None,
+            max_score: None,
+            cards_per_set: None,
+            retry_missed_only: None,
+            new_cards_per_day: None,
+            card_order: None,
+            recent_ban_window: None,
+            max_cards: None,
+            time_limit_minutes: None,
+            correct_streak_goal: None,
+            answer_format: AnswerFormat::Cloze,
+            answer_strictness: AnswerStrictness::Tolerant,
+            pronunciation_check_enabled: true,
+            pronunciation_score_threshold: 75,
+            utc_offset_minutes: 0,
+        };
+
+        let session = bridge
+            .study_sessions()
+            .create_session(command(&profile_ids[0]))
+            .await
+            .unwrap();
+        assert!(!session.pronunciation_check_enabled);
+        let Some(CurrentCardView::Test {
+            prompt,
+            readings,
+            cloze,
+            ..
+        }) = session.current_card
+        else {
+            panic!("expected a test card");
+        };
+        assert_eq!((prompt, readings), (None, Vec::new()));
+        assert_eq!(
+            cloze,
+            Some(ClozePrompt {
+                sentence_before: "Я читаю ".to_string(),
+                sentence_after: ".".to_string(),
+                translation: "translated example".to_string(),
+            })
+        );
+        let feedback = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id,
+                expected_version: session.version,
+                action: StudySessionAction::SubmitWrittenAnswer {
+                    answer: "Книгу".to_string(),
+                },
+            })
+            .await
+            .unwrap()
+            .answer_feedback
+            .unwrap();
+        assert!(feedback.is_correct);
+        assert_eq!(feedback.matched_answer.as_deref(), Some("книгу"));
+
+        let fallback = bridge
+            .study_sessions()
+            .create_session(command(&profile_ids[1]))
+            .await
+            .unwrap();
+        let Some(CurrentCardView::Test { prompt, cloze, .. }) = fallback.current_card else {
+            panic!("expected a test card");
+        };
+        assert_eq!((prompt.as_deref(), cloze), (Some("окно"), None));
+        let feedback = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: fallback.id,
+                expected_version: fallback.version,
+                action: StudySessionAction::SubmitWrittenAnswer {
+                    answer: "окно-translation".to_string(),
+                },
+            })
+            .await
+            .unwrap()
+            .answer_feedback
+            .unwrap();
+        assert_eq!(feedback.matched_meaning_index, Some(0));
+    }
+
+    #[tokio::test]
+    async fn reading_drills_report_misplaced_russian_stress() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("reading.db");
+        let (bridge, _) = populated_bridge(&path).await;
+        let profile = bridge
+            .language_profiles()
+            .create_profile(CreateLanguageProfileCommand {
+                user_id: UserId::new("alice"),
+                name: "Russian".to_string(),
+                source_language: "en-US".to_string(),
+                target_language: "ru-RU".to_string(),
+            })
+            .await
+            .unwrap();
+        bridge
+            .cards()
+            .create_cards(CreateCardsCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id.clone(),
+                cards: vec![NewCard {
+                    direction: CardDirection::Straight,
+                    word: Word {
+                        text: "молоко".to_string(),
+                        readings: vec!["молоко\u{301}".to_string()],
+                    },
+                    meanings: vec![Meaning {
+                        definition: "напиток".to_string(),
+                        translated_definition: "a drink".to_string(),
+                        word_translations: vec!["milk".to_string()],
+                        examples: Vec::new(),
+                    }],
+                }],
+            })
+            .await
+            .unwrap();
+        let session = bridge
+            .study_sessions()
+            .create_session(CreateStudySessionCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id,
+                mode: StudySessionMode::Test,
+                direction: None,
+                min_score: None,
+                max_score: None,
+                cards_per_set: None,
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Reading,
+                answer_strictness: AnswerStrictness::Tolerant,
+                pronunciation_check_enabled: false,
+                pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
+            })
+            .await
+            .unwrap();
+        let Some(CurrentCardView::Test {
+            prompt, readings, ..
+        }) = session.current_card
+        else {
+            panic!("expected a test card");
+        };
+        assert_eq!((prompt.as_deref(), readings), (Some("молоко"), Vec::new()));
+
+        let transition = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id.clone(),
+                expected_version: session.version,
+                action: StudySessionAction::SubmitWrittenAnswer {
+                    answer: "моло\u{301}ко".to_string(),
+                },
+            })
+            .await
+            .unwrap();
+        let feedback = transition.answer_feedback.unwrap();
+        assert!(!feedback.is_correct);
+        assert!(feedback.misplaced_stress);
+        let next = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id.clone(),
+                expected_version: transition.session.version,
+                action: StudySessionAction::ContinueAfterFeedback,
+            })
+            .await
+            .unwrap()
+            .session;
+        let feedback = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id,
+                expected_version: next.version,
+                action: StudySessionAction::SubmitWrittenAnswer {
+                    answer: "молоко'".to_string(),
+                },
+            })
+            .await
+            .unwrap()
+            .answer_feedback
+            .unwrap();
+        assert!(feedback.is_correct);
+        assert!(!feedback.misplaced_stress);
+        assert_eq!(feedback.matched_answer.as_deref(), Some("молоко\u{301}"));
+    }
+
+    #[tokio::test]
+    async fn reverse_answers_are_normalized_in_the_target_language() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("normalization.db");
+        let (bridge, _) = populated_bridge(&path).await;
+        let profile = bridge
+            .language_profiles()
+            .create_profile(CreateLanguageProfileCommand {
+                user_id: UserId::new("alice"),
+                name: "Russian".to_string(),
+                source_language: "en-US".to_string(),
+                target_language: "ru-RU".to_string(),
+            })
+            .await
+            .unwrap();
+        bridge
+            .cards()
+            .create_cards(CreateCardsCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id.clone(),
+                cards: vec![NewCard {
+                    direction: CardDirection::Reverse,
+                    word: Word {
+                        text: "hedgehog".to_string(),
+                        readings: Vec::new(),
+                    },
+                    meanings: vec![Meaning {
+                        definition: "a small spiny mammal".to_string(),
+                        translated_definition: String::new(),
+                        word_translations: vec!["ёж".to_string()],
+                        examples: Vec::new(),
+                    }],
+                }],
+            })
+            .await
+            .unwrap();
+        let session = bridge
+            .study_sessions()
+            .create_session(CreateStudySessionCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id,
+                mode: StudySessionMode::Test,
+                direction: Some(CardDirection::Reverse),
+                min_score: None,
+                max_score: None,
+                cards_per_set: None,
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
+                pronunciation_check_enabled: false,
+                pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
+            })
+            .await
+            .unwrap();
+
+        let feedback = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id,
+                expected_version: session.version,
+                action: StudySessionAction::SubmitWrittenAnswer {
+                    answer: "еж".to_string(),
+                },
+            })
+            .await
+            .unwrap()
+            .answer_feedback
+            .unwrap();
+
+        assert!(feedback.is_correct);
+        assert_eq!(feedback.normalizations, vec![AnswerNormalization::FoldYo]);
+    }
+
+    #[tokio::test]
+    async fn exact_sessions_ask_to_retype_almost_correct_answers_once() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("strictness.db");
+        let (bridge, _) = populated_bridge(&path).await;
+        let profile = bridge
+            .language_profiles()
+            .create_profile(CreateLanguageProfileCommand {
+                user_id: UserId::new("alice"),
+                name: "Strictness".to_string(),
+                source_language: "en-US".to_string(),
+                target_language: "ru-RU".to_string(),
+            })
+            .await
+            .unwrap();
+        bridge
+            .cards()
+            .create_cards(CreateCardsCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id.clone(),
+                cards: vec![NewCard {
+                    direction: CardDirection::Straight,
+                    word: Word {
+                        text: "слон".to_string(),
+                        readings: Vec::new(),
+                    },
+                    meanings: vec![Meaning {
+                        definition: "a large animal with a trunk".to_string(),
+                        translated_definition: String::new(),
+                        word_translations: vec!["elephant".to_string()],
+                        examples: Vec::new(),
+                    }],
+                }],
+            })
+            .await
+            .unwrap();
+        let command = |profile_id: &ProfileId| CreateStudySessionCommand {
+            user_id: UserId::new("alice"),
+            profile_id: profile_id.clone(),
+            mode: StudySessionMode::Test,
+            direction: None,
+            min_score: None,
+            max_score: None,
+            cards_per_set: None,
+            retry_missed_only: None,
+            new_cards_per_day: None,
+            card_order: None,
+            recent_ban_window: None,
+            max_cards: None,
+            time_limit_minutes: None,
+            correct_streak_goal: None,
+            answer_format: AnswerFormat::Written,
+            answer_strictness: AnswerStrictness::Exact,
+            pronunciation_check_enabled: false,
+            pronunciation_score_threshold: 75,
+            utc_offset_minutes: 0,
+        };
+        let submit = |session: &StudySessionView, answer: &str| ApplyStudySessionActionCommand {
+            user_id: UserId::new("alice"),
+            session_id: session.id.clone(),
+            expected_version: session.version,
+            action: StudySessionAction::SubmitWrittenAnswer {
+                answer: answer.to_string(),
+            },
+        };
+
+        let session = bridge
+            .study_sessions()
+            .create_session(command(&profile.id))
+            .await
+            .unwrap();
+        assert_eq!(session.answer_strictness, AnswerStrictness::Exact);
+        let almost = bridge
+            .study_sessions()
+            .apply_action(submit(&session, "elephent"))
+            .await
+            .unwrap();
+        let feedback = almost.answer_feedback.unwrap();
+        assert_eq!(feedback.outcome, AnswerOutcome::AlmostCorrect);
+        assert!(!feedback.is_correct);
+        assert!(!feedback.card_completed);
+        assert_eq!(feedback.score_delta, 0);
+        assert_eq!(feedback.matched_answer.as_deref(), Some("elephant"));
+        assert_eq!(feedback.similarity_percent, 87);
+        assert!(almost.session.retype_pending);
+        assert!(!almost.session.awaiting_continue);
+
+        let retyped = bridge
+            .study_sessions()
+            .apply_action(submit(&almost.session, "elephant"))
+            .await
+            .unwrap();
+        let feedback = retyped.answer_feedback.unwrap();
+        assert_eq!(feedback.outcome, AnswerOutcome::Correct);
+        assert!(feedback.card_completed);
+        assert!(!retyped.session.retype_pending);
+
+        let session = bridge
+            .study_sessions()
+            .create_session(command(&profile.id))
+            .await
+            .unwrap();
+        let almost = bridge
+            .study_sessions()
+            .apply_action(submit(&session, "elephent"))
+            .await
+            .unwrap();
+        let again = bridge
+            .study_sessions()
+            .apply_action(submit(&almost.session, "elephent"))
+            .await
+            .unwrap();
+        let feedback = again.answer_feedback.unwrap();
+        assert_eq!(feedback.outcome, AnswerOutcome::Incorrect);
+        assert!(feedback.card_completed);
+    }
+
+    #[tokio::test]
+    async fn hints_are_revealed_in_order_and_reduce_the_credit() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("hints.db");
+        let (bridge, _) = populated_bridge(&path).await;
+        let profile = bridge
+            .language_profiles()
+            .create_profile(CreateLanguageProfileCommand {
+                user_id: UserId::new("alice"),
+                name: "Hints".to_string(),
+                source_language: "en-US".to_string(),
+                target_language: "ru-RU".to_string(),
+            })
+            .await
+            .unwrap();
+        bridge
+            .cards()
+            .create_cards(CreateCardsCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id.clone(),
+                cards: vec![NewCard {
+                    direction: CardDirection::Straight,
+                    word: Word {
+                        text: "кошка".to_string(),
+                        readings: Vec::new(),
+                    },
+                    meanings: vec![Meaning {
+                        definition: "домашнее животное".to_string(),
+                        translated_definition: "a small domestic animal".to_string(),
+                        word_translations: vec!["cat".to_string()],
+                        examples: vec![UsageExample {
+                            sentence: "Кошка спит.".to_string(),
+                            translation: "The cat is sleeping.".to_string(),
+                        }],
+                    }],
+                }],
+            })
+            .await
+            .unwrap();
+        let command = CreateStudySessionCommand {
+            user_id: UserId::new("alice"),
+            profile_id: profile.id.clone(),
+            mode: StudySessionMode::Test,
+            direction: None,
+            min_score: None,
+            max_score: None,
+            cards_per_set: None,
+            retry_missed_only: None,
+            new_cards_per_day: None,
+            card_order: None,
+            recent_ban_window: None,
+            max_cards: None,
+            time_limit_minutes: None,
+            correct_streak_goal: None,
+            answer_format: AnswerFormat::Written,
+            answer_strictness: AnswerStrictness::Tolerant,
+            pronunciation_check_enabled: false,
+            pronunciation_score_threshold: 75,
+            utc_offset_minutes: 0,
+        };
+        let action = |session: &StudySessionView, action| ApplyStudySessionActionCommand {
+            user_id: UserId::new("alice"),
+            session_id: session.id.clone(),
+            expected_version: session.version,
+            action,
+        };
+        let hints = |session: &StudySessionView| match &session.current_card {
+            Some(CurrentCardView::Test {
+                hints,
+                hint_available,
+                ..
+            }) => (
+                hints
+                    .iter()
+                    .map(|hint| hint.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join("|"),
+                *hint_available,
+            ),
+            _ => panic!("expected a test card"),
+        };
+
+        let mut session = bridge
+            .study_sessions()
+            .create_session(command.clone())
+            .await
+            .unwrap();
+        assert_eq!(hints(&session), (String::new(), true));
+        for _ in 0..4 {
+            session = bridge
+                .study_sessions()
+                .apply_action(action(&session, StudySessionAction::RevealHint))
+                .await
+                .unwrap()
+                .session;
+        }
+        assert_eq!(
+            hints(&session),
+            (
+                "c|3|a small domestic animal|The cat is sleeping.".to_string(),
+                false
+            )
+        );
+        assert_eq!(
+            bridge
+                .study_sessions()
+                .apply_action(action(&session, StudySessionAction::RevealHint))
+                .await
+                .unwrap_err(),
+            StudySessionError::InvalidAction
+        );
+        let answered = bridge
+            .study_sessions()
+            .apply_action(action(
+                &session,
+                StudySessionAction::SubmitWrittenAnswer {
+                    answer: "cat".to_string(),
+                },
+            ))
+            .await
+            .unwrap();
+        let feedback = answered.answer_feedback.unwrap();
+        assert!(feedback.is_correct);
+        assert_eq!(feedback.score_delta, 0);
+        assert_eq!(
+            bridge
+                .study_sessions()
+                .apply_action(action(&answered.session, StudySessionAction::RevealHint))
+                .await
+                .unwrap_err(),
+            StudySessionError::InvalidAction,
+            "hints are only offered before the card is answered"
+        );
+
+        let session = bridge
+            .study_sessions()
+            .create_session(command)
+            .await
+            .unwrap();
+        let answered = bridge
+            .study_sessions()
+            .apply_action(action(
+                &session,
+                StudySessionAction::SubmitWrittenAnswer {
+                    answer: "cat".to_string(),
+                },
+            ))
+            .await
+            .unwrap();
+        assert_eq!(answered.answer_feedback.unwrap().score_delta, 1);
+    }
+
+    #[tokio::test]
+    async fn giving_up_fails_the_card_and_reveals_it() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("give-up.db");
+        let (bridge, _) = populated_bridge(&path).await;
+        let profile = bridge
+            .language_profiles()
+            .create_profile(CreateLanguageProfileCommand {
+                user_id: UserId::new("alice"),
+                name: "Give up".to_string(),
+                source_language: "en-US".to_string(),
+                target_language: "ru-RU".to_string(),
+            })
+            .await
+            .unwrap();
+        bridge
+            .cards()
+            .create_cards(CreateCardsCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id.clone(),
+                cards: vec![NewCard {
+                    direction: CardDirection::Straight,
+                    word: Word {
+                        text: "собака".to_string(),
+                        readings: Vec::new(),
+                    },
+                    meanings: vec![Meaning {
+                        definition: "домашнее животное".to_string(),
+                        translated_definition: String::new(),
+                        word_translations: vec!["dog".to_string()],
+                        examples: Vec::new(),
+                    }],
+                }],
+            })
+            .await
+            .unwrap();
+        let session = bridge
+            .study_sessions()
+            .create_session(CreateStudySessionCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id.clone(),
+                mode: StudySessionMode::Test,
+                direction: None,
+                min_score: None,
+                max_score: None,
+                cards_per_set: None,
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
+                pronunciation_check_enabled: false,
+                pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
+            })
+            .await
+            .unwrap();
+        let action = |session: &StudySessionView, action| ApplyStudySessionActionCommand {
+            user_id: UserId::new("alice"),
+            session_id: session.id.clone(),
+            expected_version: session.version,
+            action,
+        };
+
+        let gave_up = bridge
+            .study_sessions()
+            .apply_action(action(&session, StudySessionAction::GiveUp))
+            .await
+            .unwrap();
+        let feedback = gave_up.answer_feedback.unwrap();
+        assert_eq!(feedback.outcome, AnswerOutcome::GaveUp);
+        assert!(!feedback.is_correct && feedback.card_completed);
+        assert_eq!(feedback.matched_answer, None);
+        assert_eq!(feedback.score_delta, -2);
+        assert_eq!(feedback.card.meanings[0].word_translations, vec!["dog"]);
+        assert_eq!(gave_up.session.summary.incorrect, 1);
+        assert_eq!(
+            bridge
+                .study_sessions()
+                .apply_action(action(
+                    &gave_up.session,
+                    StudySessionAction::AcceptLastAnswer {
+                        add_to_translations: false,
+                    },
+                ))
+                .await
+                .unwrap_err(),
+            StudySessionError::InvalidAction,
+            "there is no rejected answer to accept"
+        );
+        assert_eq!(
+            bridge
+                .study_sessions()
+                .apply_action(action(&gave_up.session, StudySessionAction::GiveUp))
+                .await
+                .unwrap_err(),
+            StudySessionError::InvalidAction
+        );
+    }
+
+    #[tokio::test]
+    async fn bounded_test_sessions_report_their_budget_and_complete_at_the_limit() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("bounded.db");
+        let (bridge, profile_id) = populated_bridge(&path).await;
+        let command = CreateStudySessionCommand {
+            user_id: UserId::new("alice"),
+            profile_id: ProfileId::new(profile_id),
+            mode: StudySessionMode::Test,
+            direction: None,
+            min_score: None,
+            max_score: None,
+            cards_per_set: None,
+            retry_missed_only: None,
+            new_cards_per_day: None,
+            card_order: None,
+            recent_ban_window: Some(0),
+            max_cards: Some(2),
+            time_limit_minutes: Some(10),
+            correct_streak_goal: None,
+            answer_format: AnswerFormat::Written,
+            answer_strictness: AnswerStrictness::Tolerant,
+            pronunciation_check_enabled: false,
+            pronunciation_score_threshold: 75,
+            utc_offset_minutes: 0,
+        };
+        assert_eq!(
+            bridge
+                .study_sessions()
+                .create_session(CreateStudySessionCommand {
+                    mode: StudySessionMode::Learning,
+                    cards_per_set: Some(1),
+                    recent_ban_window: None,
+                    ..command.clone()
+                })
+                .await
+                .unwrap_err(),
+            StudySessionError::InvalidSession
+        );
+        let mut session = bridge
+            .study_sessions()
+            .create_session(command)
+            .await
+            .unwrap();
+        assert_eq!(session.progress.total_cards, 2);
+        let budget = session.remaining_budget.unwrap();
+        assert_eq!(budget.cards, Some(2));
+        assert!(budget.time_millis.is_some_and(|millis| millis <= 600_000));
+        assert_eq!(budget.correct_in_a_row, None);
+
+        for remaining in [1, 0] {
+            for action in [
+                StudySessionAction::GiveUp,
+                StudySessionAction::ContinueAfterFeedback,
+            ] {
+                session = bridge
+                    .study_sessions()
+                    .apply_action(ApplyStudySessionActionCommand {
+                        user_id: UserId::new("alice"),
+                        session_id: session.id.clone(),
+                        expected_version: session.version,
+                        action,
+                    })
+                    .await
+                    .unwrap()
+                    .session;
+            }
+            assert_eq!(session.remaining_budget.unwrap().cards, Some(remaining));
+        }
+        assert_eq!(session.status, StudySessionStatus::Completed);
+        assert_eq!(session.summary.incorrect, 2);
+    }
+
+    #[tokio::test]
+    async fn leeches_are_suspended_until_the_learner_returns_them() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("leeches.db");
+        let (bridge, _) = populated_bridge(&path).await;
+        let profile = bridge
+            .language_profiles()
+            .create_profile(CreateLanguageProfileCommand {
+                user_id: UserId::new("alice"),
+                name: "Leeches".to_string(),
+                source_language: "en-US".to_string(),
+                target_language: "ru-RU".to_string(),
+            })
+            .await
+            .unwrap();
+        let profile = bridge
+            .language_profiles()
+            .update_profile(UpdateLanguageProfileCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id,
+                expected_version: profile.version,
+                changes: LanguageProfileChanges {
+                    leech_threshold: Some(2),
+                    leech_action: Some(LeechAction::Suspend),
+                    ..LanguageProfileChanges::default()
+                },
+            })
+            .await
+            .unwrap();
+        let card = bridge
+            .cards()
+            .create_cards(CreateCardsCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id.clone(),
+                cards: vec![NewCard {
+                    direction: CardDirection::Straight,
+                    word: Word {
+                        text: "слон".to_string(),
+                        readings: Vec::new(),
+                    },
+                    meanings: vec![Meaning {
+                        definition: "a large animal with a trunk".to_string(),
+                        translated_definition: String::new(),
+                        word_translations: vec!["elephant".to_string()],
+                        examples: Vec::new(),
+                    }],
+                }],
+            })
+            .await
+            .unwrap()
+            .remove(0);
+        let command = CreateStudySessionCommand {
+            user_id: UserId::new("alice"),
+            profile_id: profile.id.clone(),
+            mode: StudySessionMode::Test,
+            direction: None,
+            min_score: None,
+            max_score: None,
+            cards_per_set: None,
+            retry_missed_only: None,
+            new_cards_per_day: None,
+            card_order: None,
+            recent_ban_window: None,
+            max_cards: None,
+            time_limit_minutes: None,
+            correct_streak_goal: None,
+            answer_format: AnswerFormat::Written,
+            answer_strictness: AnswerStrictness::Tolerant,
+            pronunciation_check_enabled: false,
+            pronunciation_score_threshold: 75,
+            utc_offset_minutes: 0,
+        };
+        let action = |session: &StudySessionView, action| ApplyStudySessionActionCommand {
+            user_id: UserId::new("alice"),
+            session_id: session.id.clone(),
+            expected_version: session.version,
+            action,
+        };
+        let wrong = || StudySessionAction::SubmitWrittenAnswer {
+            answer: "giraffe".to_string(),
+        };
+
+        let session = bridge
+            .study_sessions()
+            .create_session(command.clone())
+            .await
+            .unwrap();
+        let failed = bridge
+            .study_sessions()
+            .apply_action(action(&session, wrong()))
+            .await
+            .unwrap();
+        let next = bridge
+            .study_sessions()
+            .apply_action(action(
+                &failed.session,
+                StudySessionAction::ContinueAfterFeedback,
+            ))
+            .await
+            .unwrap();
+        let failed = bridge
+            .study_sessions()
+            .apply_action(action(&next.session, wrong()))
+            .await
+            .unwrap();
+        let leech = bridge
+            .cards()
+            .get_card(GetCardQuery {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id.clone(),
+                card_id: card.id.clone(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(leech.leech.consecutive_failures, 2);
+        assert!(leech.leech.is_leech);
+        assert_eq!(leech.status, CardStatus::Suspended);
+        let finished = bridge
+            .study_sessions()
+            .apply_action(action(
+                &failed.session,
+                StudySessionAction::ContinueAfterFeedback,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(finished.session.status, StudySessionStatus::Completed);
+        assert_eq!(
+            bridge
+                .study_sessions()
+                .create_session(command.clone())
+                .await,
+            Err(StudySessionError::NoCardsAvailable)
+        );
+
+        bridge
+            .cards()
+            .set_card_status(SetCardStatusCommand {
+                user_id: UserId::new("alice"),
+                profile_id: profile.id.clone(),
+                card_ids: vec![card.id],
+                status: CardStatus::Active,
+            })
+            .await
+            .unwrap();
+        assert!(
+            bridge
+                .study_sessions()
+                .create_session(command)
+                .await
+                .is_ok()
+        );
     }
 
     #[tokio::test]
@@ -935,8 +2928,18 @@ mod tests {
                 min_score: None,
                 max_score: None,
                 cards_per_set: None,
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
                 pronunciation_check_enabled: false,
                 pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
             })
             .await
             .unwrap();
@@ -0,0 +1,491 @@
+use application::ports::input::{
+    card_catalog::models::CardDirection,
+    language_profile::models::ProfileId,
+    local_user::models::UserId,
+    study_statistics::{
+        StudyStatisticsUsecase,
+        models::{
+            AccuracyPoint, DailyReviewCount, HardCard, HardestCardsQuery, ProfileStatisticsQuery,
+            ScoreBucket, StatisticsPeriodQuery, StudyStreak, StudyStreakQuery,
+        },
+    },
+};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::{error::CommandError, state::DesktopState};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatisticsPeriodDto {
+    username: String,
+    profile_id: String,
+    since: i64,
+    utc_offset_minutes: i32,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccuracyPointDto {
+    day_start: i64,
+    answers: usize,
+    correct: usize,
+    average_response_millis: Option<u64>,
+}
+
+impl From<AccuracyPoint> for AccuracyPointDto {
+    fn from(point: AccuracyPoint) -> Self {
+        Self {
+            day_start: point.day_start,
+            answers: point.answers,
+            correct: point.correct,
+            average_response_millis: point.average_response_millis,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyReviewCountDto {
+    day_start: i64,
+    cards: usize,
+}
+
+impl From<DailyReviewCount> for DailyReviewCountDto {
+    fn from(count: DailyReviewCount) -> Self {
+        Self {
+            day_start: count.day_start,
+            cards: count.cards,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreBucketDto {
+    score: i32,
+    cards: usize,
+}
+
+impl From<ScoreBucket> for ScoreBucketDto {
+    fn from(bucket: ScoreBucket) -> Self {
+        Self {
+            score: bucket.score,
+            cards: bucket.cards,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StudyStreakDto {
+    current_days: usize,
+    longest_days: usize,
+    studied_today: bool,
+}
+
+impl From<StudyStreak> for StudyStreakDto {
+    fn from(streak: StudyStreak) -> Self {
+        Self {
+            current_days: streak.current_days,
+            longest_days: streak.longest_days,
+            studied_today: streak.studied_today,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HardCardDto {
+    card_id: String,
+    word: String,
+    direction: String,
+    score: i32,
+    answers: usize,
+    failures: usize,
+}
+
+impl From<HardCard> for HardCardDto {
+    fn from(card: HardCard) -> Self {
+        Self {
+            card_id: card.card_id.into_inner(),
+            word: card.word,
+            direction: match card.direction {
+                CardDirection::Straight => "straight",
+                CardDirection::Reverse => "reverse",
+            }
+            .to_string(),
+            score: card.score,
+            answers: card.answers,
+            failures: card.failures,
+        }
+    }
+}
+
+fn period_query(request: StatisticsPeriodDto) -> StatisticsPeriodQuery {
+    StatisticsPeriodQuery {
+        user_id: UserId::new(request.username),
+        profile_id: ProfileId::new(request.profile_id),
+        since: request.since,
+        utc_offset_minutes: request.utc_offset_minutes,
+    }
+}
+
+async fn accuracy_over_time(
+    usecase: &dyn StudyStatisticsUsecase,
+    request: StatisticsPeriodDto,
+) -> Result<Vec<AccuracyPointDto>, CommandError> {
+    usecase
+        .accuracy_over_time(period_query(request))
+        .await
+        .map(|points| points.into_iter().map(Into::into).collect())
+        .map_err(Into::into)
+}
+
+async fn cards_reviewed_per_day(
+    usecase: &dyn StudyStatisticsUsecase,
+    request: StatisticsPeriodDto,
+) -> Result<Vec<DailyReviewCountDto>, CommandError> {
+    usecase
+        .cards_reviewed_per_day(period_query(request))
+        .await
+        .map(|counts| counts.into_iter().map(Into::into).collect())
+        .map_err(Into::into)
+}
+
+async fn score_distribution(
+    usecase: &dyn StudyStatisticsUsecase,
+    username: String,
+    profile_id: String,
+) -> Result<Vec<ScoreBucketDto>, CommandError> {
+    usecase
+        .score_distribution(ProfileStatisticsQuery {
+            user_id: UserId::new(username),
+            profile_id: ProfileId::new(profile_id),
+        })
+        .await
+        .map(|buckets| buckets.into_iter().map(Into::into).collect())
+        .map_err(Into::into)
+}
+
+async fn study_streak(
+    usecase: &dyn StudyStatisticsUsecase,
+    username: String,
+    profile_id: String,
+    utc_offset_minutes: i32,
+) -> Result<StudyStreakDto, CommandError> {
+    usecase
+        .streak(StudyStreakQuery {
+            user_id: UserId::new(username),
+            profile_id: ProfileId::new(profile_id),
+            utc_offset_minutes,
+        })
+        .await
+        .map(Into::into)
+        .map_err(Into::into)
+}
+
+async fn hardest_cards(
+    usecase: &dyn StudyStatisticsUsecase,
+    username: String,
+    profile_id: String,
+    limit: usize,
+) -> Result<Vec<HardCardDto>, CommandError> {
+    usecase
+        .hardest_cards(HardestCardsQuery {
+            user_id: UserId::new(username),
+            profile_id: ProfileId::new(profile_id),
+            limit,
+        })
+        .await
+        .map(|cards| cards.into_iter().map(Into::into).collect())
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_accuracy_over_time(
+    state: State<'_, DesktopState>,
+    request: StatisticsPeriodDto,
+) -> Result<Vec<AccuracyPointDto>, CommandError> {
+    accuracy_over_time(state.study_statistics().as_ref(), request).await
+}
+
+#[tauri::command]
+pub async fn get_cards_reviewed_per_day(
+    state: State<'_, DesktopState>,
+    request: StatisticsPeriodDto,
+) -> Result<Vec<DailyReviewCountDto>, CommandError> {
+    cards_reviewed_per_day(state.study_statistics().as_ref(), request).await
+}
+
+#[tauri::command]
+pub async fn get_score_distribution(
+    state: State<'_, DesktopState>,
+    username: String,
+    profile_id: String,
+) -> Result<Vec<ScoreBucketDto>, CommandError> {
+    score_distribution(state.study_statistics().as_ref(), username, profile_id).await
+}
+
+#[tauri::command]
+pub async fn get_study_streak(
+    state: State<'_, DesktopState>,
+    username: String,
+    profile_id: String,
+    utc_offset_minutes: i32,
+) -> Result<StudyStreakDto, CommandError> {
+    study_streak(
+        state.study_statistics().as_ref(),
+        username,
+        profile_id,
+        utc_offset_minutes,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_hardest_cards(
+    state: State<'_, DesktopState>,
+    username: String,
+    profile_id: String,
+    limit: usize,
+) -> Result<Vec<HardCardDto>, CommandError> {
+    hardest_cards(
+        state.study_statistics().as_ref(),
+        username,
+        profile_id,
+        limit,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use application::ports::input::{
+        card_catalog::models::{CreateCardsCommand, Meaning, NewCard, Word},
+        language_profile::models::CreateLanguageProfileCommand,
+        local_user::models::CreateLocalUserCommand,
+        study_session::models::{
+            AnswerFormat, AnswerStrictness, ApplyStudySessionActionCommand,
+            CreateStudySessionCommand, StudySessionAction, StudySessionMode, StudySessionView,
+        },
+    };
+    use lh_bootstrap::{BootstrapBridge, BootstrapConfig};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn statistics_reflect_answers_given_in_a_study_session() {
+        let directory = TempDir::new().unwrap();
+        let bridge =
+            BootstrapBridge::create(BootstrapConfig::new(directory.path().join("stats.db")))
+                .unwrap();
+        bridge
+            .local_users()
+            .create_user(CreateLocalUserCommand {
+                username: "alice".to_string(),
+            })
+            .await
+            .unwrap();
+        let profile = bridge
+            .language_profiles()
+            .create_profile(CreateLanguageProfileCommand {
+                user_id: UserId::new("alice"),
+                name: "Japanese".to_string(),
+                source_language: "en-US".to_string(),
+                target_language: "ja-JP".to_string(),
+            })
+            .await
+            .unwrap();
+        let profile_id = profile.id.into_inner();
+        bridge
+            .cards()
+            .create_cards(CreateCardsCommand {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new(&profile_id),
+                cards: vec![NewCard {
+                    direction: CardDirection::Straight,
+                    word: Word {
+                        text: "水".to_string(),
+                        readings: vec!["みず".to_string()],
+                    },
+                    meanings: vec![Meaning {
+                        definition: "water".to_string(),
+                        translated_definition: String::new(),
+                        word_translations: vec!["water".to_string()],
+                        examples: Vec::new(),
+                    }],
+                }],
+            })
+            .await
+            .unwrap();
+        let session = bridge
+            .study_sessions()
+            .create_session(CreateStudySessionCommand {
+                user_id: UserId::new("alice"),
+                profile_id: ProfileId::new(&profile_id),
+                mode: StudySessionMode::Test,
+                direction: None,
+                min_score: None,
+                max_score: None,
+                cards_per_set: None,
+                retry_missed_only: None,
+                new_cards_per_day: None,
+                card_order: None,
+                recent_ban_window: None,
+                max_cards: None,
+                time_limit_minutes: None,
+                correct_streak_goal: None,
+                answer_format: AnswerFormat::Written,
+                answer_strictness: AnswerStrictness::Tolerant,
+                pronunciation_check_enabled: false,
+                pronunciation_score_threshold: 75,
+                utc_offset_minutes: 0,
+            })
+            .await
+            .unwrap();
+        let answered = bridge
+            .study_sessions()
+            .apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id,
+                expected_version: session.version,
+                action: StudySessionAction::SubmitWrittenAnswer {
+                    answer: "fire".to_string(),
+                },
+            })
+            .await
+            .unwrap()
+            .session;
+        let statistics = bridge.study_statistics();
+        let period = || StatisticsPeriodDto {
+            username: "alice".to_string(),
+            profile_id: profile_id.clone(),
+            since: 0,
+            utc_offset_minutes: 180,
+        };
+
+        let accuracy = accuracy_over_time(statistics.as_ref(), period())
+            .await
+            .unwrap();
+        assert_eq!(accuracy.len(), 1);
+        assert_eq!((accuracy[0].answers, accuracy[0].correct), (1, 0));
+        assert_eq!(
+            cards_reviewed_per_day(statistics.as_ref(), period())
+                .await
+                .unwrap()[0]
+                .cards,
+            1
+        );
+        assert_eq!(
+            score_distribution(statistics.as_ref(), "alice".to_string(), profile_id.clone())
+                .await
+                .unwrap(),
+            vec![ScoreBucketDto {
+                score: -2,
+                cards: 1
+            }]
+        );
+        assert_eq!(
+            study_streak(
+                statistics.as_ref(),
+                "alice".to_string(),
+                profile_id.clone(),
+                180
+            )
+            .await
+            .unwrap(),
+            StudyStreakDto {
+                current_days: 1,
+                longest_days: 1,
+                studied_today: true,
+            }
+        );
+        let hardest = hardest_cards(
+            statistics.as_ref(),
+            "alice".to_string(),
+            profile_id.clone(),
+            10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(hardest[0].word, "水");
+        assert_eq!((hardest[0].answers, hardest[0].failures), (1, 1));
+        assert_eq!(
+            hardest_cards(
+                statistics.as_ref(),
+                "bob".to_string(),
+                profile_id.clone(),
+                10
+            )
+            .await
+            .unwrap_err()
+            .code(),
+            "language_profile_not_found"
+        );
+
+        let sessions = bridge.study_sessions();
+        let act = |session: &StudySessionView, action| {
+            sessions.apply_action(ApplyStudySessionActionCommand {
+                user_id: UserId::new("alice"),
+                session_id: session.id.clone(),
+                expected_version: session.version,
+                action,
+            })
+        };
+        let accepted = act(
+            &answered,
+            StudySessionAction::AcceptLastAnswer {
+                add_to_translations: false,
+            },
+        )
+        .await
+        .unwrap()
+        .session;
+        let accuracy = accuracy_over_time(statistics.as_ref(), period())
+            .await
+            .unwrap();
+        assert_eq!(
+            (accuracy[0].answers, accuracy[0].correct),
+            (1, 1),
+            "an accepted answer is counted once, as correct"
+        );
+        assert!(
+            hardest_cards(
+                statistics.as_ref(),
+                "alice".to_string(),
+                profile_id.clone(),
+                10
+            )
+            .await
+            .unwrap()
+            .is_empty()
+        );
+
+        let restored = act(&accepted, StudySessionAction::UndoLastAction)
+            .await
+            .unwrap()
+            .session;
+        let accuracy = accuracy_over_time(statistics.as_ref(), period())
+            .await
+            .unwrap();
+        assert_eq!((accuracy[0].answers, accuracy[0].correct), (1, 0));
+        act(&restored, StudySessionAction::UndoLastAction)
+            .await
+            .unwrap();
+        assert!(
+            accuracy_over_time(statistics.as_ref(), period())
+                .await
+                .unwrap()
+                .is_empty(),
+            "undone answers are not counted"
+        );
+        assert!(
+            hardest_cards(statistics.as_ref(), "alice".to_string(), profile_id, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -3,7 +3,7 @@ use application::ports::input::{
     card_normalization::models::CardNormalizationError, card_speech::models::CardSpeechError,
     language_profile::models::LanguageProfileError, local_user::models::LocalUserError,
     pronunciation_settings::models::PronunciationSettingsError,
-    study_session::models::StudySessionError,
+    study_session::models::StudySessionError, study_statistics::models::StudyStatisticsError,
 };
 use serde::Serialize;
 
@@ -13,6 +13,13 @@ pub struct CommandError {
     message: String,
 }
 
+impl CommandError {
+    #[cfg(test)]
+    pub(crate) fn code(&self) -> &'static str {
+        self.code
+    }
+}
+
 impl From<CardSpeechError> for CommandError {
     fn from(error: CardSpeechError) -> Self {
         let code = match &error {
@@ -122,6 +129,7 @@ impl From<StudySessionError> for CommandError {
             StudySessionError::InvalidAction => "invalid_study_session_action",
             StudySessionError::Conflict => "study_session_conflict",
             StudySessionError::PronunciationNotConfigured => "pronunciation_not_configured",
+            StudySessionError::SpeechNotConfigured => "ai_not_configured",
             StudySessionError::Unexpected(_) => "unexpected_error",
         };
         Self {
@@ -144,3 +152,17 @@ impl From<PronunciationSettingsError> for CommandError {
         }
     }
 }
+
+impl From<StudyStatisticsError> for CommandError {
+    fn from(error: StudyStatisticsError) -> Self {
+        let code = match &error {
+            StudyStatisticsError::InvalidQuery => "invalid_statistics_query",
+            StudyStatisticsError::NotFound => "language_profile_not_found",
+            StudyStatisticsError::Unexpected(_) => "unexpected_error",
+        };
+        Self {
+            code,
+            message: error.to_string(),
+        }
+    }
+}
@@ -36,6 +36,7 @@ pub fn run() {
             #[cfg(not(target_os = "windows"))]
             let bridge = BootstrapBridge::create(BootstrapConfig::new(database_path(app)?))?;
 
+            tauri::async_runtime::block_on(bridge.study_sessions().expire_idle_sessions())?;
             app.manage(state::DesktopState::new(bridge));
             Ok(())
         })
@@ -46,6 +47,8 @@ pub fn run() {
             commands::profiles::list_language_profiles,
             commands::profiles::create_language_profile,
             commands::profiles::delete_language_profile,
+            commands::profiles::get_leech_settings,
+            commands::profiles::save_leech_settings,
             commands::profiles::get_ai_settings,
             commands::profiles::save_ai_settings,
             commands::profiles::get_pronunciation_settings,
@@ -55,16 +58,26 @@ pub fn run() {
             commands::cards::create_cards,
             commands::cards::update_card,
             commands::cards::delete_cards,
+            commands::cards::suspend_cards,
+            commands::cards::unsuspend_cards,
+            commands::cards::bury_cards,
             commands::cards::prepare_inverse_cards,
             commands::cards::save_inverse_cards,
             commands::cards::normalize_card,
             commands::speech::get_card_speech,
             commands::sessions::create_study_session,
             commands::sessions::get_study_session_preferences,
+            commands::sessions::list_active_study_sessions,
+            commands::sessions::resume_study_session,
             commands::sessions::apply_study_session_action,
             commands::sessions::assess_pronunciation,
             commands::sessions::finish_study_session,
-            commands::sessions::cancel_study_session
+            commands::sessions::cancel_study_session,
+            commands::statistics::get_accuracy_over_time,
+            commands::statistics::get_cards_reviewed_per_day,
+            commands::statistics::get_score_distribution,
+            commands::statistics::get_study_streak,
+            commands::statistics::get_hardest_cards
         ])
         .run(tauri::generate_context!())
         .expect("failed to run Language Helper");
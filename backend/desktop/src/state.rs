@@ -5,6 +5,7 @@ use application::ports::input::{
     card_normalization::CardNormalizationUsecase, card_speech::CardSpeechUsecase,
     language_profile::LanguageProfileUsecase, local_user::LocalUserUsecase,
     pronunciation_settings::PronunciationSettingsUsecase, study_session::StudySessionUsecase,
+    study_statistics::StudyStatisticsUsecase,
 };
 use lh_bootstrap::BootstrapBridge;
 
@@ -17,6 +18,7 @@ pub struct DesktopState {
     ai_settings: Arc<dyn AiSettingsUsecase>,
     pronunciation_settings: Arc<dyn PronunciationSettingsUsecase>,
     study_sessions: Arc<dyn StudySessionUsecase>,
+    study_statistics: Arc<dyn StudyStatisticsUsecase>,
 }
 
 impl DesktopState {
@@ -30,6 +32,7 @@ impl DesktopState {
             ai_settings: bridge.ai_settings(),
             pronunciation_settings: bridge.pronunciation_settings(),
             study_sessions: bridge.study_sessions(),
+            study_statistics: bridge.study_statistics(),
         }
     }
 
@@ -64,4 +67,8 @@ impl DesktopState {
     pub fn study_sessions(&self) -> Arc<dyn StudySessionUsecase> {
         Arc::clone(&self.study_sessions)
     }
+
+    pub fn study_statistics(&self) -> Arc<dyn StudyStatisticsUsecase> {
+        Arc::clone(&self.study_statistics)
+    }
 }